@@ -21,6 +21,11 @@ pub enum Kind {
     StopWatch(PlaceId, TransitionId, usize),
     /// Stopwatch inhibitor arc
     StopWatchInhibitor(PlaceId, TransitionId, usize),
+    /// Reset arc, the place is emptied when the transition is fired
+    ///
+    /// The weight does not change the firing, it is only kept so that formats which store one
+    /// (PNML inscriptions, Romeo weights) write it back unchanged.
+    Reset(PlaceId, TransitionId, usize),
 }
 
 impl fmt::Display for Kind {
@@ -56,6 +61,9 @@ impl fmt::Display for Kind {
                     pl_id, w, tr_id
                 )
             }
+            Kind::Reset(pl_id, tr_id, _) => {
+                write!(f, "reset {} for transition {}", pl_id, tr_id)
+            }
         }
     }
 }
@@ -7,8 +7,9 @@ use crate::{arc, PlaceId, TransitionId};
 /// Errors generated when manipulating a Petri net
 #[derive(Debug, Eq, PartialEq)]
 pub enum NetError {
-    /// There is at least one cyclic priority (t1 > t0 > t1 for example)
-    CyclicPriorities,
+    /// There is at least one cyclic priority (t1 > t0 > t1 for example), with the names of the
+    /// transitions of a cycle
+    CyclicPriorities(Vec<String>),
     /// There is an invalid time range in the net
     InvalidTimeRange,
     /// This kind of arc is not supported in ths version of the crate
@@ -23,12 +24,27 @@ pub enum NetError {
     UnknownIdentifier(String),
     /// This net contains an invalid arc (place to place, transition to transition, ...)
     InvalidArc,
+    /// This constraint can not be used (a constraint without any place for example)
+    InvalidConstraint,
+    /// This place can contain more than one token but the operation requires a safe net
+    UnsafePlace(PlaceId),
+    /// This transition can not be fired in the current state
+    NotFirable(TransitionId),
+    /// This transition must be fired before time can elapse
+    UrgentTransition(TransitionId),
+    /// This line of a labelled transition system is not valid, with the number of the line
+    InvalidLts(usize, String),
 }
 
 impl fmt::Display for NetError {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
-            NetError::CyclicPriorities => write!(f, "Cyclic priorities in Petri net"),
+            NetError::CyclicPriorities(cycle) => write!(
+                f,
+                "Cyclic priorities in Petri net: {} > {}",
+                cycle.join(" > "),
+                cycle.first().map_or("", String::as_str)
+            ),
             NetError::InvalidTimeRange => write!(f, "Invalid time range found"),
             NetError::UnsupportedArc(arc) => {
                 write!(f, "Unsupported arc {}", arc)
@@ -44,6 +60,21 @@ impl fmt::Display for NetError {
             NetError::InvalidArc => {
                 write!(f, "Invalid arc in the net")
             }
+            NetError::InvalidConstraint => {
+                write!(f, "Invalid constraint on the net")
+            }
+            NetError::UnsafePlace(pl) => write!(f, "Place {} is not safe", pl),
+            NetError::NotFirable(tr) => write!(f, "Transition {} is not firable", tr),
+            NetError::UrgentTransition(tr) => {
+                write!(f, "Transition {} must be fired before time elapses", tr)
+            }
+            NetError::InvalidLts(line, message) => {
+                write!(
+                    f,
+                    "Invalid labelled transition system at line {}: {}",
+                    line, message
+                )
+            }
         }
     }
 }
@@ -0,0 +1,410 @@
+//! Exact integer linear programming
+//!
+//! This module provides a small solver for integer linear problems. The linear relaxation is
+//! solved with a two-phase simplex using exact rational arithmetic (and Bland's rule to avoid
+//! cycling), then integrality is enforced with a depth-first branch and bound.
+//!
+//! It is meant for the structural analyses of nets (see
+//! [`standard::structural`][`crate::standard::structural`]) and does not try to compete with
+//! dedicated solvers on large problems.
+//!
+//! All variables of a [`Problem`] are non-negative.
+use num_bigint::BigInt;
+use num_traits::{One, Signed, Zero};
+
+pub use num_rational::BigRational;
+
+use crate::predicate::Comparison;
+
+/// Direction of the optimization
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Goal {
+    /// Minimize the objective
+    Minimize,
+    /// Maximize the objective
+    Maximize,
+}
+
+/// Result of the resolution of a [`Problem`]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Solution {
+    /// An optimal solution was found
+    Optimal {
+        /// Value of the objective
+        value: BigRational,
+        /// Value of each variable
+        values: Vec<BigRational>,
+    },
+    /// The problem has no solution
+    Infeasible,
+    /// The objective is not bounded
+    Unbounded,
+    /// The node limit of the branch and bound was reached before the end of the resolution
+    Unknown,
+}
+
+/// Linear constraint `Σ coefficient * variable <comparison> constant`
+#[derive(Debug, Clone)]
+struct Row {
+    coefficients: Vec<(usize, BigRational)>,
+    comparison: Comparison,
+    constant: BigRational,
+}
+
+/// Integer linear problem over non-negative variables
+#[derive(Debug, Clone)]
+pub struct Problem {
+    /// Integrality of each variable
+    integer: Vec<bool>,
+    /// Constraints of the problem
+    rows: Vec<Row>,
+    /// Direction of the optimization
+    goal: Goal,
+    /// Objective coefficients
+    objective: Vec<(usize, BigRational)>,
+    /// Maximum number of nodes explored by the branch and bound
+    node_limit: Option<usize>,
+}
+
+impl Problem {
+    /// Create a new problem with `variables` non-negative integer variables
+    ///
+    /// By default the objective is empty, so any solution is optimal.
+    #[must_use]
+    pub fn new(variables: usize) -> Self {
+        Self {
+            integer: vec![true; variables],
+            rows: vec![],
+            goal: Goal::Minimize,
+            objective: vec![],
+            node_limit: None,
+        }
+    }
+
+    /// Number of variables of the problem
+    #[must_use]
+    pub fn variables(&self) -> usize {
+        self.integer.len()
+    }
+
+    /// Add a new variable and return its index
+    pub fn add_variable(&mut self, integer: bool) -> usize {
+        self.integer.push(integer);
+        self.integer.len() - 1
+    }
+
+    /// Set if a variable must have an integer value
+    pub fn set_integer(&mut self, variable: usize, integer: bool) {
+        self.integer[variable] = integer;
+    }
+
+    /// Set the maximum number of nodes explored by the branch and bound
+    pub fn set_node_limit(&mut self, node_limit: Option<usize>) {
+        self.node_limit = node_limit;
+    }
+
+    /// Add the constraint `Σ coefficient * variable <comparison> constant`
+    pub fn add_constraint(
+        &mut self,
+        coefficients: &[(usize, isize)],
+        comparison: Comparison,
+        constant: isize,
+    ) {
+        self.add_rational_constraint(
+            coefficients
+                .iter()
+                .map(|&(v, c)| (v, Self::rational(c)))
+                .collect(),
+            comparison,
+            Self::rational(constant),
+        );
+    }
+
+    /// Add a constraint with rational coefficients
+    pub fn add_rational_constraint(
+        &mut self,
+        coefficients: Vec<(usize, BigRational)>,
+        comparison: Comparison,
+        constant: BigRational,
+    ) {
+        self.rows.push(Row {
+            coefficients,
+            comparison,
+            constant,
+        });
+    }
+
+    /// Set the objective of the problem
+    pub fn set_objective(&mut self, goal: Goal, coefficients: &[(usize, isize)]) {
+        self.goal = goal;
+        self.objective = coefficients
+            .iter()
+            .map(|&(v, c)| (v, Self::rational(c)))
+            .collect();
+    }
+
+    /// Convert an integer to a rational
+    fn rational(v: isize) -> BigRational {
+        BigRational::from_integer(BigInt::from(v))
+    }
+
+    /// Solve the linear relaxation of the problem (integrality is ignored)
+    #[must_use]
+    pub fn solve_relaxation(&self) -> Solution {
+        Simplex::new(self).solve(self)
+    }
+
+    /// Solve the problem with integrality constraints
+    #[must_use]
+    pub fn solve(&self) -> Solution {
+        let mut best: Option<(BigRational, Vec<BigRational>)> = None;
+        let mut problem = self.clone();
+        // Each node of the search is the list of bound rows added to the original problem
+        let mut branches: Vec<Vec<Row>> = vec![vec![]];
+        let mut explored = 0;
+        while let Some(bounds) = branches.pop() {
+            explored += 1;
+            if let Some(limit) = self.node_limit {
+                if explored > limit {
+                    return Solution::Unknown;
+                }
+            }
+            problem.rows.truncate(self.rows.len());
+            problem.rows.extend(bounds.iter().cloned());
+            let (value, values) = match problem.solve_relaxation() {
+                Solution::Optimal { value, values } => (value, values),
+                Solution::Unbounded => return Solution::Unbounded,
+                _ => continue,
+            };
+            // The relaxation can not be better than the best integer solution
+            if let Some((best_value, _)) = &best {
+                let worse = match self.goal {
+                    Goal::Minimize => value >= *best_value,
+                    Goal::Maximize => value <= *best_value,
+                };
+                if worse {
+                    continue;
+                }
+            }
+            match values
+                .iter()
+                .enumerate()
+                .find(|&(v, value)| self.integer[v] && !value.is_integer())
+            {
+                None => best = Some((value, values)),
+                Some((v, value)) => {
+                    let mut lower = bounds.clone();
+                    lower.push(Row {
+                        coefficients: vec![(v, BigRational::one())],
+                        comparison: Comparison::LessEqual,
+                        constant: value.floor(),
+                    });
+                    let mut upper = bounds;
+                    upper.push(Row {
+                        coefficients: vec![(v, BigRational::one())],
+                        comparison: Comparison::GreaterEqual,
+                        constant: value.ceil(),
+                    });
+                    branches.push(lower);
+                    branches.push(upper);
+                }
+            }
+        }
+        match best {
+            Some((value, values)) => Solution::Optimal { value, values },
+            None => Solution::Infeasible,
+        }
+    }
+}
+
+/// Simplex tableau
+///
+/// Columns are ordered as: variables of the problem, slack variables, artificial variables and
+/// the right-hand side.
+struct Simplex {
+    /// Rows of the tableau
+    rows: Vec<Vec<BigRational>>,
+    /// Basic variable of each row
+    basis: Vec<usize>,
+    /// Number of columns, without the right-hand side
+    columns: usize,
+    /// First artificial column
+    artificial: usize,
+}
+
+impl Simplex {
+    /// Build the tableau of a problem with a feasible basis made of slack and artificial variables
+    fn new(problem: &Problem) -> Self {
+        let variables = problem.variables();
+        let slacks = problem
+            .rows
+            .iter()
+            .filter(|row| row.comparison != Comparison::Equal)
+            .count();
+        let artificial = variables + slacks;
+        let columns = artificial + problem.rows.len();
+        let mut simplex = Self {
+            rows: vec![],
+            basis: vec![],
+            columns,
+            artificial,
+        };
+        let mut slack = variables;
+        for (i, row) in problem.rows.iter().enumerate() {
+            let mut values = vec![BigRational::zero(); columns + 1];
+            for (v, c) in &row.coefficients {
+                values[*v] += c;
+            }
+            values[columns] = row.constant.clone();
+            match row.comparison {
+                Comparison::LessEqual => {
+                    values[slack] = BigRational::one();
+                    slack += 1;
+                }
+                Comparison::GreaterEqual => {
+                    values[slack] = -BigRational::one();
+                    slack += 1;
+                }
+                Comparison::Equal => {}
+            }
+            // The right-hand side must be non-negative to start from a feasible basis
+            if values[columns].is_negative() {
+                for value in values.iter_mut() {
+                    *value = -value.clone();
+                }
+            }
+            values[artificial + i] = BigRational::one();
+            simplex.rows.push(values);
+            simplex.basis.push(artificial + i);
+        }
+        simplex
+    }
+
+    /// Replace the basic variable of `row` by the variable of `column`
+    fn pivot(&mut self, objective: &mut [BigRational], row: usize, column: usize) {
+        let pivot = self.rows[row][column].clone();
+        for value in self.rows[row].iter_mut() {
+            *value /= &pivot;
+        }
+        let pivot_row = self.rows[row].clone();
+        let eliminate = |values: &mut [BigRational]| {
+            let factor = values[column].clone();
+            if !factor.is_zero() {
+                for (value, p) in values.iter_mut().zip(pivot_row.iter()) {
+                    if !p.is_zero() {
+                        *value -= &factor * p;
+                    }
+                }
+            }
+        };
+        for (i, values) in self.rows.iter_mut().enumerate() {
+            if i != row {
+                eliminate(values);
+            }
+        }
+        eliminate(objective);
+        self.basis[row] = column;
+    }
+
+    /// Build the reduced costs row of a minimization objective over the `columns` first columns
+    fn reduced_costs(&self, costs: &[BigRational]) -> Vec<BigRational> {
+        let mut objective = costs.to_vec();
+        objective.resize(self.columns + 1, BigRational::zero());
+        for (row, &basic) in self.basis.iter().enumerate() {
+            let cost = objective[basic].clone();
+            if !cost.is_zero() {
+                for (value, r) in objective.iter_mut().zip(self.rows[row].iter()) {
+                    *value -= &cost * r;
+                }
+            }
+        }
+        objective
+    }
+
+    /// Minimize the objective using columns before `limit`, returns [`false`] if it is unbounded
+    fn optimize(&mut self, objective: &mut [BigRational], limit: usize) -> bool {
+        loop {
+            // Bland's rule: smallest improving column and smallest leaving basic variable
+            let column = match (0..limit).find(|&c| objective[c].is_negative()) {
+                None => return true,
+                Some(column) => column,
+            };
+            let mut leaving: Option<(usize, BigRational)> = None;
+            for (row, values) in self.rows.iter().enumerate() {
+                if values[column].is_positive() {
+                    let ratio = &values[self.columns] / &values[column];
+                    let better = match &leaving {
+                        None => true,
+                        Some((best, best_ratio)) => {
+                            ratio < *best_ratio
+                                || (ratio == *best_ratio && self.basis[row] < self.basis[*best])
+                        }
+                    };
+                    if better {
+                        leaving = Some((row, ratio));
+                    }
+                }
+            }
+            match leaving {
+                None => return false,
+                Some((row, _)) => self.pivot(objective, row, column),
+            }
+        }
+    }
+
+    /// Solve the linear relaxation of a problem
+    fn solve(mut self, problem: &Problem) -> Solution {
+        let variables = problem.variables();
+        // Phase 1: minimize the sum of artificial variables
+        let mut costs = vec![BigRational::zero(); self.columns];
+        for cost in costs.iter_mut().skip(self.artificial) {
+            *cost = BigRational::one();
+        }
+        let mut objective = self.reduced_costs(&costs);
+        self.optimize(&mut objective, self.columns);
+        if !objective[self.columns].is_zero() {
+            return Solution::Infeasible;
+        }
+
+        // Drive artificial variables out of the basis, or drop redundant rows
+        let mut row = 0;
+        while row < self.rows.len() {
+            if self.basis[row] >= self.artificial {
+                match (0..self.artificial).find(|&c| !self.rows[row][c].is_zero()) {
+                    Some(column) => self.pivot(&mut objective, row, column),
+                    None => {
+                        self.rows.remove(row);
+                        self.basis.remove(row);
+                        continue;
+                    }
+                }
+            }
+            row += 1;
+        }
+
+        // Phase 2: optimize the real objective without artificial variables
+        let mut costs = vec![BigRational::zero(); self.columns];
+        for (v, c) in &problem.objective {
+            match problem.goal {
+                Goal::Minimize => costs[*v] += c,
+                Goal::Maximize => costs[*v] -= c,
+            }
+        }
+        let mut objective = self.reduced_costs(&costs);
+        if !self.optimize(&mut objective, self.artificial) {
+            return Solution::Unbounded;
+        }
+
+        let mut values = vec![BigRational::zero(); variables];
+        for (row, &basic) in self.basis.iter().enumerate() {
+            if basic < variables {
+                values[basic] = self.rows[row][self.columns].clone();
+            }
+        }
+        let value = problem
+            .objective
+            .iter()
+            .fold(BigRational::zero(), |acc, (v, c)| acc + c * &values[*v]);
+        Solution::Optimal { value, values }
+    }
+}
@@ -12,7 +12,11 @@
 //! In order to easily manipulate these nets this api provides the following elements:
 //! - [`arc::Kind`] - an enum of the different types of arcs that exist in a Petri net;
 //! - [`Marking`] - a structure for manipulating hollow vectors;
-//! - [`PlaceId`] and [`TransitionId`] - a type for indexing places and transitions in nets.
+//! - [`PlaceId`] and [`TransitionId`] - a type for indexing places and transitions in nets;
+//! - [`metadata::Metadata`] - notes and comments which document a net;
+//! - [`predicate::Predicate`] - a conjunction of linear constraints over the marking of a net;
+//! - [`simulation::Simulator`] - a seeded random walk simulator for both kinds of nets;
+//! - [`lts::Lts`] - a labelled transition system, read and written in the Aldebaran format.
 //!
 pub use errors::NetError;
 pub use marking::Marking;
@@ -20,7 +24,12 @@ pub use net::{NodeId, PlaceId, TransitionId};
 
 pub mod arc;
 mod errors;
+pub mod ilp;
+pub mod lts;
 mod marking;
+pub mod metadata;
 mod net;
+pub mod predicate;
+pub mod simulation;
 pub mod standard;
 pub mod timed;
@@ -0,0 +1,174 @@
+//! Labelled transition systems
+//!
+//! A labelled transition system is a graph of states whose edges are labelled by actions. It is
+//! the result of the exploration of the behaviour of a net, see
+//! [`StateClassGraph::lts`][`crate::timed::state_class::StateClassGraph::lts`] and
+//! [`ZoneGraph::lts`][`crate::timed::zone::ZoneGraph::lts`].
+//!
+//! Systems are read and written in the [Aldebaran](https://cadp.inria.fr/man/aut.html) format of
+//! CADP. Tina writes its state graphs in this format with the `-aut` option, so graphs computed
+//! by both tools can be compared. The binary `.ktz` format of Tina is not supported.
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::io::{BufRead, Write};
+
+use crate::NetError;
+
+/// Labelled edge between two states
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Edge {
+    /// Source state
+    pub from: usize,
+    /// Label of the edge, the name of a transition for graphs of nets
+    pub label: String,
+    /// Target state
+    pub to: usize,
+}
+
+/// Labelled transition system, states are numbered from `0` to `states - 1`
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub struct Lts {
+    /// Initial state
+    pub initial: usize,
+    /// Number of states
+    pub states: usize,
+    /// Edges of the system
+    pub edges: Vec<Edge>,
+}
+
+impl Lts {
+    /// Labels of the edges
+    #[must_use]
+    pub fn labels(&self) -> BTreeSet<&str> {
+        self.edges.iter().map(|edge| edge.label.as_str()).collect()
+    }
+
+    /// States without successor
+    #[must_use]
+    pub fn deadlocks(&self) -> Vec<usize> {
+        let mut has_successor = vec![false; self.states];
+        for edge in &self.edges {
+            has_successor[edge.from] = true;
+        }
+        (0..self.states).filter(|&s| !has_successor[s]).collect()
+    }
+
+    /// Read a system in the Aldebaran format
+    ///
+    /// Labels may be quoted, a quoted label can contain `\"` and `\\`.
+    ///
+    /// # Errors
+    /// Return [`NetError::InvalidLts`] with the number of the line if a line is not valid, if a
+    /// state is out of range or if the number of edges differs from the header.
+    pub fn read_aut(reader: &mut dyn BufRead) -> Result<Self, Box<dyn Error>> {
+        let mut lts: Option<Self> = None;
+        let mut expected = 0;
+        let mut number = 0;
+        for line in reader.lines() {
+            let line = line?;
+            number += 1;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let invalid = |message: &str| NetError::InvalidLts(number, message.to_string());
+            let inner = line
+                .strip_suffix(')')
+                .and_then(|line| line.strip_prefix("des").or(Some(line)))
+                .map(str::trim_start)
+                .and_then(|line| line.strip_prefix('('))
+                .ok_or_else(|| invalid("expected parentheses"))?;
+            let (first, rest) = inner
+                .split_once(',')
+                .ok_or_else(|| invalid("expected three fields"))?;
+            let (middle, last) = rest
+                .rsplit_once(',')
+                .ok_or_else(|| invalid("expected three fields"))?;
+            let state = |field: &str| {
+                field
+                    .trim()
+                    .parse::<usize>()
+                    .map_err(|_| invalid("expected a number"))
+            };
+            match &mut lts {
+                None if line.starts_with("des") => {
+                    expected = state(middle)?;
+                    lts = Some(Self {
+                        initial: state(first)?,
+                        states: state(last)?,
+                        edges: Vec::with_capacity(expected),
+                    });
+                }
+                None => return Err(Box::new(invalid("expected des header"))),
+                Some(_) if line.starts_with("des") => {
+                    return Err(Box::new(invalid("duplicated des header")))
+                }
+                Some(lts) => {
+                    let middle = middle.trim();
+                    let label = match middle
+                        .strip_prefix('"')
+                        .and_then(|label| label.strip_suffix('"'))
+                    {
+                        Some(label) => {
+                            let mut unescaped = String::with_capacity(label.len());
+                            let mut chars = label.chars();
+                            while let Some(c) = chars.next() {
+                                match c {
+                                    '\\' => unescaped.extend(chars.next()),
+                                    c => unescaped.push(c),
+                                }
+                            }
+                            unescaped
+                        }
+                        None => middle.to_string(),
+                    };
+                    let edge = Edge {
+                        from: state(first)?,
+                        label,
+                        to: state(last)?,
+                    };
+                    if edge.from >= lts.states || edge.to >= lts.states {
+                        return Err(Box::new(invalid("state out of range")));
+                    }
+                    lts.edges.push(edge);
+                }
+            }
+        }
+        let lts = lts.ok_or_else(|| NetError::InvalidLts(number, "expected des header".into()))?;
+        if lts.initial >= lts.states.max(1) || lts.edges.len() != expected {
+            return Err(Box::new(NetError::InvalidLts(
+                number,
+                format!(
+                    "expected {} edges and an initial state below {}",
+                    expected, lts.states
+                ),
+            )));
+        }
+        Ok(lts)
+    }
+
+    /// Write the system in the Aldebaran format, labels are quoted
+    pub fn write_aut(&self, writer: &mut dyn Write) -> Result<(), Box<dyn Error>> {
+        writer.write_all(
+            format!(
+                "des ({}, {}, {})\n",
+                self.initial,
+                self.edges.len(),
+                self.states
+            )
+            .as_ref(),
+        )?;
+        for edge in &self.edges {
+            writer.write_all(
+                format!(
+                    "({}, \"{}\", {})\n",
+                    edge.from,
+                    edge.label.replace('\\', "\\\\").replace('"', "\\\""),
+                    edge.to
+                )
+                .as_ref(),
+            )?;
+        }
+        Ok(())
+    }
+}
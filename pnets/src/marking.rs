@@ -6,7 +6,7 @@ use std::slice::Iter;
 /// Allow manipulation of big vector which contains a lot of zeroes.
 /// This type of vector is very useful to represent the connection between locations and
 /// transitions in order to avoid creating a matrix mainly filled with zeros.
-#[derive(Debug, PartialEq, Default, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Default, Clone)]
 pub struct Marking<T: Ord + Copy> {
     values: Vec<(T, usize)>,
 }
@@ -184,6 +184,19 @@ where
         }
     }
 
+    /// Decrement the value contained in the marking by weight.
+    ///
+    /// The index is deleted when its value reaches zero.
+    pub fn sub_or_delete(&mut self, index: T, weight: usize) {
+        if let Ok(pos) = self.values.binary_search_by(|&v| v.0.cmp(&index)) {
+            if self.values[pos].1 <= weight {
+                self.values.remove(pos);
+            } else {
+                self.values[pos].1 -= weight;
+            }
+        }
+    }
+
     /// Delete a specific index from the marking
     pub fn delete(&mut self, index: T) {
         if let Ok(index) = self.values.binary_search_by(|&v| v.0.cmp(&index)) {
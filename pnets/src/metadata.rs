@@ -0,0 +1,72 @@
+//! Notes and comments attached to a net
+//!
+//! These informations do not change the behaviour of a net, they document it. They are kept by
+//! parsers so that exporters can write them back.
+use std::collections::HashMap;
+
+use crate::NodeId;
+
+/// Note of a net, a text box as written by net editors
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub struct Note {
+    /// Name of the note
+    pub name: String,
+    /// Justification of the text, `0` to left justify and `1` to center it in Tina
+    pub justification: usize,
+    /// Text of the note
+    pub text: String,
+    /// Comment lines written before the note
+    pub comments: Vec<String>,
+}
+
+/// Element of a net to which comment lines are attached
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Anchor {
+    /// Comments before the declaration of the net itself
+    Net,
+    /// Comments before the declaration of a node
+    Node(NodeId),
+    /// Comments at the end of the net, after all declarations
+    End,
+}
+
+/// Notes and comments of a net
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub struct Metadata {
+    /// Notes about the whole net
+    pub notes: Vec<Note>,
+    /// Notes attached to places and transitions
+    pub node_notes: HashMap<NodeId, Vec<Note>>,
+    /// Comment lines, without their comment marker
+    pub comments: HashMap<Anchor, Vec<String>>,
+}
+
+impl Metadata {
+    /// Attach a comment line to an element of the net
+    pub fn add_comment(&mut self, anchor: Anchor, comment: String) {
+        self.comments.entry(anchor).or_default().push(comment);
+    }
+
+    /// Comment lines attached to an element of the net
+    #[must_use]
+    pub fn comments(&self, anchor: Anchor) -> &[String] {
+        self.comments.get(&anchor).map_or(&[], Vec::as_slice)
+    }
+
+    /// Attach a note to a place or a transition
+    pub fn add_node_note(&mut self, node: NodeId, note: Note) {
+        self.node_notes.entry(node).or_default().push(note);
+    }
+
+    /// Notes attached to a place or a transition
+    #[must_use]
+    pub fn node_notes(&self, node: NodeId) -> &[Note] {
+        self.node_notes.get(&node).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns [`true`] if a place or a transition has comments or notes
+    #[must_use]
+    pub fn is_documented(&self, node: NodeId) -> bool {
+        !self.comments(Anchor::Node(node)).is_empty() || !self.node_notes(node).is_empty()
+    }
+}
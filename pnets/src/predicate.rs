@@ -0,0 +1,135 @@
+//! Linear predicates over the marking of a net
+//!
+//! A [`Predicate`] is a conjunction of linear [`Constraint`]s on places. It is used to describe
+//! target markings for reachability analyses and encodings (state equation, bounded model
+//! checking, guided search, ...).
+use std::fmt;
+use std::fmt::Formatter;
+
+use crate::{Marking, PlaceId};
+
+/// Comparison operator of a [`Constraint`]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Comparison {
+    /// `<=`
+    LessEqual,
+    /// `=`
+    Equal,
+    /// `>=`
+    GreaterEqual,
+}
+
+impl Comparison {
+    /// Returns [`true`] if `left <comparison> right` holds
+    #[must_use]
+    pub fn holds(&self, left: isize, right: isize) -> bool {
+        match self {
+            Comparison::LessEqual => left <= right,
+            Comparison::Equal => left == right,
+            Comparison::GreaterEqual => left >= right,
+        }
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Comparison::LessEqual => write!(f, "<="),
+            Comparison::Equal => write!(f, "="),
+            Comparison::GreaterEqual => write!(f, ">="),
+        }
+    }
+}
+
+/// Linear constraint over places
+///
+/// The constraint is `Σ coefficient * place <comparison> constant`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Constraint {
+    /// Weighted places of the left-hand side
+    pub coefficients: Vec<(PlaceId, isize)>,
+    /// Comparison operator
+    pub comparison: Comparison,
+    /// Right-hand side of the constraint
+    pub constant: isize,
+}
+
+impl Constraint {
+    /// Create a new constraint
+    #[must_use]
+    pub fn new(
+        coefficients: Vec<(PlaceId, isize)>,
+        comparison: Comparison,
+        constant: isize,
+    ) -> Self {
+        Self {
+            coefficients,
+            comparison,
+            constant,
+        }
+    }
+
+    /// Constraint `place >= value`
+    #[must_use]
+    pub fn at_least(place: PlaceId, value: usize) -> Self {
+        Self::new(vec![(place, 1)], Comparison::GreaterEqual, value as isize)
+    }
+
+    /// Constraint `place <= value`
+    #[must_use]
+    pub fn at_most(place: PlaceId, value: usize) -> Self {
+        Self::new(vec![(place, 1)], Comparison::LessEqual, value as isize)
+    }
+
+    /// Constraint `place = value`
+    #[must_use]
+    pub fn exactly(place: PlaceId, value: usize) -> Self {
+        Self::new(vec![(place, 1)], Comparison::Equal, value as isize)
+    }
+
+    /// Value of the left-hand side for a marking
+    #[must_use]
+    pub fn left_value(&self, marking: &Marking<PlaceId>) -> isize {
+        self.coefficients
+            .iter()
+            .map(|&(pl, c)| c * marking[pl] as isize)
+            .sum()
+    }
+
+    /// Returns [`true`] if the marking satisfies this constraint
+    #[must_use]
+    pub fn evaluate(&self, marking: &Marking<PlaceId>) -> bool {
+        self.comparison
+            .holds(self.left_value(marking), self.constant)
+    }
+}
+
+/// Conjunction of linear constraints over places
+///
+/// An empty predicate is always true.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Predicate {
+    /// Constraints which must all hold
+    pub constraints: Vec<Constraint>,
+}
+
+impl Predicate {
+    /// Create a predicate from a list of constraints
+    #[must_use]
+    pub fn new(constraints: Vec<Constraint>) -> Self {
+        Self { constraints }
+    }
+
+    /// Add a constraint to the conjunction
+    #[must_use]
+    pub fn and(mut self, constraint: Constraint) -> Self {
+        self.constraints.push(constraint);
+        self
+    }
+
+    /// Returns [`true`] if the marking satisfies all constraints
+    #[must_use]
+    pub fn evaluate(&self, marking: &Marking<PlaceId>) -> bool {
+        self.constraints.iter().all(|c| c.evaluate(marking))
+    }
+}
@@ -0,0 +1,419 @@
+//! Random simulation of nets
+//!
+//! A [`Simulator`] fires random transitions from the initial marking of a net until no
+//! transition can be fired or a maximum depth is reached, and repeats this walk a number of
+//! times. It is a cheap way to find deadlocks or bad markings before exhaustive verification.
+//!
+//! The pseudo-random generator is seeded, so a simulation with the same parameters on the same
+//! net always produces the same walks.
+//!
+//! Both [`standard::Net`] and [`timed::Net`] can be simulated, see [`Simulate`]. Time is
+//! discrete for timed nets: delays are integers, so only the integer firing times of rational time
+//! ranges are considered, and an unbounded delay is replaced by the earliest possible delay.
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::io::Write;
+
+use fastrand::Rng;
+
+use crate::timed::Bound;
+use crate::{standard, timed, Marking, NodeId, PlaceId, TransitionId};
+
+/// Transition which can be fired from a state
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Firable {
+    /// Transition to fire
+    pub transition: TransitionId,
+    /// Smallest delay before the firing
+    pub earliest: usize,
+    /// Largest delay before the firing, [`None`] if the delay is not bounded
+    pub latest: Option<usize>,
+}
+
+/// Net which can be simulated
+pub trait Simulate {
+    /// State of the net during the simulation
+    type State: Clone;
+
+    /// State of the net before any firing
+    fn initial_state(&self) -> Self::State;
+
+    /// Marking of a state
+    fn state_marking<'s>(&self, state: &'s Self::State) -> &'s Marking<PlaceId>;
+
+    /// Transitions which can be fired from a state
+    fn firable(&self, state: &Self::State) -> Vec<Firable>;
+
+    /// Compute the state reached by waiting `delay` and firing a transition
+    fn fire(&self, state: &Self::State, transition: TransitionId, delay: usize) -> Self::State;
+
+    /// Label of a transition
+    fn transition_label(&self, transition: TransitionId) -> Option<&str>;
+
+    /// Transitions over which a transition has priority
+    fn transition_priorities(&self, transition: TransitionId) -> &[TransitionId];
+
+    /// Name of a transition
+    fn transition_name(&self, transition: TransitionId) -> String;
+}
+
+impl Simulate for standard::Net {
+    type State = Marking<PlaceId>;
+
+    fn initial_state(&self) -> Self::State {
+        self.initial_marking()
+    }
+
+    fn state_marking<'s>(&self, state: &'s Self::State) -> &'s Marking<PlaceId> {
+        state
+    }
+
+    fn firable(&self, state: &Self::State) -> Vec<Firable> {
+        self.transitions
+            .iter()
+            .filter(|tr| !tr.deleted && !tr.is_disconnected() && tr.is_enabled(state))
+            .map(|tr| Firable {
+                transition: tr.id(),
+                earliest: 0,
+                latest: Some(0),
+            })
+            .collect()
+    }
+
+    fn fire(&self, state: &Self::State, transition: TransitionId, _delay: usize) -> Self::State {
+        self[transition].fire(state)
+    }
+
+    fn transition_label(&self, transition: TransitionId) -> Option<&str> {
+        self[transition].label.as_deref()
+    }
+
+    fn transition_priorities(&self, _transition: TransitionId) -> &[TransitionId] {
+        &[]
+    }
+
+    fn transition_name(&self, transition: TransitionId) -> String {
+        self.get_name_by_index(&NodeId::Transition(transition))
+            .unwrap_or_default()
+    }
+}
+
+/// State of a timed net: a marking and the time elapsed since the enabling of each enabled
+/// transition
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct TimedState {
+    /// Marking of the net
+    pub marking: Marking<PlaceId>,
+    /// Clock of each enabled transition
+    pub clocks: Marking<TransitionId>,
+}
+
+impl Simulate for timed::Net {
+    type State = TimedState;
+
+    fn initial_state(&self) -> Self::State {
+        let marking = self.initial_marking();
+        let mut clocks = Marking::default();
+        for tr in self.enabled_transitions(&marking) {
+            clocks.insert_or_add(tr, 0);
+        }
+        TimedState { marking, clocks }
+    }
+
+    fn state_marking<'s>(&self, state: &'s Self::State) -> &'s Marking<PlaceId> {
+        &state.marking
+    }
+
+    fn firable(&self, state: &Self::State) -> Vec<Firable> {
+        // Time can not elapse beyond the latest firing time of an enabled transition, clocks
+        // suspended by stopwatch arcs do not progress and their transitions can not be fired
+        let mut deadline: Option<usize> = None;
+        let mut windows = vec![];
+        for &(tr, clock) in state
+            .clocks
+            .iter()
+            .filter(|&&(tr, _)| self[tr].is_active(&state.marking))
+        {
+            let time = self[tr].time;
+            // Smallest and largest integer firing times in the time range
+            let earliest = match time.start {
+                Bound::Closed(v) => Some(v.ceil().to_integer() as usize),
+                Bound::Open(v) => Some(v.floor().to_integer() as usize + 1),
+                Bound::Infinity => None,
+            };
+            let latest = match time.end {
+                Bound::Closed(v) => (v.floor().to_integer() as usize).checked_sub(clock),
+                Bound::Open(v) => (v.ceil().to_integer() as usize).checked_sub(clock + 1),
+                Bound::Infinity => None,
+            };
+            if let Some(latest) = latest {
+                deadline = Some(deadline.map_or(latest, |d| d.min(latest)));
+            }
+            if let Some(earliest) = earliest {
+                windows.push((tr, earliest.saturating_sub(clock)));
+            }
+        }
+        windows
+            .into_iter()
+            .filter(|&(_, earliest)| !matches!(deadline, Some(d) if earliest > d))
+            .map(|(transition, earliest)| Firable {
+                transition,
+                earliest,
+                latest: deadline,
+            })
+            .collect()
+    }
+
+    fn fire(&self, state: &Self::State, transition: TransitionId, delay: usize) -> Self::State {
+        let transition = &self[transition];
+        // Transitions which stay enabled during the firing keep their clock, which only
+        // progressed during the delay if it was not suspended
+        let intermediate = transition.intermediate(&state.marking);
+        let marking = transition.fire(&state.marking);
+        let mut clocks = Marking::default();
+        for tr in self.enabled_transitions(&marking) {
+            let clock = if tr != transition.id() && self[tr].is_enabled(&intermediate) {
+                if self[tr].is_active(&state.marking) {
+                    state.clocks[tr] + delay
+                } else {
+                    state.clocks[tr]
+                }
+            } else {
+                0
+            };
+            clocks.insert_or_add(tr, clock);
+        }
+        TimedState { marking, clocks }
+    }
+
+    fn transition_label(&self, transition: TransitionId) -> Option<&str> {
+        self[transition].label.as_deref()
+    }
+
+    fn transition_priorities(&self, transition: TransitionId) -> &[TransitionId] {
+        &self[transition].priorities
+    }
+
+    fn transition_name(&self, transition: TransitionId) -> String {
+        self.get_name_by_index(&NodeId::Transition(transition))
+            .unwrap_or_default()
+    }
+}
+
+/// Choice of the next transition to fire
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Policy {
+    /// All firable transitions have the same probability
+    Uniform,
+    /// The probability of a transition is proportional to the weight of its label
+    ///
+    /// Transitions without label or with a label which is not in the map have a weight of one.
+    /// The walk stops if all firable transitions have a weight of zero.
+    Weighted(HashMap<String, usize>),
+    /// Transitions are chosen uniformly among firable transitions which are not dominated by
+    /// another firable transition with a higher priority
+    Priority,
+}
+
+/// Firing in a walk
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Step {
+    /// Fired transition
+    pub transition: TransitionId,
+    /// Delay elapsed before the firing
+    pub delay: usize,
+}
+
+/// Sequence of firings from the initial state
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Walk {
+    /// Firings of the walk
+    pub steps: Vec<Step>,
+    /// The walk ended in a state where no transition is firable
+    pub deadlock: bool,
+}
+
+impl Walk {
+    /// Write the walk in the format of Tina traces
+    ///
+    /// Fired transitions are separated by spaces, a non-zero delay `d` before the firing of `t`
+    /// is written `t@d`. Names which are not simple identifiers are escaped with braces.
+    pub fn export<N: Simulate>(
+        &self,
+        net: &N,
+        writer: &mut dyn Write,
+    ) -> Result<(), Box<dyn Error>> {
+        let mut first = true;
+        for step in &self.steps {
+            if !first {
+                writer.write_all(b" ")?;
+            }
+            first = false;
+            writer.write_all(escape(&net.transition_name(step.transition)).as_ref())?;
+            if step.delay != 0 {
+                writer.write_all(format!("@{}", step.delay).as_ref())?;
+            }
+        }
+        writer.write_all(b"\n")?;
+        Ok(())
+    }
+}
+
+/// Escape a name which is not a Tina identifier
+fn escape(name: &str) -> String {
+    if !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '\'')
+    {
+        name.to_string()
+    } else {
+        format!(
+            "{{{}}}",
+            name.replace("\\", "\\\\")
+                .replace("{", "\\{")
+                .replace("}", "\\}")
+        )
+    }
+}
+
+/// Result of a simulation
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    /// Walks of the simulation, in order
+    pub walks: Vec<Walk>,
+    /// Distinct markings visited by the walks
+    pub visited: HashSet<Marking<PlaceId>>,
+    /// Distinct markings where a walk ended in a deadlock
+    pub deadlocks: Vec<Marking<PlaceId>>,
+}
+
+/// Random walk simulator
+pub struct Simulator {
+    seed: u64,
+    policy: Policy,
+    depth: usize,
+    restarts: usize,
+}
+
+impl Simulator {
+    /// Create a new simulator with a seed
+    ///
+    /// By default the policy is [`Policy::Uniform`], walks have at most 1000 firings and there is
+    /// no restart.
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            policy: Policy::Uniform,
+            depth: 1000,
+            restarts: 0,
+        }
+    }
+
+    /// Set the policy used to choose the next transition
+    #[must_use]
+    pub fn with_policy(self, policy: Policy) -> Self {
+        Self { policy, ..self }
+    }
+
+    /// Set the maximum number of firings of a walk
+    #[must_use]
+    pub fn with_depth(self, depth: usize) -> Self {
+        Self { depth, ..self }
+    }
+
+    /// Set the number of walks started after the first one
+    #[must_use]
+    pub fn with_restarts(self, restarts: usize) -> Self {
+        Self { restarts, ..self }
+    }
+
+    /// Simulate a net
+    pub fn run<N: Simulate>(&self, net: &N) -> Report {
+        let rng = Rng::with_seed(self.seed);
+        let mut report = Report::default();
+        for _ in 0..=self.restarts {
+            let walk = self.walk(net, &rng, &mut report);
+            report.walks.push(walk);
+        }
+        report
+    }
+
+    /// Simulate one walk from the initial state
+    fn walk<N: Simulate>(&self, net: &N, rng: &Rng, report: &mut Report) -> Walk {
+        let mut walk = Walk::default();
+        let mut state = net.initial_state();
+        report.visited.insert(net.state_marking(&state).clone());
+        while walk.steps.len() < self.depth {
+            let firable = net.firable(&state);
+            if firable.is_empty() {
+                walk.deadlock = true;
+                let marking = net.state_marking(&state);
+                if !report.deadlocks.contains(marking) {
+                    report.deadlocks.push(marking.clone());
+                }
+                break;
+            }
+            let chosen = match self.choose(net, &firable, rng) {
+                Some(chosen) => chosen,
+                None => break,
+            };
+            let delay = match chosen.latest {
+                Some(latest) => rng.usize(chosen.earliest..=latest),
+                None => chosen.earliest,
+            };
+            state = net.fire(&state, chosen.transition, delay);
+            report.visited.insert(net.state_marking(&state).clone());
+            walk.steps.push(Step {
+                transition: chosen.transition,
+                delay,
+            });
+        }
+        walk
+    }
+
+    /// Choose the next transition to fire with the policy
+    fn choose<N: Simulate>(&self, net: &N, firable: &[Firable], rng: &Rng) -> Option<Firable> {
+        match &self.policy {
+            Policy::Uniform => Some(firable[rng.usize(..firable.len())]),
+            Policy::Weighted(weights) => {
+                let weight = |f: &Firable| {
+                    net.transition_label(f.transition)
+                        .and_then(|label| weights.get(label))
+                        .copied()
+                        .unwrap_or(1)
+                };
+                let total: usize = firable.iter().map(weight).sum();
+                if total == 0 {
+                    return None;
+                }
+                let mut choice = rng.usize(..total);
+                for f in firable {
+                    if choice < weight(f) {
+                        return Some(*f);
+                    }
+                    choice -= weight(f);
+                }
+                None
+            }
+            Policy::Priority => {
+                let candidates: Vec<&Firable> = firable
+                    .iter()
+                    .filter(|f| {
+                        !firable.iter().any(|other| {
+                            net.transition_priorities(other.transition)
+                                .contains(&f.transition)
+                        })
+                    })
+                    .collect();
+                // Cyclic priorities may dominate every transition
+                if candidates.is_empty() {
+                    Some(firable[rng.usize(..firable.len())])
+                } else {
+                    Some(*candidates[rng.usize(..candidates.len())])
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,377 @@
+//! Bounded model checking of safe nets
+//!
+//! [`Bmc`] unrolls the firing rule of a safe (1-bounded) [`Net`] for a number of steps and encodes
+//! the reachability of a target [`Predicate`] as a CNF formula in the
+//! [DIMACS](https://www.satcompetition.org/2009/format-benchmarks2009.html) format. The formula
+//! is satisfiable if and only if a marking satisfying the target is reachable in at most `steps`
+//! firings. A model returned by a SAT solver can be turned back into a firing sequence with
+//! [`Bmc::decode`].
+//!
+//! The encoding has one variable per place and step (the place is marked) and one variable per
+//! transition and step (the transition is fired). At most one transition is fired at each step,
+//! steps without firing are allowed so shorter sequences are also found.
+//!
+//! The net must be safe, the encoding is wrong if a reachable marking has more than one token in
+//! a place.
+use std::error::Error;
+use std::io::{BufRead, Write};
+
+use indexed_vec::Idx;
+
+use crate::predicate::{Comparison, Predicate};
+use crate::standard::Net;
+use crate::{arc, NetError, NodeId, PlaceId, TransitionId};
+
+/// Literal of the formula, a variable or its negation (DIMACS convention)
+type Literal = isize;
+
+/// Encoding of the bounded reachability problem of a net
+pub struct Bmc {
+    /// Number of steps
+    steps: usize,
+    /// Number of places of the net
+    places: usize,
+    /// Number of transitions of the net
+    transitions: usize,
+    /// Number of variables of the formula
+    variables: usize,
+    /// Clauses of the formula
+    clauses: Vec<Vec<Literal>>,
+}
+
+impl Bmc {
+    /// Encode the reachability of `target` in at most `steps` firings
+    ///
+    /// # Errors
+    /// Return [`NetError::UnsafePlace`] if a place has more than one token in the initial marking,
+    /// [`NetError::UnsupportedArc`] if an arc has a weight greater than one and
+    /// [`NetError::InvalidPlace`] if the predicate uses an unknown place.
+    pub fn new(net: &Net, target: &Predicate, steps: usize) -> Result<Self, NetError> {
+        let mut bmc = Self {
+            steps,
+            places: net.places.len(),
+            transitions: net.transitions.len(),
+            variables: (steps + 1) * net.places.len() + steps * net.transitions.len(),
+            clauses: vec![],
+        };
+        for place in &net.places {
+            if place.initial > 1 {
+                return Err(NetError::UnsafePlace(place.id()));
+            }
+        }
+        for transition in &net.transitions {
+            for arc in transition.get_arcs() {
+                match arc {
+                    arc::Kind::Consume(_, _, w) | arc::Kind::Produce(_, _, w) if w > 1 => {
+                        return Err(NetError::UnsupportedArc(arc))
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        // Initial marking
+        for place in &net.places {
+            let literal = bmc.place(place.id(), 0);
+            bmc.clauses.push(vec![if place.initial == 1 {
+                literal
+            } else {
+                -literal
+            }]);
+        }
+
+        for step in 0..steps {
+            bmc.encode_step(net, step);
+        }
+
+        bmc.encode_target(net, target)?;
+        Ok(bmc)
+    }
+
+    /// Variable of a place at a step
+    fn place(&self, pl: PlaceId, step: usize) -> Literal {
+        (1 + step * self.places + pl.index()) as Literal
+    }
+
+    /// Variable of the firing of a transition at a step
+    fn transition(&self, tr: TransitionId, step: usize) -> Literal {
+        (1 + (self.steps + 1) * self.places + step * self.transitions + tr.index()) as Literal
+    }
+
+    /// Allocate a new auxiliary variable
+    fn new_variable(&mut self) -> Literal {
+        self.variables += 1;
+        self.variables as Literal
+    }
+
+    /// Encode the firing rule between `step` and `step + 1`
+    fn encode_step(&mut self, net: &Net, step: usize) {
+        let mut fired = vec![];
+        for transition in &net.transitions {
+            let fire = self.transition(transition.id(), step);
+            if transition.deleted {
+                self.clauses.push(vec![-fire]);
+                continue;
+            }
+            fired.push(fire);
+            for &(pl, _) in transition.consume.iter() {
+                // Enabled
+                self.clauses.push(vec![-fire, self.place(pl, step)]);
+                if transition.produce[pl] == 0 {
+                    self.clauses.push(vec![-fire, -self.place(pl, step + 1)]);
+                }
+            }
+            for &(pl, _) in transition.produce.iter() {
+                self.clauses.push(vec![-fire, self.place(pl, step + 1)]);
+            }
+        }
+        self.at_most_one(&fired);
+
+        // A place can only change if a transition which modifies it is fired
+        for place in &net.places {
+            let before = self.place(place.id(), step);
+            let after = self.place(place.id(), step + 1);
+            // A marked place is emptied only by a consumer, an empty place is filled only by a
+            // producer
+            let mut emptied = vec![-before, after];
+            let mut filled = vec![before, -after];
+            for (tr, produced, consumed) in place.produced_by.iter_with(&place.consumed_by) {
+                if consumed > produced {
+                    emptied.push(self.transition(tr, step));
+                } else if produced > consumed {
+                    filled.push(self.transition(tr, step));
+                }
+            }
+            self.clauses.push(emptied);
+            self.clauses.push(filled);
+        }
+    }
+
+    /// Sequential counter encoding of `at most one literal is true`
+    fn at_most_one(&mut self, literals: &[Literal]) {
+        if literals.len() < 2 {
+            return;
+        }
+        let mut previous = self.new_variable();
+        self.clauses.push(vec![-literals[0], previous]);
+        for &literal in &literals[1..literals.len() - 1] {
+            let current = self.new_variable();
+            self.clauses.push(vec![-literal, current]);
+            self.clauses.push(vec![-previous, current]);
+            self.clauses.push(vec![-literal, -previous]);
+            previous = current;
+        }
+        self.clauses
+            .push(vec![-literals[literals.len() - 1], -previous]);
+    }
+
+    /// Totalizer encoding, returns `outputs` where `outputs[i]` is true iff more than `i`
+    /// literals are true
+    fn totalizer(&mut self, literals: &[Literal]) -> Vec<Literal> {
+        if literals.len() <= 1 {
+            return literals.to_vec();
+        }
+        let (left, right) = literals.split_at(literals.len() / 2);
+        let left = self.totalizer(left);
+        let right = self.totalizer(right);
+        let outputs: Vec<Literal> = (0..literals.len()).map(|_| self.new_variable()).collect();
+        for i in 0..=left.len() {
+            for j in 0..=right.len() {
+                if i + j > 0 {
+                    // left >= i ∧ right >= j → sum >= i + j
+                    let mut clause = vec![outputs[i + j - 1]];
+                    if i > 0 {
+                        clause.push(-left[i - 1]);
+                    }
+                    if j > 0 {
+                        clause.push(-right[j - 1]);
+                    }
+                    self.clauses.push(clause);
+                }
+                if i + j < literals.len() {
+                    // left < i + 1 ∧ right < j + 1 → sum < i + j + 1
+                    let mut clause = vec![-outputs[i + j]];
+                    if i < left.len() {
+                        clause.push(left[i]);
+                    }
+                    if j < right.len() {
+                        clause.push(right[j]);
+                    }
+                    self.clauses.push(clause);
+                }
+            }
+        }
+        outputs
+    }
+
+    /// Encode the target predicate on the last step
+    fn encode_target(&mut self, net: &Net, target: &Predicate) -> Result<(), NetError> {
+        for constraint in &target.constraints {
+            // Rewrite the constraint as a cardinality constraint: a negative coefficient c on x
+            // is replaced by c + |c| * ¬x
+            let mut literals = vec![];
+            let mut constant = constraint.constant;
+            for &(pl, c) in &constraint.coefficients {
+                if pl.index() >= net.places.len() {
+                    return Err(NetError::InvalidPlace(pl));
+                }
+                let literal = self.place(pl, self.steps);
+                if c < 0 {
+                    constant -= c;
+                }
+                for _ in 0..c.abs() {
+                    literals.push(if c < 0 { -literal } else { literal });
+                }
+            }
+            let outputs = self.totalizer(&literals);
+            let at_least = |constant: isize| -> Option<Vec<Literal>> {
+                if constant <= 0 {
+                    None
+                } else if constant as usize > outputs.len() {
+                    Some(vec![])
+                } else {
+                    Some(vec![outputs[constant as usize - 1]])
+                }
+            };
+            let at_most = |constant: isize| -> Option<Vec<Literal>> {
+                if constant < 0 {
+                    Some(vec![])
+                } else if constant as usize >= outputs.len() {
+                    None
+                } else {
+                    Some(vec![-outputs[constant as usize]])
+                }
+            };
+            let clauses = match constraint.comparison {
+                Comparison::GreaterEqual => vec![at_least(constant)],
+                Comparison::LessEqual => vec![at_most(constant)],
+                Comparison::Equal => vec![at_least(constant), at_most(constant)],
+            };
+            self.clauses.extend(clauses.into_iter().flatten());
+        }
+        Ok(())
+    }
+
+    /// Number of variables of the formula
+    #[must_use]
+    pub fn variables(&self) -> usize {
+        self.variables
+    }
+
+    /// Number of clauses of the formula
+    #[must_use]
+    pub fn clauses(&self) -> usize {
+        self.clauses.len()
+    }
+
+    /// Write the formula in DIMACS format
+    ///
+    /// Comments describe the variable of each place and transition at each step.
+    pub fn write_dimacs(&self, net: &Net, writer: &mut dyn Write) -> Result<(), Box<dyn Error>> {
+        writer.write_all(
+            format!(
+                "c bounded reachability of net {} in {} steps\n",
+                net.name, self.steps
+            )
+            .as_ref(),
+        )?;
+        for step in 0..=self.steps {
+            for place in &net.places {
+                writer.write_all(
+                    format!(
+                        "c place {} {} {}\n",
+                        self.place(place.id(), step),
+                        step,
+                        net.get_name_by_index(&NodeId::Place(place.id()))
+                            .unwrap_or_default()
+                    )
+                    .as_ref(),
+                )?;
+            }
+        }
+        for step in 0..self.steps {
+            for transition in &net.transitions {
+                writer.write_all(
+                    format!(
+                        "c transition {} {} {}\n",
+                        self.transition(transition.id(), step),
+                        step,
+                        net.get_name_by_index(&NodeId::Transition(transition.id()))
+                            .unwrap_or_default()
+                    )
+                    .as_ref(),
+                )?;
+            }
+        }
+        writer.write_all(format!("p cnf {} {}\n", self.variables, self.clauses.len()).as_ref())?;
+        for clause in &self.clauses {
+            for literal in clause {
+                writer.write_all(format!("{} ", literal).as_ref())?;
+            }
+            writer.write_all(b"0\n")?;
+        }
+        Ok(())
+    }
+
+    /// Extract the firing sequence from a model of the formula
+    ///
+    /// The model is the list of true literals, variables which are not in the model are
+    /// considered false.
+    #[must_use]
+    pub fn decode(&self, model: &[isize]) -> Vec<TransitionId> {
+        let mut values = vec![false; self.variables + 1];
+        for &literal in model {
+            if literal > 0 && (literal as usize) < values.len() {
+                values[literal as usize] = true;
+            }
+        }
+        let mut sequence = vec![];
+        for step in 0..self.steps {
+            for tr in 0..self.transitions {
+                let tr = TransitionId::from(tr);
+                if values[self.transition(tr, step) as usize] {
+                    sequence.push(tr);
+                }
+            }
+        }
+        sequence
+    }
+
+    /// Extract the firing sequence from a model and return the names of transitions
+    #[must_use]
+    pub fn decode_names(&self, net: &Net, model: &[isize]) -> Vec<String> {
+        self.decode(model)
+            .into_iter()
+            .map(|tr| {
+                net.get_name_by_index(&NodeId::Transition(tr))
+                    .unwrap_or_default()
+            })
+            .collect()
+    }
+}
+
+/// Read the output of a SAT solver
+///
+/// Both the competition format (`s SATISFIABLE` and `v` lines) and the minisat format (`SAT`
+/// followed by the literals) are supported. Returns [`None`] if the formula is unsatisfiable.
+pub fn read_model<R: BufRead>(reader: R) -> Result<Option<Vec<isize>>, Box<dyn Error>> {
+    let mut model = vec![];
+    for line in reader.lines() {
+        let line = line?;
+        let line = line.trim();
+        let values = match line.split_once(' ') {
+            _ if line.ends_with("UNSATISFIABLE") || line == "UNSAT" => return Ok(None),
+            Some(("v", values)) => values,
+            Some(("c", _)) | Some(("s", _)) => continue,
+            _ if line == "SAT" || line.is_empty() => continue,
+            _ => line,
+        };
+        for value in values.split_whitespace() {
+            match value.parse::<isize>()? {
+                0 => {}
+                literal => model.push(literal),
+            }
+        }
+    }
+    Ok(Some(model))
+}
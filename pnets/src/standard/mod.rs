@@ -6,6 +6,10 @@ pub use net::Net;
 pub use place::Place;
 pub use transition::Transition;
 
+pub mod bmc;
 mod net;
 mod place;
+pub mod search;
+pub mod state_equation;
+pub mod structural;
 mod transition;
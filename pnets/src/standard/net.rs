@@ -2,9 +2,10 @@ use std::ops::{Index, IndexMut};
 
 use indexed_vec::IndexVec;
 
+use crate::metadata::Metadata;
 use crate::net::NodeId;
 use crate::standard::{Place, Transition};
-use crate::{arc, timed, NetError, PlaceId, TransitionId};
+use crate::{arc, timed, Marking, NetError, PlaceId, TransitionId};
 use bimap::{BiHashMap, BiMap};
 
 /// Standard Petri net, with only produce and consume arcs
@@ -13,8 +14,8 @@ use bimap::{BiHashMap, BiMap};
 /// and transitions.
 ///
 /// As this kind of net is a subset of timed Petri net, so we can create one from timed Petri
-/// net (but you loose [`arc::Kind::Inhibitor`], [`arc::Kind::StopWatch`] and
-/// [`arc::Kind::StopWatchInhibitor`] arcs and timings).
+/// net (but you loose [`arc::Kind::Inhibitor`], [`arc::Kind::StopWatch`],
+/// [`arc::Kind::StopWatchInhibitor`] and [`arc::Kind::Reset`] arcs and timings).
 #[derive(Default, Debug, Clone)]
 pub struct Net {
     /// Name of this net
@@ -27,6 +28,8 @@ pub struct Net {
     pub transitions: IndexVec<TransitionId, Transition>,
     /// Places of the net
     pub places: IndexVec<PlaceId, Place>,
+    /// Notes and comments of the net
+    pub metadata: Metadata,
 }
 
 impl PartialEq for Net {
@@ -158,6 +161,7 @@ impl From<timed::Net> for Net {
         // Crate a new net
         let mut net = Net {
             name: timed.name.clone(),
+            metadata: timed.metadata.clone(),
             ..Net::default()
         };
         // Copy all places
@@ -223,6 +227,20 @@ impl Net {
         self.transitions.last_idx().unwrap()
     }
 
+    /// Marking of the net before any firing
+    #[must_use]
+    pub fn initial_marking(&self) -> Marking<PlaceId> {
+        let mut marking = Marking::default();
+        for place in self
+            .places
+            .iter()
+            .filter(|pl| !pl.deleted && pl.initial > 0)
+        {
+            marking.insert_or_add(place.id(), place.initial);
+        }
+        marking
+    }
+
     /// Get node name with its id
     pub fn get_name_by_index(&self, index: &NodeId) -> Option<String> {
         self.id_index_map.get_by_right(index).map(|v| v.clone())
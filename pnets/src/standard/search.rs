@@ -0,0 +1,182 @@
+//! Guided reachability search
+//!
+//! [`Search`] looks for the shortest firing sequence from the initial marking of a [`Net`] to a
+//! marking satisfying a target [`Predicate`]. The exploration is an A* search where the distance
+//! to the target is estimated with the linear relaxation of the state equation (see
+//! [`structural::firing_lower_bound`]).
+//!
+//! The estimate never exceeds the real distance, so the first sequence found is one of the
+//! shortest. Markings from which the relaxation has no solution can not reach the target and
+//! are not explored, which often prunes most of the reachability graph.
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::predicate::Predicate;
+use crate::standard::{structural, Net};
+use crate::{Marking, NetError, NodeId, PlaceId, TransitionId};
+
+/// Result of a [`Search`]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Witness {
+    /// Shortest firing sequence which reaches a marking satisfying the target
+    Found(Vec<TransitionId>),
+    /// No reachable marking satisfies the target
+    Unreachable,
+    /// The search reached its marking limit before the end of the exploration
+    Unknown,
+}
+
+/// A* search of a marking satisfying a predicate
+pub struct Search<'n> {
+    net: &'n Net,
+    target: Predicate,
+    /// Maximum number of markings explored
+    marking_limit: Option<usize>,
+}
+
+/// Marking discovered by the search
+struct Node {
+    marking: Marking<PlaceId>,
+    /// Length of the shortest known sequence reaching this marking
+    distance: usize,
+    /// Previous node and fired transition on the shortest known sequence
+    parent: Option<(usize, TransitionId)>,
+    /// Estimated distance to the target, [`None`] if the target is not reachable
+    estimate: Option<usize>,
+    /// All successors of this node were discovered
+    closed: bool,
+}
+
+impl<'n> Search<'n> {
+    /// Create a new search of a marking satisfying `target` in `net`
+    #[must_use]
+    pub fn new(net: &'n Net, target: Predicate) -> Self {
+        Self {
+            net,
+            target,
+            marking_limit: None,
+        }
+    }
+
+    /// Set the maximum number of markings explored by the search
+    #[must_use]
+    pub fn with_marking_limit(mut self, marking_limit: Option<usize>) -> Self {
+        self.marking_limit = marking_limit;
+        self
+    }
+
+    /// Look for the shortest firing sequence which reaches a marking satisfying the target
+    ///
+    /// # Errors
+    /// Return [`NetError::InvalidPlace`] if the predicate uses an unknown or deleted place
+    pub fn run(&self) -> Result<Witness, NetError> {
+        let mut nodes = vec![];
+        let mut indices: HashMap<Marking<PlaceId>, usize> = HashMap::new();
+        // Nodes to explore ordered by estimated length of the sequence through them
+        let mut queue = BinaryHeap::new();
+
+        let initial = self.net.initial_marking();
+        let estimate = structural::firing_lower_bound(self.net, &initial, &self.target)?;
+        if let Some(estimate) = estimate {
+            queue.push(Reverse((estimate, 0, 0)));
+        }
+        indices.insert(initial.clone(), 0);
+        nodes.push(Node {
+            marking: initial,
+            distance: 0,
+            parent: None,
+            estimate,
+            closed: false,
+        });
+
+        let mut explored = 0;
+        while let Some(Reverse((_, distance, index))) = queue.pop() {
+            // Outdated entry, a shorter sequence to this node was found since
+            if nodes[index].closed || distance > nodes[index].distance {
+                continue;
+            }
+            if self.target.evaluate(&nodes[index].marking) {
+                return Ok(Witness::Found(Self::sequence(&nodes, index)));
+            }
+            explored += 1;
+            if let Some(limit) = self.marking_limit {
+                if explored > limit {
+                    return Ok(Witness::Unknown);
+                }
+            }
+            nodes[index].closed = true;
+
+            for transition in self
+                .net
+                .transitions
+                .iter()
+                .filter(|tr| !tr.deleted && !tr.is_disconnected())
+            {
+                if !transition.is_enabled(&nodes[index].marking) {
+                    continue;
+                }
+                let marking = transition.fire(&nodes[index].marking);
+                let next = match indices.get(&marking) {
+                    Some(&next) => {
+                        if nodes[next].closed || nodes[next].distance <= distance + 1 {
+                            continue;
+                        }
+                        nodes[next].distance = distance + 1;
+                        nodes[next].parent = Some((index, transition.id()));
+                        next
+                    }
+                    None => {
+                        let estimate =
+                            structural::firing_lower_bound(self.net, &marking, &self.target)?;
+                        indices.insert(marking.clone(), nodes.len());
+                        nodes.push(Node {
+                            marking,
+                            distance: distance + 1,
+                            parent: Some((index, transition.id())),
+                            estimate,
+                            closed: false,
+                        });
+                        nodes.len() - 1
+                    }
+                };
+                if let Some(estimate) = nodes[next].estimate {
+                    queue.push(Reverse((distance + 1 + estimate, distance + 1, next)));
+                }
+            }
+        }
+        Ok(Witness::Unreachable)
+    }
+
+    /// Rebuild the firing sequence which reaches a node
+    fn sequence(nodes: &[Node], mut index: usize) -> Vec<TransitionId> {
+        let mut sequence = vec![];
+        while let Some((parent, tr)) = nodes[index].parent {
+            sequence.push(tr);
+            index = parent;
+        }
+        sequence.reverse();
+        sequence
+    }
+
+    /// Look for the shortest firing sequence and return the names of its transitions
+    ///
+    /// Returns [`None`] if no sequence was found.
+    ///
+    /// # Errors
+    /// Return [`NetError::InvalidPlace`] if the predicate uses an unknown or deleted place
+    pub fn run_names(&self) -> Result<Option<Vec<String>>, NetError> {
+        Ok(match self.run()? {
+            Witness::Found(sequence) => Some(
+                sequence
+                    .into_iter()
+                    .map(|tr| {
+                        self.net
+                            .get_name_by_index(&NodeId::Transition(tr))
+                            .unwrap_or_default()
+                    })
+                    .collect(),
+            ),
+            _ => None,
+        })
+    }
+}
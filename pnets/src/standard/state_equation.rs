@@ -0,0 +1,328 @@
+//! Export of the state equation of a standard Petri net
+//!
+//! The state equation (or marking equation) of a net is `M = M0 + C·σ` where `C` is the incidence
+//! matrix of the net and `σ` the firing count vector. Every reachable marking is a non-negative
+//! integer solution of this equation, so if the equation together with a target [`Predicate`]
+//! has no solution, no marking satisfying the predicate is reachable.
+//!
+//! The equation can be written as a [CPLEX LP](Format::Lp) problem or as a
+//! [SMT-LIB2 QF_LIA](Format::SmtLib) script so it can be fed to external solvers.
+//!
+//! Variables are named after the nodes of the net: `m_<place>` for the marking of a place and
+//! `s_<transition>` for the number of times a transition is fired. The equation of a place is
+//! named `eq_<place>` and the constraints of the target predicate are named `target_<index>`.
+//! Characters which can not be used in identifiers are replaced with `_`.
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::io::Write;
+
+use crate::predicate::{Comparison, Predicate};
+use crate::standard::Net;
+use crate::{NetError, NodeId, PlaceId, TransitionId};
+
+/// Output format of the state equation
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Format {
+    /// CPLEX LP format
+    Lp,
+    /// SMT-LIB2 script for the `QF_LIA` logic
+    SmtLib,
+}
+
+/// Create a new state equation exporter from parameters
+pub struct StateEquationExporterBuilder<'w> {
+    writer: &'w mut dyn Write,
+    format: Format,
+    target: Option<Predicate>,
+}
+
+impl<'w> StateEquationExporterBuilder<'w> {
+    /// Create a new builder
+    ///
+    /// By default the equation is written in [`Format::Lp`] without target predicate
+    pub fn new(writer: &'w mut dyn Write) -> Self {
+        Self {
+            writer,
+            format: Format::Lp,
+            target: None,
+        }
+    }
+
+    /// Set the output format
+    pub fn with_format(self, format: Format) -> Self {
+        Self { format, ..self }
+    }
+
+    /// Set the target predicate added to the equation
+    pub fn with_target(self, target: Predicate) -> Self {
+        Self {
+            target: Some(target),
+            ..self
+        }
+    }
+
+    /// Build the exporter
+    pub fn build(self) -> StateEquationExporter<'w> {
+        StateEquationExporter {
+            writer: self.writer,
+            format: self.format,
+            target: self.target,
+        }
+    }
+}
+
+/// Exporter of the state equation of a [`Net`]
+pub struct StateEquationExporter<'w> {
+    writer: &'w mut dyn Write,
+    format: Format,
+    target: Option<Predicate>,
+}
+
+/// Linear expression, a list of (variable, coefficient)
+type Expression = Vec<(String, isize)>;
+
+/// Identifiers of the variables and constraints of the equation
+struct Identifiers {
+    places: HashMap<PlaceId, String>,
+    transitions: HashMap<TransitionId, String>,
+}
+
+impl Identifiers {
+    /// Compute identifiers for all nodes of the net which are not deleted
+    fn new(net: &Net) -> Self {
+        let mut used = HashSet::new();
+        let mut identifiers = Self {
+            places: HashMap::new(),
+            transitions: HashMap::new(),
+        };
+        for place in net.places.iter().filter(|pl| !pl.deleted) {
+            let name = Self::unique(net, NodeId::Place(place.id), &mut used);
+            identifiers.places.insert(place.id, name);
+        }
+        for transition in net.transitions.iter().filter(|tr| !tr.deleted) {
+            let name = Self::unique(net, NodeId::Transition(transition.id), &mut used);
+            identifiers.transitions.insert(transition.id, name);
+        }
+        identifiers
+    }
+
+    /// Sanitize the name of a node and make it unique
+    fn unique(net: &Net, id: NodeId, used: &mut HashSet<String>) -> String {
+        let sanitized: String = net
+            .get_name_by_index(&id)
+            .unwrap_or_default()
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '_' || c == '.' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        let mut name = sanitized.clone();
+        let mut suffix = 0;
+        while !used.insert(name.clone()) {
+            suffix += 1;
+            name = format!("{}_{}", sanitized, suffix);
+        }
+        name
+    }
+
+    fn place(&self, pl: PlaceId) -> Result<String, NetError> {
+        self.places
+            .get(&pl)
+            .map(|name| format!("m_{}", name))
+            .ok_or(NetError::InvalidPlace(pl))
+    }
+
+    fn transition(&self, tr: TransitionId) -> String {
+        format!("s_{}", self.transitions[&tr])
+    }
+}
+
+/// Equation of the net, computed once and written in any format
+struct Equation {
+    /// Marking variables
+    places: Vec<String>,
+    /// Firing count variables
+    transitions: Vec<String>,
+    /// Named constraints `expression <comparison> constant`
+    constraints: Vec<(String, Expression, Comparison, isize)>,
+}
+
+impl Equation {
+    fn new(net: &Net, target: Option<&Predicate>) -> Result<Self, NetError> {
+        let identifiers = Identifiers::new(net);
+        let mut equation = Self {
+            places: vec![],
+            transitions: vec![],
+            constraints: vec![],
+        };
+        for transition in net.transitions.iter().filter(|tr| !tr.deleted) {
+            equation
+                .transitions
+                .push(identifiers.transition(transition.id));
+        }
+        for place in net.places.iter().filter(|pl| !pl.deleted) {
+            let variable = identifiers.place(place.id)?;
+            // m_p - Σ C[p, t] * s_t = M0(p)
+            let mut expression = vec![(variable.clone(), 1)];
+            for (tr, produced, consumed) in place.produced_by.iter_with(&place.consumed_by) {
+                let weight = produced as isize - consumed as isize;
+                if weight != 0 {
+                    expression.push((identifiers.transition(tr), -weight));
+                }
+            }
+            equation.constraints.push((
+                format!("eq_{}", identifiers.places[&place.id]),
+                expression,
+                Comparison::Equal,
+                place.initial as isize,
+            ));
+            equation.places.push(variable);
+        }
+        if let Some(target) = target {
+            for (i, constraint) in target.constraints.iter().enumerate() {
+                if constraint.coefficients.is_empty() {
+                    return Err(NetError::InvalidConstraint);
+                }
+                let mut expression = vec![];
+                for &(pl, c) in &constraint.coefficients {
+                    expression.push((identifiers.place(pl)?, c));
+                }
+                equation.constraints.push((
+                    format!("target_{}", i),
+                    expression,
+                    constraint.comparison,
+                    constraint.constant,
+                ));
+            }
+        }
+        Ok(equation)
+    }
+}
+
+impl<'w> StateEquationExporter<'w> {
+    /// Export the state equation of a net
+    ///
+    /// # Errors
+    /// Return [`NetError::InvalidPlace`] if the target predicate references a deleted or unknown
+    /// place and [`NetError::InvalidConstraint`] if one of its constraints has no place.
+    pub fn export(&mut self, net: &Net) -> Result<(), Box<dyn Error>> {
+        let equation = Equation::new(net, self.target.as_ref())?;
+        match self.format {
+            Format::Lp => self.write_lp(net, &equation),
+            Format::SmtLib => self.write_smtlib(net, &equation),
+        }
+    }
+
+    /// Write a linear expression in LP format, splitting long expressions over several lines
+    fn write_lp_expression(
+        &mut self,
+        expression: &[(String, isize)],
+    ) -> Result<(), Box<dyn Error>> {
+        for (i, (variable, coefficient)) in expression.iter().enumerate() {
+            if i != 0 && i % 8 == 0 {
+                self.writer.write_all(b"\n   ")?;
+            }
+            let sign = match (i, *coefficient < 0) {
+                (0, false) => "",
+                (0, true) => "-",
+                (_, false) => " + ",
+                (_, true) => " - ",
+            };
+            match coefficient.abs() {
+                1 => self
+                    .writer
+                    .write_all(format!("{}{}", sign, variable).as_ref())?,
+                c => self
+                    .writer
+                    .write_all(format!("{}{} {}", sign, c, variable).as_ref())?,
+            }
+        }
+        Ok(())
+    }
+
+    fn write_lp(&mut self, net: &Net, equation: &Equation) -> Result<(), Box<dyn Error>> {
+        self.writer
+            .write_all(format!("\\ State equation of net {}\n", net.name).as_ref())?;
+        // Minimizing the length of the firing sequence gives a lower bound on witnesses
+        self.writer.write_all(b"Minimize\n obj: ")?;
+        let objective: Expression = equation
+            .transitions
+            .iter()
+            .map(|tr| (tr.clone(), 1))
+            .collect();
+        self.write_lp_expression(&objective)?;
+        self.writer.write_all(b"\nSubject To\n")?;
+        for (name, expression, comparison, constant) in &equation.constraints {
+            self.writer.write_all(format!(" {}: ", name).as_ref())?;
+            self.write_lp_expression(expression)?;
+            self.writer
+                .write_all(format!(" {} {}\n", comparison, constant).as_ref())?;
+        }
+        // Variables are non-negative by default in LP format
+        self.writer.write_all(b"General\n")?;
+        for variable in equation.places.iter().chain(equation.transitions.iter()) {
+            self.writer.write_all(format!(" {}\n", variable).as_ref())?;
+        }
+        self.writer.write_all(b"End\n")?;
+        Ok(())
+    }
+
+    /// Format a linear expression as a SMT-LIB term
+    fn smtlib_expression(expression: &[(String, isize)]) -> String {
+        let integer = |v: isize| {
+            if v < 0 {
+                format!("(- {})", -v)
+            } else {
+                v.to_string()
+            }
+        };
+        let terms: Vec<String> = expression
+            .iter()
+            .map(|(variable, coefficient)| match coefficient {
+                1 => variable.clone(),
+                -1 => format!("(- {})", variable),
+                &c => format!("(* {} {})", integer(c), variable),
+            })
+            .collect();
+        match terms.len() {
+            0 => "0".to_string(),
+            1 => terms[0].clone(),
+            _ => format!("(+ {})", terms.join(" ")),
+        }
+    }
+
+    fn write_smtlib(&mut self, net: &Net, equation: &Equation) -> Result<(), Box<dyn Error>> {
+        self.writer
+            .write_all(format!("; State equation of net {}\n", net.name).as_ref())?;
+        self.writer
+            .write_all(b"(set-option :produce-models true)\n(set-logic QF_LIA)\n")?;
+        for variable in equation.places.iter().chain(equation.transitions.iter()) {
+            self.writer.write_all(
+                format!("(declare-const {0} Int)\n(assert (>= {0} 0))\n", variable).as_ref(),
+            )?;
+        }
+        for (name, expression, comparison, constant) in &equation.constraints {
+            let constant = if *constant < 0 {
+                format!("(- {})", -constant)
+            } else {
+                constant.to_string()
+            };
+            self.writer.write_all(
+                format!(
+                    "(assert (! ({} {} {}) :named {}))\n",
+                    comparison,
+                    Self::smtlib_expression(expression),
+                    constant,
+                    name
+                )
+                .as_ref(),
+            )?;
+        }
+        self.writer.write_all(b"(check-sat)\n(get-model)\n")?;
+        Ok(())
+    }
+}
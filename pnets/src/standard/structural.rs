@@ -0,0 +1,220 @@
+//! Structural analyses based on the state equation
+//!
+//! Every reachable marking `M` of a net satisfies `M = M0 + C·σ` with `σ` a non-negative integer
+//! vector (see [`state_equation`][`crate::standard::state_equation`]). The functions of this
+//! module solve integer problems over this equation with the [`ilp`][`crate::ilp`] solver to
+//! compute upper bounds of places and to prove that some markings are not reachable.
+//!
+//! The firing count variables are eliminated from the marking variables, so the problems only
+//! have one variable per transition.
+use std::collections::HashMap;
+
+use indexed_vec::{Idx, IndexVec};
+use num_traits::ToPrimitive;
+
+use crate::ilp::{Goal, Problem, Solution};
+use crate::predicate::{Comparison, Predicate};
+use crate::standard::Net;
+use crate::{Marking, NetError, PlaceId, TransitionId};
+
+/// Maximum number of branch and bound nodes explored by the analyses of this module
+pub const NODE_LIMIT: usize = 10_000;
+
+/// Result of [`check_state_equation`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verdict {
+    /// The state equation has no solution, so the target is not reachable
+    Infeasible,
+    /// The state equation has a solution, this is the firing count of each transition
+    ///
+    /// The target may or may not be reachable.
+    Feasible(Marking<TransitionId>),
+    /// The solver reached [`NODE_LIMIT`] before the end of the resolution
+    Unknown,
+}
+
+/// Integer problem over the firing counts of the transitions of a net
+struct Equation<'n> {
+    net: &'n Net,
+    /// Marking from which transitions are fired
+    marking: Marking<PlaceId>,
+    /// Variable of each transition which is not deleted
+    variables: HashMap<TransitionId, usize>,
+    problem: Problem,
+}
+
+impl<'n> Equation<'n> {
+    /// Build the problem `M0 + C·σ >= 0`
+    fn new(net: &'n Net) -> Self {
+        Self::from_marking(net, net.initial_marking())
+    }
+
+    /// Build the problem `M + C·σ >= 0`
+    fn from_marking(net: &'n Net, marking: Marking<PlaceId>) -> Self {
+        let variables: HashMap<TransitionId, usize> = net
+            .transitions
+            .iter()
+            .filter(|tr| !tr.deleted)
+            .enumerate()
+            .map(|(i, tr)| (tr.id(), i))
+            .collect();
+        let mut equation = Self {
+            net,
+            marking,
+            problem: Problem::new(variables.len()),
+            variables,
+        };
+        equation.problem.set_node_limit(Some(NODE_LIMIT));
+        for place in net.places.iter().filter(|pl| !pl.deleted) {
+            let (coefficients, constant) = equation.expression(&[(place.id(), 1)]).unwrap();
+            equation
+                .problem
+                .add_constraint(&coefficients, Comparison::GreaterEqual, -constant);
+        }
+        equation
+    }
+
+    /// Express `Σ a_p * M'(p)` as `Σ b_t * σ(t) + constant` with `M' = M + C·σ`
+    fn expression(
+        &self,
+        coefficients: &[(PlaceId, isize)],
+    ) -> Result<(Vec<(usize, isize)>, isize), NetError> {
+        let mut constant = 0;
+        let mut expression = vec![0; self.variables.len()];
+        for &(pl, a) in coefficients {
+            if pl.index() >= self.net.places.len() || self.net[pl].deleted {
+                return Err(NetError::InvalidPlace(pl));
+            }
+            let place = &self.net[pl];
+            constant += a * self.marking[pl] as isize;
+            for (tr, produced, consumed) in place.produced_by.iter_with(&place.consumed_by) {
+                expression[self.variables[&tr]] += a * (produced as isize - consumed as isize);
+            }
+        }
+        Ok((
+            expression
+                .into_iter()
+                .enumerate()
+                .filter(|&(_, b)| b != 0)
+                .collect(),
+            constant,
+        ))
+    }
+
+    /// Add the constraints of a predicate to the problem
+    fn constrain(&mut self, predicate: &Predicate) -> Result<(), NetError> {
+        for constraint in &predicate.constraints {
+            let (coefficients, constant) = self.expression(&constraint.coefficients)?;
+            self.problem.add_constraint(
+                &coefficients,
+                constraint.comparison,
+                constraint.constant - constant,
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Compute a structural upper bound of `Σ coefficient * M(place)` over reachable markings
+///
+/// Returns [`None`] if the state equation does not bound the sum or if the solver found no bound.
+///
+/// # Errors
+/// Return [`NetError::InvalidPlace`] if a place is unknown or deleted
+pub fn upper_bound(
+    net: &Net,
+    coefficients: &[(PlaceId, isize)],
+) -> Result<Option<isize>, NetError> {
+    let mut equation = Equation::new(net);
+    let (objective, constant) = equation.expression(coefficients)?;
+    equation.problem.set_objective(Goal::Maximize, &objective);
+    let value = match equation.problem.solve() {
+        Solution::Optimal { value, .. } => value,
+        // The initial marking (σ = 0) is always a solution, so the problem is only reported
+        // infeasible if the solver failed and no bound is known
+        Solution::Unbounded | Solution::Infeasible => return Ok(None),
+        // The relaxation is still an upper bound of the integer problem
+        Solution::Unknown => match equation.problem.solve_relaxation() {
+            Solution::Optimal { value, .. } => value,
+            _ => return Ok(None),
+        },
+    };
+    Ok(value.floor().to_integer().to_isize().map(|v| v + constant))
+}
+
+/// Compute a structural upper bound of the marking of a place
+///
+/// Returns [`None`] if the state equation does not bound the place.
+///
+/// # Errors
+/// Return [`NetError::InvalidPlace`] if the place is unknown or deleted
+pub fn place_bound(net: &Net, place: PlaceId) -> Result<Option<usize>, NetError> {
+    Ok(upper_bound(net, &[(place, 1)])?.map(|v| v.max(0) as usize))
+}
+
+/// Compute structural upper bounds of all places of the net
+///
+/// Deleted places have a bound of `Some(0)`.
+#[must_use]
+pub fn place_bounds(net: &Net) -> IndexVec<PlaceId, Option<usize>> {
+    let mut bounds = IndexVec::default();
+    for place in &net.places {
+        bounds.push(if place.deleted {
+            Some(0)
+        } else {
+            place_bound(net, place.id()).unwrap()
+        });
+    }
+    bounds
+}
+
+/// Check if the state equation has a solution satisfying the target predicate
+///
+/// If it has no solution ([`Verdict::Infeasible`]), no reachable marking satisfies the target.
+///
+/// # Errors
+/// Return [`NetError::InvalidPlace`] if the predicate uses an unknown or deleted place
+pub fn check_state_equation(net: &Net, target: &Predicate) -> Result<Verdict, NetError> {
+    let mut equation = Equation::new(net);
+    equation.constrain(target)?;
+    // Look for the shortest firing count vector
+    let objective: Vec<(usize, isize)> = (0..equation.variables.len()).map(|v| (v, 1)).collect();
+    equation.problem.set_objective(Goal::Minimize, &objective);
+    Ok(match equation.problem.solve() {
+        Solution::Optimal { values, .. } => {
+            let mut firing_count = Marking::default();
+            for (&tr, &v) in &equation.variables {
+                let count = values[v].to_integer().to_usize().unwrap_or(usize::MAX);
+                if count != 0 {
+                    firing_count.insert_or_add(tr, count);
+                }
+            }
+            Verdict::Feasible(firing_count)
+        }
+        Solution::Infeasible => Verdict::Infeasible,
+        Solution::Unbounded | Solution::Unknown => Verdict::Unknown,
+    })
+}
+
+/// Compute a lower bound of the number of firings needed to reach the target from a marking
+///
+/// The bound is the optimum of the linear relaxation of the state equation, so it is cheaper to
+/// compute than [`check_state_equation`] but less precise. Returns [`None`] if the relaxation has
+/// no solution, so no marking satisfying the target is reachable from `marking`.
+///
+/// # Errors
+/// Return [`NetError::InvalidPlace`] if the predicate uses an unknown or deleted place
+pub fn firing_lower_bound(
+    net: &Net,
+    marking: &Marking<PlaceId>,
+    target: &Predicate,
+) -> Result<Option<usize>, NetError> {
+    let mut equation = Equation::from_marking(net, marking.clone());
+    equation.constrain(target)?;
+    let objective: Vec<(usize, isize)> = (0..equation.variables.len()).map(|v| (v, 1)).collect();
+    equation.problem.set_objective(Goal::Minimize, &objective);
+    Ok(match equation.problem.solve_relaxation() {
+        Solution::Optimal { value, .. } => Some(value.ceil().to_integer().to_usize().unwrap_or(0)),
+        _ => None,
+    })
+}
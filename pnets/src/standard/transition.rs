@@ -30,6 +30,27 @@ impl Transition {
         self.consume.is_empty() && self.produce.is_empty()
     }
 
+    /// Returns [`true`] if the marking has enough tokens to fire this transition
+    #[must_use]
+    pub fn is_enabled(&self, marking: &Marking<PlaceId>) -> bool {
+        self.consume.iter().all(|&(pl, w)| marking[pl] >= w)
+    }
+
+    /// Compute the marking reached by firing this transition
+    ///
+    /// The transition must be enabled in the marking, see [`Transition::is_enabled`].
+    #[must_use]
+    pub fn fire(&self, marking: &Marking<PlaceId>) -> Marking<PlaceId> {
+        let mut next = marking.clone();
+        for &(pl, w) in self.consume.iter() {
+            next.sub_or_delete(pl, w);
+        }
+        for &(pl, w) in self.produce.iter().filter(|&&(_, w)| w > 0) {
+            next.insert_or_add(pl, w);
+        }
+        next
+    }
+
     /// Get all arcs of this transition
     #[must_use]
     pub fn get_arcs(&self) -> Vec<arc::Kind> {
@@ -0,0 +1,290 @@
+//! Difference bound matrices
+//!
+//! A difference bound matrix (DBM) represents a conjunction of constraints `x_i - x_j ≺ c` where
+//! `≺` is `<` or `<=`. It is the representation of firing domains of state classes and of clock
+//! zones.
+//!
+//! The variable `x_0` is always zero, so `x_i ≺ c` is written `x_i - x_0 ≺ c` and `x_i ≻ c` is
+//! written `x_0 - x_i ≺ -c`. Each entry of the matrix is a signed rational [`Bound`]:
+//! [`Bound::Closed`] for `<=`, [`Bound::Open`] for `<` and [`Bound::Infinity`] when the
+//! difference is not bounded.
+//!
+//! Most operations expect a canonical matrix, where each entry is the tightest bound implied by
+//! the other ones. Operations which can break this property are followed by [`Dbm::close`].
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+
+use num_rational::Ratio;
+
+use crate::timed::{Bound, Time, TimeRange};
+use crate::NetError;
+
+/// Exact signed rational bound of a difference of variables
+///
+/// Differences are wider than [`Time`], so every time and the sums of times computed by the
+/// closure of a matrix are represented without overflow.
+pub type Difference = Ratio<i128>;
+
+const ZERO: Difference = Ratio::new_raw(0, 1);
+
+/// Compare two upper bounds, a smaller bound is tighter
+fn compare(left: Bound<Difference>, right: Bound<Difference>) -> Ordering {
+    match (left, right) {
+        (Bound::Infinity, Bound::Infinity) => Ordering::Equal,
+        (Bound::Infinity, _) => Ordering::Greater,
+        (_, Bound::Infinity) => Ordering::Less,
+        (Bound::Closed(a), Bound::Closed(b)) | (Bound::Open(a), Bound::Open(b)) => a.cmp(&b),
+        (Bound::Open(a), Bound::Closed(b)) => a.cmp(&b).then(Ordering::Less),
+        (Bound::Closed(a), Bound::Open(b)) => a.cmp(&b).then(Ordering::Greater),
+    }
+}
+
+/// Sum of two upper bounds
+fn add(left: Bound<Difference>, right: Bound<Difference>) -> Bound<Difference> {
+    match (left, right) {
+        (Bound::Infinity, _) | (_, Bound::Infinity) => Bound::Infinity,
+        (Bound::Closed(a), Bound::Closed(b)) => Bound::Closed(a + b),
+        (Bound::Open(a), Bound::Closed(b))
+        | (Bound::Closed(a), Bound::Open(b))
+        | (Bound::Open(a), Bound::Open(b)) => Bound::Open(a + b),
+    }
+}
+
+/// Convert a time to a signed value, negated if `negate` is true
+pub(crate) fn difference(time: Time, negate: bool) -> Difference {
+    let value = Ratio::new_raw(i128::from(*time.numer()), i128::from(*time.denom()));
+    if negate {
+        -value
+    } else {
+        value
+    }
+}
+
+/// Convert a bound of a time range to a signed bound, negated if `negate` is true
+pub(crate) fn signed(bound: Bound, negate: bool) -> Bound<Difference> {
+    match bound {
+        Bound::Closed(v) => Bound::Closed(difference(v, negate)),
+        Bound::Open(v) => Bound::Open(difference(v, negate)),
+        Bound::Infinity => Bound::Infinity,
+    }
+}
+
+/// Convert a signed bound to a bound of a time range, negative values are replaced by zero
+///
+/// # Errors
+/// Return [`NetError::InvalidTimeRange`] if the value is too large for a [`Time`]
+pub(crate) fn unsigned(bound: Bound<Difference>) -> Result<Bound, NetError> {
+    let time = |v: Difference| {
+        let v = v.max(ZERO);
+        match (u64::try_from(*v.numer()), u64::try_from(*v.denom())) {
+            (Ok(numer), Ok(denom)) => Ok(Time::new_raw(numer, denom)),
+            _ => Err(NetError::InvalidTimeRange),
+        }
+    };
+    Ok(match bound {
+        Bound::Closed(v) => Bound::Closed(time(v)?),
+        Bound::Open(v) => Bound::Open(time(v)?),
+        Bound::Infinity => Bound::Infinity,
+    })
+}
+
+/// Difference bound matrix over non-negative variables `x_1 … x_n`
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Dbm {
+    /// Number of variables, including `x_0`
+    size: usize,
+    /// Entries in row-major order, `entries[i * size + j]` bounds `x_i - x_j`
+    entries: Vec<Bound<Difference>>,
+}
+
+impl Dbm {
+    /// Create a canonical matrix with `variables` non-negative and unbounded variables
+    #[must_use]
+    pub fn new(variables: usize) -> Self {
+        let size = variables + 1;
+        let mut entries = vec![Bound::Infinity; size * size];
+        for i in 0..size {
+            entries[i * size + i] = Bound::Closed(ZERO);
+            entries[i] = Bound::Closed(ZERO);
+        }
+        Self { size, entries }
+    }
+
+    /// Number of variables, without `x_0`
+    #[must_use]
+    pub fn variables(&self) -> usize {
+        self.size - 1
+    }
+
+    /// Upper bound of `x_i - x_j`
+    #[must_use]
+    pub fn get(&self, i: usize, j: usize) -> Bound<Difference> {
+        self.entries[i * self.size + j]
+    }
+
+    /// Add the constraint `x_i - x_j ≺ bound`
+    ///
+    /// The matrix is no longer canonical, see [`Dbm::close`].
+    pub fn constrain(&mut self, i: usize, j: usize, bound: Bound<Difference>) {
+        let entry = &mut self.entries[i * self.size + j];
+        if compare(bound, *entry) == Ordering::Less {
+            *entry = bound;
+        }
+    }
+
+    /// Constrain `x_i` to be in a time range
+    ///
+    /// The matrix is no longer canonical, see [`Dbm::close`].
+    pub fn constrain_range(&mut self, i: usize, range: TimeRange) {
+        if range.start != Bound::Infinity {
+            self.constrain(0, i, signed(range.start, true));
+        }
+        self.constrain(i, 0, signed(range.end, false));
+    }
+
+    /// Lower bound of `x_i`, [`Bound::Infinity`] if the matrix is empty
+    #[must_use]
+    pub fn lower(&self, i: usize) -> Bound<Difference> {
+        match self.get(0, i) {
+            Bound::Closed(c) => Bound::Closed(-c),
+            Bound::Open(c) => Bound::Open(-c),
+            Bound::Infinity => Bound::Infinity,
+        }
+    }
+
+    /// Upper bound of `x_i`
+    #[must_use]
+    pub fn upper(&self, i: usize) -> Bound<Difference> {
+        self.get(i, 0)
+    }
+
+    /// Compute the canonical form of the matrix (Floyd–Warshall)
+    pub fn close(&mut self) {
+        for k in 0..self.size {
+            for i in 0..self.size {
+                let ik = self.get(i, k);
+                if ik == Bound::Infinity {
+                    continue;
+                }
+                for j in 0..self.size {
+                    let bound = add(ik, self.get(k, j));
+                    self.constrain(i, j, bound);
+                }
+            }
+        }
+    }
+
+    /// Returns [`true`] if the canonical matrix has no solution
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        (0..self.size).any(|i| compare(self.get(i, i), Bound::Closed(ZERO)) == Ordering::Less)
+    }
+
+    /// Compute the intersection of two matrices over the same variables
+    ///
+    /// The result is canonical.
+    #[must_use]
+    pub fn intersect(&self, other: &Self) -> Self {
+        assert_eq!(self.size, other.size, "Matrices have different dimensions");
+        let mut result = self.clone();
+        for (entry, &bound) in result.entries.iter_mut().zip(other.entries.iter()) {
+            if compare(bound, *entry) == Ordering::Less {
+                *entry = bound;
+            }
+        }
+        result.close();
+        result
+    }
+
+    /// Returns [`true`] if all solutions of `other` are solutions of this matrix
+    ///
+    /// Both matrices must be canonical and have the same variables.
+    #[must_use]
+    pub fn includes(&self, other: &Self) -> bool {
+        assert_eq!(self.size, other.size, "Matrices have different dimensions");
+        other.is_empty()
+            || self
+                .entries
+                .iter()
+                .zip(other.entries.iter())
+                .all(|(&bound, &o)| compare(o, bound) != Ordering::Greater)
+    }
+
+    /// Assign `value` to `x_i`
+    ///
+    /// The matrix must be canonical and stays canonical.
+    pub fn reset(&mut self, i: usize, value: Difference) {
+        for j in 0..self.size {
+            if j != i {
+                let upper = add(Bound::Closed(value), self.get(0, j));
+                let lower = add(self.get(j, 0), Bound::Closed(-value));
+                self.entries[i * self.size + j] = upper;
+                self.entries[j * self.size + i] = lower;
+            }
+        }
+    }
+
+    /// Remove the upper bounds of all variables: let time elapse when variables are clocks
+    ///
+    /// The matrix must be canonical and stays canonical.
+    pub fn up(&mut self) {
+        for i in 1..self.size {
+            self.entries[i * self.size] = Bound::Infinity;
+        }
+    }
+
+    /// Let time elapse for some variables only, the other ones are stopped clocks
+    ///
+    /// The matrix must be canonical and stays canonical.
+    pub fn up_running(&mut self, running: &[usize]) {
+        for &i in running {
+            self.entries[i * self.size] = Bound::Infinity;
+            for j in 1..self.size {
+                if !running.contains(&j) {
+                    self.entries[i * self.size + j] = Bound::Infinity;
+                }
+            }
+        }
+    }
+
+    /// Extrapolate the matrix with the maximal constant compared to each variable
+    ///
+    /// Bounds greater than the constants are removed, so the number of distinct matrices built
+    /// by an exploration is finite. `constants[i]` is the constant of the variable `i + 1`. The
+    /// result contains the original matrix and is canonical.
+    pub fn extrapolate(&mut self, constants: &[Difference]) {
+        let constant = |i: usize| if i == 0 { ZERO } else { constants[i - 1] };
+        for i in 0..self.size {
+            for j in 0..self.size {
+                if i == j {
+                    continue;
+                }
+                let entry = &mut self.entries[i * self.size + j];
+                if compare(*entry, Bound::Closed(constant(i))) == Ordering::Greater {
+                    *entry = Bound::Infinity;
+                } else if compare(*entry, Bound::Closed(-constant(j))) == Ordering::Less {
+                    *entry = Bound::Open(-constant(j));
+                }
+            }
+        }
+        self.close();
+    }
+
+    /// Project the matrix on some variables
+    ///
+    /// The `i`-th variable of the result is the variable `variables[i]` of this matrix. The
+    /// matrix must be canonical and the result is canonical.
+    #[must_use]
+    pub fn project(&self, variables: &[usize]) -> Self {
+        let mut indices = vec![0];
+        indices.extend_from_slice(variables);
+        let size = indices.len();
+        let mut entries = Vec::with_capacity(size * size);
+        for &i in &indices {
+            for &j in &indices {
+                entries.push(self.get(i, j));
+            }
+        }
+        Self { size, entries }
+    }
+}
@@ -0,0 +1,191 @@
+//! Discrete-time simulation of time Petri nets
+//!
+//! A [`DiscreteSimulator`] follows the discrete-time semantics of a [`Net`]: the clock of each
+//! enabled transition counts the time units elapsed since its enabling, time elapses one unit at
+//! a time, and a transition can be fired when its clock is in its time range.
+//!
+//! Time can not elapse beyond the latest firing time of an enabled transition: such a transition
+//! must be fired (or disabled) first. A transition can not be fired if a firable transition has
+//! priority over it. Clocks suspended by [stopwatch arcs][`crate::arc::Kind::StopWatch`] do not
+//! progress and their transitions can not be fired.
+use crate::timed::{Bound, Net, Time};
+use crate::{Marking, NetError, PlaceId, TransitionId};
+
+/// State of a discrete-time simulation
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct DiscreteState {
+    /// Marking of the net
+    pub marking: Marking<PlaceId>,
+    /// Clock of each enabled transition
+    pub clocks: Marking<TransitionId>,
+    /// Time elapsed since the beginning of the simulation
+    pub time: usize,
+}
+
+/// Step-by-step discrete-time simulator
+pub struct DiscreteSimulator<'n> {
+    net: &'n Net,
+    state: DiscreteState,
+    /// Fired transitions and their firing dates
+    history: Vec<(TransitionId, usize)>,
+}
+
+impl<'n> DiscreteSimulator<'n> {
+    /// Create a new simulation of a net from its initial marking
+    #[must_use]
+    pub fn new(net: &'n Net) -> Self {
+        let marking = net.initial_marking();
+        let mut clocks = Marking::default();
+        for tr in net.enabled_transitions(&marking) {
+            clocks.insert_or_add(tr, 0);
+        }
+        Self {
+            net,
+            state: DiscreteState {
+                marking,
+                clocks,
+                time: 0,
+            },
+            history: vec![],
+        }
+    }
+
+    /// Current state of the simulation
+    #[must_use]
+    pub fn state(&self) -> &DiscreteState {
+        &self.state
+    }
+
+    /// Fired transitions and their firing dates, in order
+    #[must_use]
+    pub fn history(&self) -> &[(TransitionId, usize)] {
+        &self.history
+    }
+
+    /// Transitions which can be fired now
+    #[must_use]
+    pub fn firable(&self) -> Vec<TransitionId> {
+        let candidates: Vec<TransitionId> = self
+            .state
+            .clocks
+            .iter()
+            .filter(|&&(tr, clock)| {
+                self.net[tr].is_active(&self.state.marking)
+                    && self.net[tr].time.contains(Time::from_integer(clock as u64))
+            })
+            .map(|&(tr, _)| tr)
+            .collect();
+        candidates
+            .iter()
+            .copied()
+            .filter(|&tr| {
+                !candidates
+                    .iter()
+                    .any(|&other| self.net[other].priorities.contains(&tr))
+            })
+            .collect()
+    }
+
+    /// Transition which prevents time from elapsing, [`None`] if time can elapse
+    #[must_use]
+    pub fn urgent(&self) -> Option<TransitionId> {
+        self.state
+            .clocks
+            .iter()
+            .find(|&&(tr, clock)| {
+                let next = Time::from_integer(clock as u64 + 1);
+                self.net[tr].is_active(&self.state.marking)
+                    && match self.net[tr].time.end {
+                        Bound::Closed(end) => next > end,
+                        Bound::Open(end) => next >= end,
+                        Bound::Infinity => false,
+                    }
+            })
+            .map(|&(tr, _)| tr)
+    }
+
+    /// Let one time unit elapse
+    ///
+    /// # Errors
+    /// Return [`NetError::UrgentTransition`] if a transition must be fired before
+    pub fn tick(&mut self) -> Result<(), NetError> {
+        if let Some(tr) = self.urgent() {
+            return Err(NetError::UrgentTransition(tr));
+        }
+        let mut clocks = Marking::default();
+        for &(tr, clock) in self.state.clocks.iter() {
+            if self.net[tr].is_active(&self.state.marking) {
+                clocks.insert_or_add(tr, clock + 1);
+            } else {
+                clocks.insert_or_add(tr, clock);
+            }
+        }
+        self.state.clocks = clocks;
+        self.state.time += 1;
+        Ok(())
+    }
+
+    /// Fire a transition now
+    ///
+    /// Transitions which stay enabled during the firing keep their clock, the clocks of newly
+    /// enabled transitions start at zero.
+    ///
+    /// # Errors
+    /// Return [`NetError::NotFirable`] if the transition is not in [`DiscreteSimulator::firable`]
+    pub fn fire(&mut self, transition: TransitionId) -> Result<(), NetError> {
+        if !self.firable().contains(&transition) {
+            return Err(NetError::NotFirable(transition));
+        }
+        let fired = &self.net[transition];
+        let intermediate = fired.intermediate(&self.state.marking);
+        let marking = fired.fire(&self.state.marking);
+        let mut clocks = Marking::default();
+        for tr in self.net.enabled_transitions(&marking) {
+            let clock = if tr != transition && self.net[tr].is_enabled(&intermediate) {
+                self.state.clocks[tr]
+            } else {
+                0
+            };
+            clocks.insert_or_add(tr, clock);
+        }
+        self.state.marking = marking;
+        self.state.clocks = clocks;
+        self.history.push((transition, self.state.time));
+        Ok(())
+    }
+
+    /// Returns [`true`] if letting time elapse can make an enabled transition firable
+    fn can_elapse_to_firing(&self) -> bool {
+        self.state.clocks.iter().any(|&(tr, _)| {
+            self.net[tr].is_active(&self.state.marking)
+                && self.net[tr].time.start != Bound::Infinity
+        })
+    }
+
+    /// Fire transitions as soon as possible, the first firable one in the order of the net
+    ///
+    /// The simulation stops after `firing_limit` firings, when no transition is enabled, or
+    /// when no transition is firable and time can not elapse. It also stops when no transition
+    /// is firable and none can become firable by letting time elapse: all enabled transitions
+    /// are suspended by stopwatch arcs or have a time range starting at infinity. Returns the
+    /// number of firings.
+    pub fn run_eager(&mut self, firing_limit: usize) -> usize {
+        let mut firings = 0;
+        while firings < firing_limit && !self.state.clocks.is_empty() {
+            match self.firable().first() {
+                Some(&tr) => {
+                    if self.fire(tr).is_err() {
+                        break;
+                    }
+                    firings += 1;
+                }
+                None => {
+                    if !self.can_elapse_to_firing() || self.tick().is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+        firings
+    }
+}
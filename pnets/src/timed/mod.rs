@@ -4,10 +4,17 @@
 
 pub use net::Net;
 pub use place::Place;
-pub use time_range::{Bound, TimeRange};
+pub use time_range::{Bound, Time, TimeRange};
 pub use transition::Transition;
 
+pub mod dbm;
+pub mod discrete;
 mod net;
 mod place;
+pub mod priority;
+pub mod schedule;
+pub mod state_class;
 pub mod time_range;
 mod transition;
+pub mod untime;
+pub mod zone;
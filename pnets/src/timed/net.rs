@@ -3,12 +3,15 @@ use std::ops::{Index, IndexMut};
 
 use indexed_vec::IndexVec;
 
+use crate::metadata::Metadata;
 use crate::net::NodeId;
+use crate::timed::priority::PriorityGraph;
+use crate::timed::untime;
 use crate::timed::{Place, Transition};
-use crate::{arc, standard, NetError, PlaceId, TransitionId};
+use crate::{arc, standard, Marking, NetError, PlaceId, TransitionId};
 use bimap::BiMap;
 
-/// Timed Petri net, with produce, consume, condition and inhibitors arcs
+/// Timed Petri net, with produce, consume, condition, inhibitors and stopwatch arcs
 ///
 /// This structure is indexed with [`PlaceId`] and [`TransitionId`] to allow easy access to places
 /// and transitions.
@@ -27,6 +30,8 @@ pub struct Net {
     pub transitions: IndexVec<TransitionId, Transition>,
     /// Places of the net
     pub places: IndexVec<PlaceId, Place>,
+    /// Notes and comments of the net
+    pub metadata: Metadata,
 }
 
 impl Index<TransitionId> for Net {
@@ -62,6 +67,7 @@ impl From<&standard::Net> for Net {
         // Crate a new net
         let mut net = Net {
             name: standard.name.clone(),
+            metadata: standard.metadata.clone(),
             ..Net::default()
         };
 
@@ -130,6 +136,27 @@ impl Net {
         self.transitions.last_idx().unwrap()
     }
 
+    /// Marking of the net before any firing
+    #[must_use]
+    pub fn initial_marking(&self) -> Marking<PlaceId> {
+        let mut marking = Marking::default();
+        for place in self.places.iter().filter(|pl| pl.initial > 0) {
+            marking.insert_or_add(place.id(), place.initial);
+        }
+        marking
+    }
+
+    /// Transitions which are connected and enabled by a marking, see [`Transition::is_enabled`]
+    pub fn enabled_transitions<'n>(
+        &'n self,
+        marking: &'n Marking<PlaceId>,
+    ) -> impl Iterator<Item = TransitionId> + 'n {
+        self.transitions
+            .iter()
+            .filter(move |tr| !tr.is_disconnected() && tr.is_enabled(marking))
+            .map(Transition::id)
+    }
+
     /// Get node name with its id
     pub fn get_name_by_index(&self, index: &NodeId) -> Option<String> {
         self.id_index_map.get_by_right(index).map(|v| v.clone())
@@ -156,11 +183,7 @@ impl Net {
         }
     }
 
-    /// Add an arc in the net. This kind of net support only [`arc::Kind::Consume`],
-    /// [`arc::Kind::Produce`], [`arc::Kind::Inhibitor`] and [`arc::Kind::Test`] arcs.
-    ///
-    /// # Errors
-    /// Return [`NetError::UnsupportedArc`] when trying to add a kind of arc which is not supported
+    /// Add an arc in the net. This kind of net support all kinds of arcs.
     pub fn add_arc(&mut self, arc: arc::Kind) -> Result<(), Box<dyn Error>> {
         match arc {
             arc::Kind::Consume(pl_id, tr_id, w) => {
@@ -183,7 +206,25 @@ impl Net {
                 self.places[pl_id].inhibitor_for.insert_or_min(tr_id, w);
                 Ok(())
             }
-            a => Err(Box::new(NetError::UnsupportedArc(a))),
+            arc::Kind::StopWatch(pl_id, tr_id, w) => {
+                self.transitions[tr_id].stopwatches.insert_or_max(pl_id, w);
+                self.places[pl_id].stopwatch_for.insert_or_max(tr_id, w);
+                Ok(())
+            }
+            arc::Kind::StopWatchInhibitor(pl_id, tr_id, w) => {
+                self.transitions[tr_id]
+                    .stopwatch_inhibitors
+                    .insert_or_min(pl_id, w);
+                self.places[pl_id]
+                    .stopwatch_inhibitor_for
+                    .insert_or_min(tr_id, w);
+                Ok(())
+            }
+            arc::Kind::Reset(pl_id, tr_id, w) => {
+                self.transitions[tr_id].resets.insert_or_max(pl_id, w);
+                self.places[pl_id].reset_by.insert_or_max(tr_id, w);
+                Ok(())
+            }
         }
     }
 
@@ -201,13 +242,25 @@ impl Net {
         for &(tr, _) in self.places[place].inhibitor_for.iter() {
             self.transitions[tr].inhibitors.delete(place);
         }
+        for &(tr, _) in self.places[place].stopwatch_for.iter() {
+            self.transitions[tr].stopwatches.delete(place);
+        }
+        for &(tr, _) in self.places[place].stopwatch_inhibitor_for.iter() {
+            self.transitions[tr].stopwatch_inhibitors.delete(place);
+        }
         for &(tr, _) in self.places[place].produced_by.iter() {
             self.transitions[tr].produce.delete(place);
         }
+        for &(tr, _) in self.places[place].reset_by.iter() {
+            self.transitions[tr].resets.delete(place);
+        }
         self.places[place].consumed_by.clear();
         self.places[place].condition_for.clear();
         self.places[place].inhibitor_for.clear();
+        self.places[place].stopwatch_for.clear();
+        self.places[place].stopwatch_inhibitor_for.clear();
         self.places[place].produced_by.clear();
+        self.places[place].reset_by.clear();
     }
 
     /// Disconnect a transition in the net
@@ -230,11 +283,26 @@ impl Net {
         for &(pl, _) in self.transitions[transition].conditions.iter() {
             self.places[pl].condition_for.delete(transition);
         }
+
+        for &(pl, _) in self.transitions[transition].stopwatches.iter() {
+            self.places[pl].stopwatch_for.delete(transition);
+        }
+
+        for &(pl, _) in self.transitions[transition].stopwatch_inhibitors.iter() {
+            self.places[pl].stopwatch_inhibitor_for.delete(transition);
+        }
+
+        for &(pl, _) in self.transitions[transition].resets.iter() {
+            self.places[pl].reset_by.delete(transition);
+        }
         self.transitions[transition].consume.clear();
         self.transitions[transition].produce.clear();
         self.transitions[transition].priorities.clear();
         self.transitions[transition].inhibitors.clear();
         self.transitions[transition].conditions.clear();
+        self.transitions[transition].stopwatches.clear();
+        self.transitions[transition].stopwatch_inhibitors.clear();
+        self.transitions[transition].resets.clear();
     }
 
     /// Add a priority relation in the net
@@ -245,49 +313,27 @@ impl Net {
         }
     }
 
-    /// Update all priorities to make a transitive closure
+    /// Graph of the priority relation of the net
+    #[must_use]
+    pub fn priority_graph(&self) -> PriorityGraph {
+        PriorityGraph::new(self)
+    }
+
+    /// Untimed over-approximation of the net, see [`untime`][`crate::timed::untime`]
     ///
     /// # Errors
     /// `NetError::CyclicPriorities` is returned if there is a cyclic priority in the net
-    pub fn update_priorities(&mut self) -> Result<(), Box<dyn Error>> {
-        let mut done = IndexVec::<TransitionId, bool>::default();
-        for tr in self.transitions.iter() {
-            done.push(tr.priorities.is_empty());
-        }
-
-        if !done.iter().any(|&v| v) {
-            return Err(Box::new(NetError::CyclicPriorities));
-        }
+    pub fn untimed(&self) -> Result<Self, NetError> {
+        untime::untime(self)
+    }
 
-        loop {
-            if !done.iter().any(|&v| !v) {
-                return Ok(());
-            }
-            let to_change: Vec<TransitionId> = done
-                .iter()
-                .enumerate()
-                .filter_map(|(i, &v)| if v { None } else { Some(TransitionId::from(i)) })
-                .collect();
-            let mut changed = false;
-            for current_index in to_change {
-                done[current_index] = self.transitions[current_index]
-                    .priorities
-                    .iter()
-                    .all(|&i| done[i]);
-                if done[current_index] {
-                    changed = true;
-                    let mut to_extend = vec![];
-                    for &sub_index in &self.transitions[current_index].priorities {
-                        to_extend.extend(&self.transitions[sub_index].priorities);
-                    }
-                    for e in to_extend {
-                        self.add_priority(current_index, e);
-                    }
-                }
-            }
-            if !changed {
-                return Err(Box::new(NetError::CyclicPriorities));
-            }
-        }
+    /// Update all priorities to make a transitive closure
+    ///
+    /// # Errors
+    /// `NetError::CyclicPriorities` is returned with the names of the transitions of a cycle if
+    /// there is a cyclic priority in the net
+    pub fn update_priorities(&mut self) -> Result<(), Box<dyn Error>> {
+        self.priority_graph().transitive_closure()?.apply(self);
+        Ok(())
     }
 }
@@ -19,6 +19,12 @@ pub struct Place {
     pub condition_for: Marking<TransitionId>,
     /// Transitions that has inhibitor on this place
     pub inhibitor_for: Marking<TransitionId>,
+    /// Transitions that has stopwatch on this place
+    pub stopwatch_for: Marking<TransitionId>,
+    /// Transitions that has stopwatch inhibitor on this place
+    pub stopwatch_inhibitor_for: Marking<TransitionId>,
+    /// Transitions that reset this place
+    pub reset_by: Marking<TransitionId>,
 }
 
 impl Place {
@@ -35,5 +41,8 @@ impl Place {
             && self.consumed_by.is_empty()
             && self.condition_for.is_empty()
             && self.inhibitor_for.is_empty()
+            && self.stopwatch_for.is_empty()
+            && self.stopwatch_inhibitor_for.is_empty()
+            && self.reset_by.is_empty()
     }
 }
@@ -0,0 +1,182 @@
+//! Priority relation of time Petri nets
+//!
+//! A transition with priority over another one is fired first when both can be fired. The
+//! relation is a strict partial order: it must be acyclic, and its transitive closure gives all
+//! the pairs of ordered transitions. A [`PriorityGraph`] computes the closure, the transitive
+//! reduction (the smallest relation with the same closure, used to export priorities) and finds
+//! cycles.
+use indexed_vec::IndexVec;
+
+use crate::net::NodeId;
+use crate::timed::Net;
+use crate::{NetError, TransitionId};
+
+/// Graph of the priority relation, with an edge from each transition to the transitions over
+/// which it has priority
+#[derive(Debug, Clone)]
+pub struct PriorityGraph {
+    /// Transitions over which each transition has priority, in increasing order
+    priorities: IndexVec<TransitionId, Vec<TransitionId>>,
+    /// Names of transitions, used to report cycles
+    names: IndexVec<TransitionId, String>,
+}
+
+impl PriorityGraph {
+    /// Create the graph of the priorities of a net
+    #[must_use]
+    pub fn new(net: &Net) -> Self {
+        let mut priorities = IndexVec::new();
+        let mut names = IndexVec::new();
+        for (tr, transition) in net.transitions.iter_enumerated() {
+            let mut over = transition.priorities.clone();
+            over.sort();
+            over.dedup();
+            priorities.push(over);
+            names.push(
+                net.get_name_by_index(&NodeId::Transition(tr))
+                    .unwrap_or_default(),
+            );
+        }
+        Self { priorities, names }
+    }
+
+    /// Transitions over which a transition has priority
+    #[must_use]
+    pub fn priorities(&self, transition: TransitionId) -> &[TransitionId] {
+        &self.priorities[transition]
+    }
+
+    /// Returns [`true`] if the graph has an edge from `transition` to `over`
+    ///
+    /// On a [transitive closure][`PriorityGraph::transitive_closure`], this is the partial order
+    /// of priorities.
+    #[must_use]
+    pub fn has_priority(&self, transition: TransitionId, over: TransitionId) -> bool {
+        self.priorities[transition].binary_search(&over).is_ok()
+    }
+
+    /// Number of edges of the graph
+    #[must_use]
+    pub fn edge_count(&self) -> usize {
+        self.priorities.iter().map(Vec::len).sum()
+    }
+
+    /// Depth-first search of the graph
+    ///
+    /// Returns the transitions in post-order (a transition after all transitions over which it
+    /// has priority) or the transitions of a cycle.
+    fn post_order(&self) -> Result<Vec<TransitionId>, Vec<TransitionId>> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum Color {
+            White,
+            Grey,
+            Black,
+        }
+        let mut colors: IndexVec<TransitionId, Color> = IndexVec::new();
+        for _ in self.priorities.iter() {
+            colors.push(Color::White);
+        }
+        let mut order = Vec::with_capacity(self.priorities.len());
+        for (root, _) in self.priorities.iter_enumerated() {
+            if colors[root] != Color::White {
+                continue;
+            }
+            colors[root] = Color::Grey;
+            // Transitions on the current path and index of their next successor to visit
+            let mut stack = vec![(root, 0)];
+            while let Some(&mut (tr, ref mut next)) = stack.last_mut() {
+                match self.priorities[tr].get(*next).copied() {
+                    Some(over) => {
+                        *next += 1;
+                        match colors[over] {
+                            Color::White => {
+                                colors[over] = Color::Grey;
+                                stack.push((over, 0));
+                            }
+                            Color::Grey => {
+                                let start = stack.iter().position(|&(t, _)| t == over).unwrap();
+                                return Err(stack[start..].iter().map(|&(t, _)| t).collect());
+                            }
+                            Color::Black => {}
+                        }
+                    }
+                    None => {
+                        colors[tr] = Color::Black;
+                        order.push(tr);
+                        stack.pop();
+                    }
+                }
+            }
+        }
+        Ok(order)
+    }
+
+    /// Find a cycle of priorities
+    ///
+    /// Returns the transitions of the cycle in order, each one has priority over the next one
+    /// and the last one has priority over the first one.
+    #[must_use]
+    pub fn find_cycle(&self) -> Option<Vec<TransitionId>> {
+        self.post_order().err()
+    }
+
+    /// Error describing a cycle with the names of its transitions
+    fn cycle_error(&self, cycle: &[TransitionId]) -> NetError {
+        NetError::CyclicPriorities(cycle.iter().map(|&tr| self.names[tr].clone()).collect())
+    }
+
+    /// Compute the transitive closure of the relation
+    ///
+    /// # Errors
+    /// Return [`NetError::CyclicPriorities`] with the names of the transitions of a cycle if the
+    /// relation is cyclic
+    pub fn transitive_closure(&self) -> Result<Self, NetError> {
+        let order = self
+            .post_order()
+            .map_err(|cycle| self.cycle_error(&cycle))?;
+        let mut closure = self.clone();
+        // Transitions are visited after all transitions over which they have priority
+        for tr in order {
+            let mut reachable = self.priorities[tr].clone();
+            for &over in &self.priorities[tr] {
+                reachable.extend_from_slice(&closure.priorities[over]);
+            }
+            reachable.sort();
+            reachable.dedup();
+            closure.priorities[tr] = reachable;
+        }
+        Ok(closure)
+    }
+
+    /// Compute the transitive reduction of the relation
+    ///
+    /// The reduction has the same transitive closure as this relation with as few edges as
+    /// possible.
+    ///
+    /// # Errors
+    /// Return [`NetError::CyclicPriorities`] with the names of the transitions of a cycle if the
+    /// relation is cyclic
+    pub fn transitive_reduction(&self) -> Result<Self, NetError> {
+        let closure = self.transitive_closure()?;
+        let mut reduction = closure.clone();
+        for (tr, over) in closure.priorities.iter_enumerated() {
+            reduction.priorities[tr] = over
+                .iter()
+                .copied()
+                .filter(|&target| {
+                    !over
+                        .iter()
+                        .any(|&other| other != target && closure.has_priority(other, target))
+                })
+                .collect();
+        }
+        Ok(reduction)
+    }
+
+    /// Replace the priorities of the transitions of a net by the relation of this graph
+    pub fn apply(&self, net: &mut Net) {
+        for (tr, over) in self.priorities.iter_enumerated() {
+            net[tr].priorities = over.clone();
+        }
+    }
+}
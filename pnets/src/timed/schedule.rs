@@ -0,0 +1,149 @@
+//! Earliest and latest firing dates of a firing sequence
+//!
+//! The dates at which the transitions of a sequence can be fired are the solutions of a
+//! conjunction of difference constraints: each transition is fired in its time range relative
+//! to the date of its enabling, after the previous firing, and before the latest firing time of
+//! every transition enabled at this moment. These constraints are solved in dense time with a
+//! [`Dbm`], so the bounds of each firing date are exact.
+//!
+//! As in [state class graphs][`crate::timed::state_class`], a transition with priority over
+//! another one must be strictly before its earliest firing time when the other one is fired.
+use crate::arc;
+use crate::timed::dbm::{signed, unsigned, Dbm, Difference};
+use crate::timed::{Bound, Net, Time, TimeRange};
+use crate::{Marking, NetError, TransitionId};
+
+/// Firing dates of a transition of a sequence
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ScheduledFiring {
+    /// Fired transition
+    pub transition: TransitionId,
+    /// Dates at which the transition can be fired, relative to the beginning of the sequence
+    pub dates: TimeRange,
+}
+
+/// Firing dates of all transitions of a sequence
+#[derive(Debug, Clone)]
+pub struct Schedule {
+    /// Firings of the sequence, in order
+    pub firings: Vec<ScheduledFiring>,
+    /// Firing dates of the sequence, the variable `k` is the date of the `k`-th firing
+    domain: Dbm,
+}
+
+impl Schedule {
+    /// Compute the firing dates of a sequence fired from the initial marking
+    ///
+    /// Returns [`None`] if the sequence can not be fired.
+    ///
+    /// # Errors
+    /// Return [`NetError::UnsupportedArc`] if a transition of the net has a stopwatch arc, and
+    /// [`NetError::InvalidTimeRange`] if a firing date is too large for a [`Time`]
+    pub fn compute(net: &Net, sequence: &[TransitionId]) -> Result<Option<Self>, NetError> {
+        for transition in net.transitions.iter() {
+            if let Some(&(pl, w)) = transition.stopwatches.iter().next() {
+                return Err(NetError::UnsupportedArc(arc::Kind::StopWatch(
+                    pl,
+                    transition.id(),
+                    w,
+                )));
+            }
+            if let Some(&(pl, w)) = transition.stopwatch_inhibitors.iter().next() {
+                return Err(NetError::UnsupportedArc(arc::Kind::StopWatchInhibitor(
+                    pl,
+                    transition.id(),
+                    w,
+                )));
+            }
+        }
+
+        let mut domain = Dbm::new(sequence.len());
+        let mut marking = net.initial_marking();
+        // Index of the firing which enabled each enabled transition, 0 for the initial marking
+        let mut enabling: Marking<TransitionId> = Marking::default();
+        for tr in net.enabled_transitions(&marking) {
+            enabling.insert_or_add(tr, 0);
+        }
+        for (k, &fired) in sequence.iter().enumerate().map(|(k, tr)| (k + 1, tr)) {
+            let transition = &net[fired];
+            if !transition.is_enabled(&marking) {
+                return Ok(None);
+            }
+            if transition.time.start == Bound::Infinity {
+                return Ok(None);
+            }
+            let e = enabling[fired];
+            domain.constrain(e, k, signed(transition.time.start, true));
+            domain.constrain(k, e, signed(transition.time.end, false));
+            domain.constrain(k - 1, k, Bound::Closed(Difference::from_integer(0)));
+            for &(tr, enabled_at) in enabling.iter() {
+                if tr == fired {
+                    continue;
+                }
+                domain.constrain(k, enabled_at, signed(net[tr].time.end, false));
+                if net[tr].priorities.contains(&fired) {
+                    match signed(net[tr].time.start, false) {
+                        Bound::Closed(a) => domain.constrain(k, enabled_at, Bound::Open(a)),
+                        Bound::Open(a) => domain.constrain(k, enabled_at, Bound::Closed(a)),
+                        Bound::Infinity => {}
+                    }
+                }
+            }
+
+            let intermediate = transition.intermediate(&marking);
+            marking = transition.fire(&marking);
+            let mut next = Marking::default();
+            for tr in net.enabled_transitions(&marking) {
+                if tr != fired && net[tr].is_enabled(&intermediate) {
+                    next.insert_or_add(tr, enabling[tr]);
+                } else {
+                    next.insert_or_add(tr, k);
+                }
+            }
+            enabling = next;
+        }
+
+        domain.close();
+        if domain.is_empty() {
+            return Ok(None);
+        }
+        let firings = sequence
+            .iter()
+            .enumerate()
+            .map(|(k, &transition)| {
+                Ok(ScheduledFiring {
+                    transition,
+                    dates: TimeRange {
+                        start: unsigned(domain.lower(k + 1))?,
+                        end: unsigned(domain.upper(k + 1))?,
+                    },
+                })
+            })
+            .collect::<Result<_, NetError>>()?;
+        Ok(Some(Self { firings, domain }))
+    }
+
+    /// Dates at which the last transition of the sequence can be fired
+    ///
+    /// The start of this range is the shortest duration of the sequence. The range of an empty
+    /// sequence is `[0,0]`.
+    #[must_use]
+    pub fn completion(&self) -> TimeRange {
+        self.firings.last().map_or(
+            TimeRange {
+                start: Bound::Closed(Time::from_integer(0)),
+                end: Bound::Closed(Time::from_integer(0)),
+            },
+            |firing| firing.dates,
+        )
+    }
+
+    /// Constraints between the firing dates, the variable `k` is the date of the `k`-th firing
+    ///
+    /// The bounds of [`ScheduledFiring::dates`] can not always be reached together, the delay
+    /// between two firings is bounded by this matrix.
+    #[must_use]
+    pub fn domain(&self) -> &Dbm {
+        &self.domain
+    }
+}
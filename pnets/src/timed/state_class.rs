@@ -0,0 +1,276 @@
+//! State class graph of time Petri nets
+//!
+//! The state class graph of Berthomieu and Menasche is a finite abstraction of the behaviour of
+//! a time Petri net which preserves its markings and firing sequences. A class is a marking and
+//! a firing domain: the set of possible firing times of the enabled transitions, represented by
+//! a difference bound matrix.
+//!
+//! The graph is finite if and only if the net is bounded, so the construction can be stopped
+//! after a maximum number of classes.
+//!
+//! A transition with priority over another must fire strictly before it, which is an
+//! approximation of the semantics of priorities when both transitions can fire at the same
+//! instant.
+use std::collections::HashMap;
+use std::error::Error;
+use std::io::Write;
+
+use crate::lts::{self, Lts};
+use crate::timed::dbm::{unsigned, Dbm, Difference};
+use crate::timed::{Bound, Net};
+use crate::{Marking, NetError, NodeId, PlaceId, TransitionId};
+
+/// State class: a marking and the firing domain of enabled transitions
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct StateClass {
+    /// Marking of the class
+    pub marking: Marking<PlaceId>,
+    /// Enabled transitions, in increasing order, the i-th transition is the variable `i + 1` of
+    /// the domain
+    pub enabled: Vec<TransitionId>,
+    /// Firing domain of enabled transitions
+    domain: Dbm,
+}
+
+impl StateClass {
+    /// Earliest and latest firing times of an enabled transition relative to the entrance in
+    /// the class
+    ///
+    /// Returns [`None`] if the transition is not enabled.
+    #[must_use]
+    pub fn firing_interval(&self, transition: TransitionId) -> Option<(Bound, Bound)> {
+        let i = self.enabled.binary_search(&transition).ok()? + 1;
+        // The bounds of a class are bounded by the time ranges of the net, so they are times
+        Some((
+            unsigned(self.domain.lower(i)).unwrap(),
+            unsigned(self.domain.upper(i)).unwrap(),
+        ))
+    }
+
+    /// Firing domain of the class, the variable `i + 1` is the firing time of the `i`-th enabled
+    /// transition
+    #[must_use]
+    pub fn domain(&self) -> &Dbm {
+        &self.domain
+    }
+}
+
+/// Firing of a transition between two classes
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Edge {
+    /// Source class
+    pub from: usize,
+    /// Fired transition
+    pub transition: TransitionId,
+    /// Target class
+    pub to: usize,
+}
+
+/// State class graph of a time Petri net
+#[derive(Debug, Clone, Default)]
+pub struct StateClassGraph {
+    /// Classes of the graph, the first one is the initial class
+    pub classes: Vec<StateClass>,
+    /// Edges of the graph
+    pub edges: Vec<Edge>,
+    /// All classes were explored before reaching the class limit
+    pub complete: bool,
+    /// Number of classes whose successors were computed
+    explored: usize,
+}
+
+impl StateClassGraph {
+    /// Build the state class graph of a net
+    ///
+    /// The exploration stops after `class_limit` classes, in this case the graph is not
+    /// [`complete`][`StateClassGraph::complete`].
+    ///
+    /// # Errors
+    /// Return [`NetError::InvalidTimeRange`] if a transition has an infinite earliest firing time
+    pub fn build(net: &Net, class_limit: Option<usize>) -> Result<Self, NetError> {
+        if net
+            .transitions
+            .iter()
+            .any(|tr| tr.time.start == Bound::Infinity)
+        {
+            return Err(NetError::InvalidTimeRange);
+        }
+
+        let marking = net.initial_marking();
+        let enabled: Vec<_> = net.enabled_transitions(&marking).collect();
+        let mut domain = Dbm::new(enabled.len());
+        for (i, &tr) in enabled.iter().enumerate() {
+            domain.constrain_range(i + 1, net[tr].time);
+        }
+        domain.close();
+        let initial = StateClass {
+            marking,
+            enabled,
+            domain,
+        };
+
+        let mut graph = Self::default();
+        let mut indices: HashMap<StateClass, usize> = HashMap::new();
+        indices.insert(initial.clone(), 0);
+        graph.classes.push(initial);
+        let mut current = 0;
+        while current < graph.classes.len() {
+            for tr in graph.classes[current].enabled.clone() {
+                let class = match Self::successor(net, &graph.classes[current], tr) {
+                    Some(class) => class,
+                    None => continue,
+                };
+                let to = match indices.get(&class) {
+                    Some(&to) => to,
+                    None => {
+                        if matches!(class_limit, Some(limit) if graph.classes.len() >= limit) {
+                            graph.explored = current;
+                            return Ok(graph);
+                        }
+                        indices.insert(class.clone(), graph.classes.len());
+                        graph.classes.push(class);
+                        graph.classes.len() - 1
+                    }
+                };
+                graph.edges.push(Edge {
+                    from: current,
+                    transition: tr,
+                    to,
+                });
+            }
+            current += 1;
+        }
+        graph.complete = true;
+        graph.explored = current;
+        Ok(graph)
+    }
+
+    /// Compute the class reached by firing a transition, [`None`] if it is not firable
+    fn successor(net: &Net, class: &StateClass, fired: TransitionId) -> Option<StateClass> {
+        let f = class.enabled.binary_search(&fired).ok()? + 1;
+
+        // The fired transition has the smallest firing time
+        let mut domain = class.domain.clone();
+        for (j, &tr) in class.enabled.iter().enumerate() {
+            if tr == fired {
+                continue;
+            }
+            if net[tr].priorities.contains(&fired) {
+                domain.constrain(f, j + 1, Bound::Open(Difference::from_integer(0)));
+            } else {
+                domain.constrain(f, j + 1, Bound::Closed(Difference::from_integer(0)));
+            }
+        }
+        domain.close();
+        if domain.is_empty() {
+            return None;
+        }
+
+        let transition = &net[fired];
+        let intermediate = transition.intermediate(&class.marking);
+        let marking = transition.fire(&class.marking);
+        let next_enabled: Vec<_> = net.enabled_transitions(&marking).collect();
+
+        // Variable of each transition in the previous domain if it stays enabled
+        let persistent: Vec<Option<usize>> = next_enabled
+            .iter()
+            .map(|&tr| {
+                if tr != fired && net[tr].is_enabled(&intermediate) {
+                    class.enabled.binary_search(&tr).ok().map(|i| i + 1)
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        // Firing times are now relative to the firing time of the fired transition
+        let mut next = Dbm::new(next_enabled.len());
+        for (i, old_i) in persistent.iter().enumerate() {
+            match old_i {
+                Some(old_i) => {
+                    next.constrain(i + 1, 0, domain.get(*old_i, f));
+                    next.constrain(0, i + 1, domain.get(f, *old_i));
+                    for (j, old_j) in persistent.iter().enumerate() {
+                        if let Some(old_j) = old_j {
+                            next.constrain(i + 1, j + 1, domain.get(*old_i, *old_j));
+                        }
+                    }
+                }
+                None => next.constrain_range(i + 1, net[next_enabled[i]].time),
+            }
+        }
+        next.close();
+        Some(StateClass {
+            marking,
+            enabled: next_enabled,
+            domain: next,
+        })
+    }
+
+    /// Number of classes of the graph
+    #[must_use]
+    pub fn class_count(&self) -> usize {
+        self.classes.len()
+    }
+
+    /// Number of edges of the graph
+    #[must_use]
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Classes without successor
+    ///
+    /// If the graph is not complete, classes which were not explored are not considered.
+    #[must_use]
+    pub fn deadlocks(&self) -> Vec<usize> {
+        deadlocks(&self.edges, self.explored)
+    }
+
+    /// Returns [`true`] if a class of the graph has no successor
+    #[must_use]
+    pub fn has_deadlock(&self) -> bool {
+        !self.deadlocks().is_empty()
+    }
+
+    /// Labelled transition system of the graph, edges are labelled with the names of
+    /// transitions
+    #[must_use]
+    pub fn lts(&self, net: &Net) -> Lts {
+        lts(net, &self.edges, self.classes.len())
+    }
+
+    /// Write the graph in the [Aldebaran](https://cadp.inria.fr/man/aut.html) format
+    ///
+    /// Edges are labelled with the names of transitions.
+    pub fn write_aut(&self, net: &Net, writer: &mut dyn Write) -> Result<(), Box<dyn Error>> {
+        self.lts(net).write_aut(writer)
+    }
+}
+
+/// Nodes among the `explored` first ones which are not the source of an edge
+pub(crate) fn deadlocks(edges: &[Edge], explored: usize) -> Vec<usize> {
+    let mut has_successor = vec![false; explored];
+    for edge in edges.iter().filter(|edge| edge.from < explored) {
+        has_successor[edge.from] = true;
+    }
+    (0..explored).filter(|&c| !has_successor[c]).collect()
+}
+
+/// Labelled transition system of a graph, edges are labelled with the names of transitions
+pub(crate) fn lts(net: &Net, edges: &[Edge], nodes: usize) -> Lts {
+    Lts {
+        initial: 0,
+        states: nodes,
+        edges: edges
+            .iter()
+            .map(|edge| lts::Edge {
+                from: edge.from,
+                label: net
+                    .get_name_by_index(&NodeId::Transition(edge.transition))
+                    .unwrap_or_default(),
+                to: edge.to,
+            })
+            .collect(),
+    }
+}
@@ -1,19 +1,33 @@
 //! Time range for timed Petri nets
+use std::cmp::Ordering;
 use std::fmt;
 use std::fmt::Formatter;
+use std::iter::Peekable;
+use std::str::{Chars, FromStr};
+
+use num_rational::Ratio;
+use num_traits::{CheckedAdd, CheckedMul};
+
+use crate::NetError;
+
+/// Exact non-negative rational time
+pub type Time = Ratio<u64>;
 
 /// Type of bound for time range
-#[derive(PartialEq, Debug, Clone, Eq, Copy)]
-pub enum Bound {
+///
+/// Bounds of time ranges are non-negative rationals, signed bounds are used by
+/// [`Dbm`][`crate::timed::dbm::Dbm`].
+#[derive(PartialEq, Debug, Clone, Eq, Copy, Hash)]
+pub enum Bound<T = Time> {
     /// Closed interval bound
-    Closed(usize),
+    Closed(T),
     /// Open interval bound
-    Open(usize),
+    Open(T),
     /// Infinity bound
     Infinity,
 }
 
-impl fmt::Display for Bound {
+impl<T: fmt::Display> fmt::Display for Bound<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
             Bound::Closed(v) => write!(f, "Close({})", v),
@@ -23,7 +37,46 @@ impl fmt::Display for Bound {
     }
 }
 
+/// Compare two lower bounds, a greater bound excludes more values
+fn compare_lower(left: Bound, right: Bound) -> Ordering {
+    match (left, right) {
+        (Bound::Infinity, Bound::Infinity) => Ordering::Equal,
+        (Bound::Infinity, _) => Ordering::Greater,
+        (_, Bound::Infinity) => Ordering::Less,
+        (Bound::Closed(a), Bound::Closed(b)) | (Bound::Open(a), Bound::Open(b)) => a.cmp(&b),
+        (Bound::Open(a), Bound::Closed(b)) => a.cmp(&b).then(Ordering::Greater),
+        (Bound::Closed(a), Bound::Open(b)) => a.cmp(&b).then(Ordering::Less),
+    }
+}
+
+/// Compare two upper bounds, a smaller bound excludes more values
+fn compare_upper(left: Bound, right: Bound) -> Ordering {
+    match (left, right) {
+        (Bound::Infinity, Bound::Infinity) => Ordering::Equal,
+        (Bound::Infinity, _) => Ordering::Greater,
+        (_, Bound::Infinity) => Ordering::Less,
+        (Bound::Closed(a), Bound::Closed(b)) | (Bound::Open(a), Bound::Open(b)) => a.cmp(&b),
+        (Bound::Open(a), Bound::Closed(b)) => a.cmp(&b).then(Ordering::Less),
+        (Bound::Closed(a), Bound::Open(b)) => a.cmp(&b).then(Ordering::Greater),
+    }
+}
+
+/// Sum of two bounds, open if one of them is open
+fn add(left: Bound, right: Bound) -> Result<Bound, NetError> {
+    let sum = |a: Time, b: Time| a.checked_add(&b).ok_or(NetError::InvalidTimeRange);
+    Ok(match (left, right) {
+        (Bound::Infinity, _) | (_, Bound::Infinity) => Bound::Infinity,
+        (Bound::Closed(a), Bound::Closed(b)) => Bound::Closed(sum(a, b)?),
+        (Bound::Open(a), Bound::Closed(b))
+        | (Bound::Closed(a), Bound::Open(b))
+        | (Bound::Open(a), Bound::Open(b)) => Bound::Open(sum(a, b)?),
+    })
+}
+
 /// Represent a timerange in the Petri net
+///
+/// Time ranges are printed and parsed with the syntax of Tina: `[a,b]`, `]a,b[`, `[a,w[`, where
+/// bounds are integers, decimals (`0.5`) or fractions (`3/2`) with an optional `K` or `M` unit.
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
 pub struct TimeRange {
     /// Start of the time range
@@ -34,54 +87,261 @@ pub struct TimeRange {
 
 impl TimeRange {
     /// Return a new timerange corresponding to the intersection of the timeranges
+    ///
+    /// The result can be empty, see [`TimeRange::is_empty`].
     #[must_use]
     pub fn intersect(&self, other: Self) -> Self {
-        let new_start = match (self.start, other.start) {
-            (Bound::Open(self_value), Bound::Open(other_value))
-            | (Bound::Closed(self_value), Bound::Open(other_value))
-            | (Bound::Open(self_value), Bound::Closed(other_value))
-            | (Bound::Closed(self_value), Bound::Closed(other_value))
-                if self_value != other_value =>
-            {
-                if self_value < other_value {
-                    other.start
-                } else {
-                    self.start
+        TimeRange {
+            start: std::cmp::max_by(self.start, other.start, |&a, &b| compare_lower(a, b)),
+            end: std::cmp::min_by(self.end, other.end, |&a, &b| compare_upper(a, b)),
+        }
+    }
+
+    /// Returns [`true`] if no time is in the time range
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        match (self.start, self.end) {
+            (Bound::Infinity, _) => true,
+            (_, Bound::Infinity) => false,
+            (Bound::Closed(start), Bound::Closed(end)) => start > end,
+            (Bound::Closed(start), Bound::Open(end))
+            | (Bound::Open(start), Bound::Closed(end))
+            | (Bound::Open(start), Bound::Open(end)) => start >= end,
+        }
+    }
+
+    /// Returns [`true`] if a time is in the time range
+    #[must_use]
+    pub fn contains(&self, time: Time) -> bool {
+        let after_start = match self.start {
+            Bound::Closed(start) => start <= time,
+            Bound::Open(start) => start < time,
+            Bound::Infinity => false,
+        };
+        let before_end = match self.end {
+            Bound::Closed(end) => time <= end,
+            Bound::Open(end) => time < end,
+            Bound::Infinity => true,
+        };
+        after_start && before_end
+    }
+
+    /// Returns [`true`] if all times of `other` are in this time range
+    #[must_use]
+    pub fn includes(&self, other: Self) -> bool {
+        other.is_empty()
+            || (compare_lower(self.start, other.start) != Ordering::Greater
+                && compare_upper(other.end, self.end) != Ordering::Greater)
+    }
+
+    /// Delay all times of the time range
+    ///
+    /// # Errors
+    /// Return [`NetError::InvalidTimeRange`] if a bound overflows
+    pub fn shift(&self, delay: Time) -> Result<Self, NetError> {
+        Ok(TimeRange {
+            start: add(self.start, Bound::Closed(delay))?,
+            end: add(self.end, Bound::Closed(delay))?,
+        })
+    }
+
+    /// Minkowski sum: the time range of the sums of a time of each time range
+    ///
+    /// # Errors
+    /// Return [`NetError::InvalidTimeRange`] if a bound overflows
+    pub fn sum(&self, other: Self) -> Result<Self, NetError> {
+        if self.is_empty() {
+            return Ok(*self);
+        }
+        if other.is_empty() {
+            return Ok(other);
+        }
+        Ok(TimeRange {
+            start: add(self.start, other.start)?,
+            end: add(self.end, other.end)?,
+        })
+    }
+
+    /// Smallest time range which includes both time ranges
+    #[must_use]
+    pub fn hull(&self, other: Self) -> Self {
+        if self.is_empty() {
+            return other;
+        }
+        if other.is_empty() {
+            return *self;
+        }
+        TimeRange {
+            start: std::cmp::min_by(self.start, other.start, |&a, &b| compare_lower(a, b)),
+            end: std::cmp::max_by(self.end, other.end, |&a, &b| compare_upper(a, b)),
+        }
+    }
+}
+
+/// Time ranges are ordered by their earliest time, then by their latest time
+///
+/// A closed start is before an open start at the same time, an open end is before a closed end.
+impl Ord for TimeRange {
+    fn cmp(&self, other: &Self) -> Ordering {
+        compare_lower(self.start, other.start).then(compare_upper(self.end, other.end))
+    }
+}
+
+impl PartialOrd for TimeRange {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for TimeRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.start {
+            Bound::Closed(v) => write!(f, "[{},", v)?,
+            Bound::Open(v) => write!(f, "]{},", v)?,
+            Bound::Infinity => write!(f, "]w,")?,
+        }
+        match self.end {
+            Bound::Closed(v) => write!(f, "{}]", v),
+            Bound::Open(v) => write!(f, "{}[", v),
+            Bound::Infinity => write!(f, "w["),
+        }
+    }
+}
+
+/// Parser of the bounds of a time range
+struct TimeParser<'s> {
+    chars: Peekable<Chars<'s>>,
+}
+
+impl TimeParser<'_> {
+    fn skip_spaces(&mut self) {
+        while matches!(self.chars.peek(), Some(' ' | '\t')) {
+            self.chars.next();
+        }
+    }
+
+    fn expect(&mut self, expected: &[char]) -> Result<char, NetError> {
+        self.skip_spaces();
+        match self.chars.next() {
+            Some(c) if expected.contains(&c) => Ok(c),
+            _ => Err(NetError::InvalidTimeRange),
+        }
+    }
+
+    /// Parse digits, fails if there is no digit
+    fn integer(&mut self) -> Result<(u64, u64), NetError> {
+        let mut value = 0_u64;
+        let mut scale = 1_u64;
+        while let Some(digit) = self.chars.peek().and_then(|c| c.to_digit(10)) {
+            self.chars.next();
+            value = value
+                .checked_mul(10)
+                .and_then(|v| v.checked_add(u64::from(digit)))
+                .ok_or(NetError::InvalidTimeRange)?;
+            scale = scale.saturating_mul(10);
+        }
+        if scale == 1 {
+            return Err(NetError::InvalidTimeRange);
+        }
+        Ok((value, scale))
+    }
+
+    /// Parse a finite time
+    fn time(&mut self) -> Result<Time, NetError> {
+        self.skip_spaces();
+        let parenthesized = self.chars.peek() == Some(&'(');
+        if parenthesized {
+            self.chars.next();
+        }
+        let (mut numerator, _) = self.integer()?;
+        let mut denominator = 1;
+        match self.chars.peek() {
+            Some('.') => {
+                self.chars.next();
+                let (decimals, scale) = self.integer()?;
+                numerator = numerator
+                    .checked_mul(scale)
+                    .and_then(|n| n.checked_add(decimals))
+                    .ok_or(NetError::InvalidTimeRange)?;
+                denominator = scale;
+            }
+            Some('/') => {
+                self.chars.next();
+                denominator = self.integer()?.0;
+                if denominator == 0 {
+                    return Err(NetError::InvalidTimeRange);
                 }
             }
-            (Bound::Closed(value), Bound::Closed(_)) => Bound::Closed(value),
-            (Bound::Open(value), _) | (_, Bound::Open(value)) => Bound::Open(value),
-            (Bound::Infinity, o) | (o, Bound::Infinity) => o,
+            _ => {}
+        }
+        let unit = match self.chars.peek() {
+            Some('K') => 1_000,
+            Some('M') => 1_000_000,
+            _ => 1,
         };
+        if unit != 1 {
+            self.chars.next();
+        }
+        if parenthesized {
+            self.expect(&[')'])?;
+        }
+        let time = Time::new(numerator, denominator);
+        time.checked_mul(&Time::from_integer(unit))
+            .ok_or(NetError::InvalidTimeRange)
+    }
+}
+
+impl FromStr for TimeRange {
+    type Err = NetError;
 
-        let new_end = match (self.end, other.end) {
-            (Bound::Open(self_value), Bound::Open(other_value))
-            | (Bound::Closed(self_value), Bound::Open(other_value))
-            | (Bound::Open(self_value), Bound::Closed(other_value))
-            | (Bound::Closed(self_value), Bound::Closed(other_value))
-                if self_value != other_value =>
-            {
-                if self_value > other_value {
-                    other.end
+    /// Parse a time range in the syntax of Tina
+    ///
+    /// A start at infinity, written `]w,` by [`Display`][`fmt::Display`], is also accepted so
+    /// that every printed range parses back. Such ranges are empty and are not valid for Tina.
+    ///
+    /// # Errors
+    /// Return [`NetError::InvalidTimeRange`] if the syntax is invalid or a bound is too large
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = TimeParser {
+            chars: s.chars().peekable(),
+        };
+        let start = match parser.expect(&['[', ']'])? {
+            '[' => Bound::Closed(parser.time()?),
+            _ => {
+                parser.skip_spaces();
+                if parser.chars.peek() == Some(&'w') {
+                    parser.chars.next();
+                    Bound::Infinity
                 } else {
-                    self.end
+                    Bound::Open(parser.time()?)
                 }
             }
-            (Bound::Closed(value), Bound::Closed(_)) => Bound::Closed(value),
-            (Bound::Open(value), _) | (_, Bound::Open(value)) => Bound::Open(value),
-            (Bound::Infinity, o) | (o, Bound::Infinity) => o,
         };
-        TimeRange {
-            start: new_start,
-            end: new_end,
+        parser.expect(&[','])?;
+        parser.skip_spaces();
+        let end = if parser.chars.peek() == Some(&'w') {
+            parser.chars.next();
+            parser.expect(&['['])?;
+            Bound::Infinity
+        } else {
+            let end = parser.time()?;
+            match parser.expect(&['[', ']'])? {
+                ']' => Bound::Closed(end),
+                _ => Bound::Open(end),
+            }
+        };
+        parser.skip_spaces();
+        if parser.chars.next().is_some() {
+            return Err(NetError::InvalidTimeRange);
         }
+        Ok(TimeRange { start, end })
     }
 }
 
 impl Default for TimeRange {
     fn default() -> Self {
         Self {
-            start: Bound::Closed(0),
+            start: Bound::Closed(Time::from_integer(0)),
             end: Bound::Infinity,
         }
     }
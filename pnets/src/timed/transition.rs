@@ -14,11 +14,19 @@ pub struct Transition {
     pub conditions: Marking<PlaceId>,
     /// Inhibitors of the transition
     pub inhibitors: Marking<PlaceId>,
+    /// Stopwatches of the transition, its clock is suspended when one of these places has less
+    /// tokens than the weight of the arc
+    pub stopwatches: Marking<PlaceId>,
+    /// Stopwatch inhibitors of the transition, its clock is suspended when one of these places
+    /// has at least as many tokens as the weight of the arc
+    pub stopwatch_inhibitors: Marking<PlaceId>,
 
     /// Consumption of the transition
     pub consume: Marking<PlaceId>,
     /// Production of the transition
     pub produce: Marking<PlaceId>,
+    /// Places emptied by the transition, after its consumption and before its production
+    pub resets: Marking<PlaceId>,
 
     /// Priorities of the transitions (this transition must be activated before all transition in this vector)
     pub priorities: Vec<TransitionId>,
@@ -38,5 +46,59 @@ impl Transition {
             && self.consume.is_empty()
             && self.conditions.is_empty()
             && self.inhibitors.is_empty()
+            && self.stopwatches.is_empty()
+            && self.stopwatch_inhibitors.is_empty()
+            && self.resets.is_empty()
+    }
+
+    /// Returns [`true`] if this transition is enabled by the marking
+    ///
+    /// Consumed places and conditions must have enough tokens and places of inhibitor arcs must
+    /// have less tokens than the weight of the arc.
+    #[must_use]
+    pub fn is_enabled(&self, marking: &Marking<PlaceId>) -> bool {
+        self.consume.iter().all(|&(pl, w)| marking[pl] >= w)
+            && self.conditions.iter().all(|&(pl, w)| marking[pl] >= w)
+            && self.inhibitors.iter().all(|&(pl, w)| marking[pl] < w)
+    }
+
+    /// Returns [`true`] if the clock of this transition progresses in the marking
+    ///
+    /// A transition whose clock is suspended can not be fired.
+    #[must_use]
+    pub fn is_active(&self, marking: &Marking<PlaceId>) -> bool {
+        self.stopwatches.iter().all(|&(pl, w)| marking[pl] >= w)
+            && self
+                .stopwatch_inhibitors
+                .iter()
+                .all(|&(pl, w)| marking[pl] < w)
+    }
+
+    /// Compute the marking after the consumption and the resets of this transition, before
+    /// its production
+    ///
+    /// The transition must be enabled in the marking, see [`Transition::is_enabled`].
+    #[must_use]
+    pub fn intermediate(&self, marking: &Marking<PlaceId>) -> Marking<PlaceId> {
+        let mut next = marking.clone();
+        for &(pl, w) in self.consume.iter() {
+            next.sub_or_delete(pl, w);
+        }
+        for &(pl, _) in self.resets.iter() {
+            next.delete(pl);
+        }
+        next
+    }
+
+    /// Compute the marking reached by firing this transition
+    ///
+    /// The transition must be enabled in the marking, see [`Transition::is_enabled`].
+    #[must_use]
+    pub fn fire(&self, marking: &Marking<PlaceId>) -> Marking<PlaceId> {
+        let mut next = self.intermediate(marking);
+        for &(pl, w) in self.produce.iter().filter(|&&(_, w)| w > 0) {
+            next.insert_or_add(pl, w);
+        }
+        next
     }
 }
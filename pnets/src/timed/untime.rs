@@ -0,0 +1,99 @@
+//! Untimed over-approximation of time Petri nets
+//!
+//! [`untime`] drops the time ranges of a [`Net`]: every transition of the result has the range
+//! `[0,w[` and can be fired at any time once it is enabled. Test, inhibitor and reset arcs are
+//! kept, so the result is still a [`Net`], and stopwatch arcs are removed as they only suspend
+//! clocks.
+//!
+//! A priority `t1 > t2` is kept only when it holds in every state where `t1` is enabled: `t1`
+//! has no stopwatch arc and its time range is not empty and starts at a closed `0`. Then `t1` is
+//! firable as soon as it is enabled, in the timed net as well as in the untimed one. The other
+//! priorities would forbid firings allowed by the timed net and are dropped.
+//!
+//! Every firing sequence of the timed net is a firing sequence of the untimed net, so the
+//! untimed net is sound for safety properties: a marking which is not reachable in the untimed
+//! net is not reachable in the timed net. The converse does not hold, a reachable marking of the
+//! untimed net can be unreachable with time.
+use crate::arc;
+use crate::net::NodeId;
+use crate::timed::{Bound, Net, Time, TimeRange, Transition};
+use crate::NetError;
+
+/// Returns [`true`] if a transition is firable whenever it is enabled
+fn immediately_firable(transition: &Transition) -> bool {
+    transition.time.start == Bound::Closed(Time::from_integer(0))
+        && !transition.time.is_empty()
+        && transition.stopwatches.is_empty()
+        && transition.stopwatch_inhibitors.is_empty()
+}
+
+/// Untimed over-approximation of a net
+///
+/// Places and transitions keep their index, name, label and initial marking, and the net keeps
+/// its metadata.
+///
+/// # Errors
+/// Return [`NetError::CyclicPriorities`] if the priorities of the net are cyclic
+pub fn untime(net: &Net) -> Result<Net, NetError> {
+    let closure = net.priority_graph().transitive_closure()?;
+    let mut untimed = Net::default();
+    untimed.name = net.name.clone();
+    untimed.metadata = net.metadata.clone();
+    for (pl, place) in net.places.iter_enumerated() {
+        let new_pl = untimed.create_place();
+        untimed[new_pl].initial = place.initial;
+        untimed[new_pl].label = place.label.clone();
+        untimed.rename_node(
+            NodeId::Place(new_pl),
+            &net.get_name_by_index(&NodeId::Place(pl)).unwrap(),
+        )?;
+    }
+    for (tr, transition) in net.transitions.iter_enumerated() {
+        let new_tr = untimed.create_transition();
+        untimed[new_tr].label = transition.label.clone();
+        untimed[new_tr].time = TimeRange::default();
+        untimed.rename_node(
+            NodeId::Transition(new_tr),
+            &net.get_name_by_index(&NodeId::Transition(tr)).unwrap(),
+        )?;
+        let mut arcs = vec![];
+        arcs.extend(
+            transition
+                .consume
+                .iter()
+                .map(|&(pl, w)| arc::Kind::Consume(pl, new_tr, w)),
+        );
+        arcs.extend(
+            transition
+                .produce
+                .iter()
+                .map(|&(pl, w)| arc::Kind::Produce(pl, new_tr, w)),
+        );
+        arcs.extend(
+            transition
+                .conditions
+                .iter()
+                .map(|&(pl, w)| arc::Kind::Test(pl, new_tr, w)),
+        );
+        arcs.extend(
+            transition
+                .inhibitors
+                .iter()
+                .map(|&(pl, w)| arc::Kind::Inhibitor(pl, new_tr, w)),
+        );
+        arcs.extend(
+            transition
+                .resets
+                .iter()
+                .map(|&(pl, w)| arc::Kind::Reset(pl, new_tr, w)),
+        );
+        for arc in arcs {
+            // All these kinds of arcs are supported by timed nets
+            untimed.add_arc(arc).unwrap();
+        }
+        if immediately_firable(transition) {
+            untimed[new_tr].priorities = closure.priorities(tr).to_vec();
+        }
+    }
+    Ok(untimed)
+}
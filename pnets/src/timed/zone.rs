@@ -0,0 +1,330 @@
+//! Strong state classes and zone graph of stopwatch time Petri nets
+//!
+//! These abstractions represent the clock of each enabled transition (the time elapsed since
+//! its enabling) instead of its firing time, which allows clocks to be suspended by
+//! [stopwatch arcs][`crate::arc::Kind::StopWatch`]: a transition whose clock is suspended keeps
+//! its clock and can not be fired.
+//!
+//! Clock domains are represented by difference bound matrices. They are extrapolated with the
+//! bounds of the time ranges of transitions, so the graph of a bounded net is finite. With
+//! stopwatches the reachable clock values are not always representable by such matrices, so
+//! the graph is an over-approximation: every firing sequence of the net is a path of the graph,
+//! but some paths of the graph may not be firable.
+//!
+//! Two abstractions are available, see [`Mode`].
+use std::collections::HashMap;
+use std::error::Error;
+use std::io::Write;
+
+use crate::lts::Lts;
+use crate::timed::dbm::{difference, signed, unsigned, Dbm, Difference};
+use crate::timed::state_class::{deadlocks, lts, Edge};
+use crate::timed::{Bound, Net};
+use crate::{Marking, NetError, PlaceId, TransitionId};
+
+/// Abstraction used to build a [`ZoneGraph`]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Mode {
+    /// Strong state classes: the clock domain is the set of clock values when entering the
+    /// class, and classes are identified by equality
+    StrongClasses,
+    /// Zones: the clock domain also contains the values reached by letting time elapse, and a
+    /// zone included in an already explored zone with the same marking is not explored
+    Zones,
+}
+
+/// Node of a [`ZoneGraph`]: a marking and the domain of the clocks of enabled transitions
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Zone {
+    /// Marking of the zone
+    pub marking: Marking<PlaceId>,
+    /// Enabled transitions, in increasing order, the clock of the i-th transition is the
+    /// variable `i + 1` of the domain
+    pub enabled: Vec<TransitionId>,
+    /// Domain of the clocks
+    domain: Dbm,
+}
+
+impl Zone {
+    /// Bounds of the clock of an enabled transition
+    ///
+    /// Returns [`None`] if the transition is not enabled.
+    #[must_use]
+    pub fn clock_interval(&self, transition: TransitionId) -> Option<(Bound, Bound)> {
+        let i = self.enabled.binary_search(&transition).ok()? + 1;
+        // Extrapolated clocks are bounded by the time ranges of the net, so they are times
+        Some((
+            unsigned(self.domain.lower(i)).unwrap(),
+            unsigned(self.domain.upper(i)).unwrap(),
+        ))
+    }
+
+    /// Domain of the clocks, the variable `i + 1` is the clock of the `i`-th enabled transition
+    #[must_use]
+    pub fn domain(&self) -> &Dbm {
+        &self.domain
+    }
+}
+
+/// Graph of strong state classes or zones of a stopwatch time Petri net
+#[derive(Debug, Clone)]
+pub struct ZoneGraph {
+    /// Abstraction used to build the graph
+    pub mode: Mode,
+    /// Zones of the graph, the first one is the initial zone
+    pub zones: Vec<Zone>,
+    /// Edges of the graph
+    pub edges: Vec<Edge>,
+    /// All zones were explored before reaching the zone limit
+    pub complete: bool,
+    /// Number of zones whose successors were computed
+    explored: usize,
+}
+
+/// Signed value of a finite bound
+fn value(bound: Bound) -> Option<Difference> {
+    match bound {
+        Bound::Closed(v) | Bound::Open(v) => Some(difference(v, false)),
+        Bound::Infinity => None,
+    }
+}
+
+impl ZoneGraph {
+    /// Build the graph of a net with an abstraction
+    ///
+    /// The exploration stops after `zone_limit` zones, in this case the graph is not
+    /// [`complete`][`ZoneGraph::complete`].
+    ///
+    /// # Errors
+    /// Return [`NetError::InvalidTimeRange`] if a transition has an infinite earliest firing time
+    pub fn build(net: &Net, mode: Mode, zone_limit: Option<usize>) -> Result<Self, NetError> {
+        if net
+            .transitions
+            .iter()
+            .any(|tr| tr.time.start == Bound::Infinity)
+        {
+            return Err(NetError::InvalidTimeRange);
+        }
+
+        let marking = net.initial_marking();
+        let enabled: Vec<_> = net.enabled_transitions(&marking).collect();
+        let mut domain = Dbm::new(enabled.len());
+        for i in 1..=enabled.len() {
+            domain.constrain(i, 0, Bound::Closed(Difference::from_integer(0)));
+        }
+        domain.close();
+        let mut initial = Zone {
+            marking,
+            enabled,
+            domain,
+        };
+        if mode == Mode::Zones {
+            Self::elapse(net, &mut initial);
+        }
+        Self::extrapolate(net, &mut initial);
+
+        let mut graph = Self {
+            mode,
+            zones: vec![],
+            edges: vec![],
+            complete: false,
+            explored: 0,
+        };
+        let mut indices: HashMap<Marking<PlaceId>, Vec<usize>> = HashMap::new();
+        indices.insert(initial.marking.clone(), vec![0]);
+        graph.zones.push(initial);
+        let mut current = 0;
+        while current < graph.zones.len() {
+            for tr in graph.zones[current].enabled.clone() {
+                let zone = match graph.successor(net, &graph.zones[current], tr) {
+                    Some(zone) => zone,
+                    None => continue,
+                };
+                let candidates = indices.entry(zone.marking.clone()).or_default();
+                let known = candidates.iter().copied().find(|&z| match mode {
+                    Mode::StrongClasses => graph.zones[z] == zone,
+                    Mode::Zones => {
+                        graph.zones[z].enabled == zone.enabled
+                            && graph.zones[z].domain.includes(&zone.domain)
+                    }
+                });
+                let to = match known {
+                    Some(to) => to,
+                    None => {
+                        if matches!(zone_limit, Some(limit) if graph.zones.len() >= limit) {
+                            graph.explored = current;
+                            return Ok(graph);
+                        }
+                        candidates.push(graph.zones.len());
+                        graph.zones.push(zone);
+                        graph.zones.len() - 1
+                    }
+                };
+                graph.edges.push(Edge {
+                    from: current,
+                    transition: tr,
+                    to,
+                });
+            }
+            current += 1;
+        }
+        graph.complete = true;
+        graph.explored = current;
+        Ok(graph)
+    }
+
+    /// Let time elapse for active transitions until the latest firing time of one of them
+    fn elapse(net: &Net, zone: &mut Zone) {
+        let running: Vec<usize> = zone
+            .enabled
+            .iter()
+            .enumerate()
+            .filter(|&(_, &tr)| net[tr].is_active(&zone.marking))
+            .map(|(i, _)| i + 1)
+            .collect();
+        zone.domain.up_running(&running);
+        for &i in &running {
+            let end = net[zone.enabled[i - 1]].time.end;
+            zone.domain.constrain(i, 0, signed(end, false));
+        }
+        zone.domain.close();
+    }
+
+    /// Extrapolate the domain of a zone with the bounds of the time ranges of transitions
+    fn extrapolate(net: &Net, zone: &mut Zone) {
+        let constants: Vec<Difference> = zone
+            .enabled
+            .iter()
+            .map(|&tr| {
+                let time = net[tr].time;
+                value(time.start)
+                    .into_iter()
+                    .chain(value(time.end))
+                    .max()
+                    .unwrap_or_else(|| Difference::from_integer(0))
+            })
+            .collect();
+        zone.domain.extrapolate(&constants);
+    }
+
+    /// Compute the zone reached by firing a transition, [`None`] if it is not firable
+    fn successor(&self, net: &Net, zone: &Zone, fired: TransitionId) -> Option<Zone> {
+        let transition = &net[fired];
+        if !transition.is_active(&zone.marking) {
+            return None;
+        }
+        let f = zone.enabled.binary_search(&fired).ok()? + 1;
+
+        let mut source = zone.clone();
+        if self.mode == Mode::StrongClasses {
+            Self::elapse(net, &mut source);
+        }
+        let mut domain = source.domain;
+        match transition.time.start {
+            Bound::Infinity => return None,
+            start => domain.constrain(0, f, signed(start, true)),
+        }
+        // Active transitions with priority over the fired one must not be firable
+        for (k, &tr) in zone.enabled.iter().enumerate() {
+            if net[tr].priorities.contains(&fired) && net[tr].is_active(&zone.marking) {
+                match net[tr].time.start {
+                    Bound::Closed(a) => {
+                        domain.constrain(k + 1, 0, Bound::Open(difference(a, false)))
+                    }
+                    Bound::Open(a) => {
+                        domain.constrain(k + 1, 0, Bound::Closed(difference(a, false)))
+                    }
+                    Bound::Infinity => {}
+                }
+            }
+        }
+        domain.close();
+        if domain.is_empty() {
+            return None;
+        }
+
+        let intermediate = transition.intermediate(&zone.marking);
+        let marking = transition.fire(&zone.marking);
+        let next_enabled: Vec<_> = net.enabled_transitions(&marking).collect();
+
+        // Variable of each transition in the previous domain if it stays enabled, the clocks of
+        // other transitions start at zero
+        let persistent: Vec<Option<usize>> = next_enabled
+            .iter()
+            .map(|&tr| {
+                if tr != fired && net[tr].is_enabled(&intermediate) {
+                    zone.enabled.binary_search(&tr).ok().map(|i| i + 1)
+                } else {
+                    None
+                }
+            })
+            .collect();
+        let mut next = Dbm::new(next_enabled.len());
+        for (i, old_i) in persistent.iter().enumerate() {
+            match old_i {
+                Some(old_i) => {
+                    next.constrain(i + 1, 0, domain.get(*old_i, 0));
+                    next.constrain(0, i + 1, domain.get(0, *old_i));
+                    for (j, old_j) in persistent.iter().enumerate() {
+                        if let Some(old_j) = old_j {
+                            next.constrain(i + 1, j + 1, domain.get(*old_i, *old_j));
+                        }
+                    }
+                }
+                None => next.constrain(i + 1, 0, Bound::Closed(Difference::from_integer(0))),
+            }
+        }
+        next.close();
+
+        let mut next = Zone {
+            marking,
+            enabled: next_enabled,
+            domain: next,
+        };
+        if self.mode == Mode::Zones {
+            Self::elapse(net, &mut next);
+        }
+        Self::extrapolate(net, &mut next);
+        Some(next)
+    }
+
+    /// Number of zones of the graph
+    #[must_use]
+    pub fn zone_count(&self) -> usize {
+        self.zones.len()
+    }
+
+    /// Number of edges of the graph
+    #[must_use]
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Zones without successor
+    ///
+    /// If the graph is not complete, zones which were not explored are not considered.
+    #[must_use]
+    pub fn deadlocks(&self) -> Vec<usize> {
+        deadlocks(&self.edges, self.explored)
+    }
+
+    /// Returns [`true`] if a zone of the graph has no successor
+    #[must_use]
+    pub fn has_deadlock(&self) -> bool {
+        !self.deadlocks().is_empty()
+    }
+
+    /// Labelled transition system of the graph, edges are labelled with the names of
+    /// transitions
+    #[must_use]
+    pub fn lts(&self, net: &Net) -> Lts {
+        lts(net, &self.edges, self.zones.len())
+    }
+
+    /// Write the graph in the [Aldebaran](https://cadp.inria.fr/man/aut.html) format
+    ///
+    /// Edges are labelled with the names of transitions.
+    pub fn write_aut(&self, net: &Net, writer: &mut dyn Write) -> Result<(), Box<dyn Error>> {
+        self.lts(net).write_aut(writer)
+    }
+}
@@ -0,0 +1,168 @@
+use pnets::arc::Kind;
+use pnets::predicate::{Comparison, Constraint, Predicate};
+use pnets::standard::bmc::{read_model, Bmc};
+use pnets::standard::Net;
+use pnets::{NetError, PlaceId};
+
+/// Minimal DPLL solver, returns the true literals of a model
+fn solve(clauses: &[Vec<isize>], assignment: &mut Vec<isize>) -> bool {
+    loop {
+        let mut unit = None;
+        for clause in clauses {
+            if clause.iter().any(|l| assignment.contains(l)) {
+                continue;
+            }
+            let free: Vec<isize> = clause
+                .iter()
+                .copied()
+                .filter(|l| !assignment.contains(&-l))
+                .collect();
+            match free.len() {
+                0 => return false,
+                1 => {
+                    unit = Some(free[0]);
+                    break;
+                }
+                _ => {}
+            }
+        }
+        match unit {
+            Some(l) => assignment.push(l),
+            None => break,
+        }
+    }
+    let free = clauses
+        .iter()
+        .flatten()
+        .find(|l| !assignment.contains(l) && !assignment.contains(&-*l));
+    match free {
+        None => true,
+        Some(&l) => {
+            for &choice in &[l, -l] {
+                let mut attempt = assignment.clone();
+                attempt.push(choice);
+                if solve(clauses, &mut attempt) {
+                    *assignment = attempt;
+                    return true;
+                }
+            }
+            false
+        }
+    }
+}
+
+/// Parse the DIMACS output of the encoder and solve it
+fn run(net: &Net, bmc: &Bmc) -> Option<Vec<isize>> {
+    let mut out = vec![];
+    bmc.write_dimacs(net, &mut out).unwrap();
+    let text = String::from_utf8(out).unwrap();
+    let mut clauses = vec![];
+    let mut header = None;
+    for line in text.lines() {
+        if line.starts_with('c') {
+            continue;
+        } else if line.starts_with("p cnf") {
+            header = Some(line.to_string());
+        } else {
+            let clause: Vec<isize> = line
+                .split_whitespace()
+                .map(|v| v.parse().unwrap())
+                .collect();
+            assert_eq!(clause.last(), Some(&0));
+            clauses.push(clause[..clause.len() - 1].to_vec());
+        }
+    }
+    assert_eq!(
+        header,
+        Some(format!("p cnf {} {}", bmc.variables(), bmc.clauses()))
+    );
+    let mut model = vec![];
+    if solve(&clauses, &mut model) {
+        Some(model)
+    } else {
+        None
+    }
+}
+
+/// p0 -> t0 -> p1 -> t1 -> p2, and t2 moves the token from p0 to p3
+fn sequence() -> (Net, Vec<PlaceId>) {
+    let mut net = Net::default();
+    let places: Vec<PlaceId> = (0..4).map(|_| net.create_place()).collect();
+    let t0 = net.create_transition();
+    let t1 = net.create_transition();
+    let t2 = net.create_transition();
+    for (i, &pl) in places.iter().enumerate() {
+        net.rename_node(pl.into(), &format!("p{}", i)).unwrap();
+    }
+    net.rename_node(t0.into(), "t0").unwrap();
+    net.rename_node(t1.into(), "t1").unwrap();
+    net.rename_node(t2.into(), "t2").unwrap();
+    net[places[0]].initial = 1;
+    net.add_arc(Kind::Consume(places[0], t0, 1)).unwrap();
+    net.add_arc(Kind::Produce(places[1], t0, 1)).unwrap();
+    net.add_arc(Kind::Consume(places[1], t1, 1)).unwrap();
+    net.add_arc(Kind::Produce(places[2], t1, 1)).unwrap();
+    net.add_arc(Kind::Consume(places[0], t2, 1)).unwrap();
+    net.add_arc(Kind::Produce(places[3], t2, 1)).unwrap();
+    (net, places)
+}
+
+#[test]
+fn reachable_test() {
+    let (net, places) = sequence();
+    let target = Predicate::default().and(Constraint::at_least(places[2], 1));
+
+    let bmc = Bmc::new(&net, &target, 1).unwrap();
+    assert_eq!(run(&net, &bmc), None);
+
+    let bmc = Bmc::new(&net, &target, 3).unwrap();
+    let model = run(&net, &bmc).unwrap();
+    assert_eq!(bmc.decode_names(&net, &model), vec!["t0", "t1"]);
+}
+
+#[test]
+fn unreachable_test() {
+    let (net, places) = sequence();
+    // p2 and p3 can not be marked together
+    let target = Predicate::new(vec![Constraint::new(
+        vec![(places[2], 1), (places[3], 1)],
+        Comparison::GreaterEqual,
+        2,
+    )]);
+    let bmc = Bmc::new(&net, &target, 4).unwrap();
+    assert_eq!(run(&net, &bmc), None);
+
+    // Negative coefficients: p3 - p0 = 1 only after firing t2
+    let target = Predicate::new(vec![Constraint::new(
+        vec![(places[3], 1), (places[0], -1)],
+        Comparison::Equal,
+        1,
+    )]);
+    let bmc = Bmc::new(&net, &target, 2).unwrap();
+    let model = run(&net, &bmc).unwrap();
+    assert_eq!(bmc.decode_names(&net, &model), vec!["t2"]);
+}
+
+#[test]
+fn unsafe_test() {
+    let (mut net, places) = sequence();
+    net[places[1]].initial = 2;
+    assert_eq!(
+        Bmc::new(&net, &Predicate::default(), 1).err(),
+        Some(NetError::UnsafePlace(places[1]))
+    );
+}
+
+#[test]
+fn read_model_test() {
+    assert_eq!(
+        read_model("c comment\ns SATISFIABLE\nv 1 -2\nv 3 0\n".as_bytes()).unwrap(),
+        Some(vec![1, -2, 3])
+    );
+    assert_eq!(
+        read_model("SAT\n-1 2 0\n".as_bytes()).unwrap(),
+        Some(vec![-1, 2])
+    );
+    assert_eq!(read_model("s UNSATISFIABLE\n".as_bytes()).unwrap(), None);
+    assert_eq!(read_model("UNSAT\n".as_bytes()).unwrap(), None);
+}
@@ -0,0 +1,26 @@
+use pnets::arc::Kind;
+use pnets::timed::Net;
+use pnets::TransitionId;
+
+/// Workflow `a` [2,4] then `b` in time range `b`, with a concurrent timeout `c` [5,5]
+pub fn workflow(b: &str) -> (Net, TransitionId, TransitionId, TransitionId) {
+    let mut net = Net::default();
+    let start = net.create_place();
+    let middle = net.create_place();
+    let end = net.create_place();
+    let timer = net.create_place();
+    let a = net.create_transition();
+    let tr_b = net.create_transition();
+    let c = net.create_transition();
+    net[start].initial = 1;
+    net[timer].initial = 1;
+    net[a].time = "[2,4]".parse().unwrap();
+    net[tr_b].time = b.parse().unwrap();
+    net[c].time = "[5,5]".parse().unwrap();
+    net.add_arc(Kind::Consume(start, a, 1)).unwrap();
+    net.add_arc(Kind::Produce(middle, a, 1)).unwrap();
+    net.add_arc(Kind::Consume(middle, tr_b, 1)).unwrap();
+    net.add_arc(Kind::Produce(end, tr_b, 1)).unwrap();
+    net.add_arc(Kind::Consume(timer, c, 1)).unwrap();
+    (net, a, tr_b, c)
+}
@@ -0,0 +1,115 @@
+use pnets::timed::dbm::{Dbm, Difference};
+use pnets::timed::{Bound, Time, TimeRange};
+
+/// x1 in [1,3], x2 in [2,4[ and x1 - x2 <= -1
+fn domain() -> Dbm {
+    let mut dbm = Dbm::new(2);
+    dbm.constrain_range(
+        1,
+        TimeRange {
+            start: Bound::Closed(Time::from_integer(1)),
+            end: Bound::Closed(Time::from_integer(3)),
+        },
+    );
+    dbm.constrain_range(
+        2,
+        TimeRange {
+            start: Bound::Closed(Time::from_integer(2)),
+            end: Bound::Open(Time::from_integer(4)),
+        },
+    );
+    dbm.constrain(1, 2, Bound::Closed(Difference::from_integer(-1)));
+    dbm.close();
+    dbm
+}
+
+#[test]
+fn closure_test() {
+    let dbm = domain();
+    assert!(!dbm.is_empty());
+    assert_eq!(dbm.variables(), 2);
+    // x1 <= x2 - 1 < 3
+    assert_eq!(dbm.upper(1), Bound::Open(Difference::from_integer(3)));
+    assert_eq!(dbm.lower(1), Bound::Closed(Difference::from_integer(1)));
+    // x2 >= x1 + 1 >= 2
+    assert_eq!(dbm.lower(2), Bound::Closed(Difference::from_integer(2)));
+    assert_eq!(dbm.get(2, 1), Bound::Open(Difference::from_integer(3)));
+
+    let mut empty = dbm.clone();
+    empty.constrain(2, 1, Bound::Open(Difference::from_integer(1)));
+    empty.close();
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn intersection_and_inclusion_test() {
+    let dbm = domain();
+    let mut smaller = Dbm::new(2);
+    smaller.constrain(1, 0, Bound::Closed(Difference::from_integer(2)));
+    smaller.close();
+    let intersection = dbm.intersect(&smaller);
+    assert_eq!(
+        intersection.upper(1),
+        Bound::Closed(Difference::from_integer(2))
+    );
+    assert!(dbm.includes(&intersection));
+    assert!(smaller.includes(&intersection));
+    assert!(!intersection.includes(&dbm));
+    assert!(Dbm::new(2).includes(&dbm));
+
+    let mut empty = Dbm::new(2);
+    empty.constrain(0, 1, Bound::Open(Difference::from_integer(-5)));
+    empty.constrain(1, 0, Bound::Closed(Difference::from_integer(5)));
+    empty.close();
+    assert!(empty.is_empty());
+    assert!(dbm.includes(&empty));
+    assert!(dbm.intersect(&empty).is_empty());
+}
+
+#[test]
+fn reset_up_and_project_test() {
+    let mut dbm = domain();
+    dbm.reset(1, Difference::from_integer(0));
+    assert_eq!(dbm.upper(1), Bound::Closed(Difference::from_integer(0)));
+    assert_eq!(dbm.lower(1), Bound::Closed(Difference::from_integer(0)));
+    assert_eq!(dbm.get(2, 1), Bound::Open(Difference::from_integer(4)));
+    assert_eq!(dbm.get(1, 2), Bound::Closed(Difference::from_integer(-2)));
+
+    let mut closed = dbm.clone();
+    closed.close();
+    assert_eq!(closed, dbm);
+
+    dbm.up();
+    assert_eq!(dbm.upper(1), Bound::Infinity);
+    assert_eq!(dbm.get(2, 1), Bound::Open(Difference::from_integer(4)));
+
+    let projection = domain().project(&[2]);
+    assert_eq!(projection.variables(), 1);
+    assert_eq!(
+        projection.lower(1),
+        Bound::Closed(Difference::from_integer(2))
+    );
+    assert_eq!(
+        projection.upper(1),
+        Bound::Open(Difference::from_integer(4))
+    );
+}
+
+#[test]
+fn large_bound_test() {
+    // Bounds above i64::MAX are not truncated
+    let mut dbm = Dbm::new(2);
+    dbm.constrain_range(1, "[0,18446744073709551615]".parse().unwrap());
+    dbm.constrain_range(2, "[18446744073709551615,w[".parse().unwrap());
+    dbm.constrain(1, 2, Bound::Closed(Difference::from_integer(-1)));
+    dbm.close();
+    assert!(!dbm.is_empty());
+    assert_eq!(
+        dbm.upper(1),
+        Bound::Closed(Difference::from_integer(i128::from(u64::MAX)))
+    );
+    assert_eq!(
+        dbm.lower(2),
+        Bound::Closed(Difference::from_integer(i128::from(u64::MAX)))
+    );
+}
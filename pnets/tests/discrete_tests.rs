@@ -0,0 +1,86 @@
+mod common;
+
+use common::workflow;
+use pnets::arc::Kind;
+use pnets::timed::discrete::DiscreteSimulator;
+use pnets::timed::{Bound, Net};
+use pnets::NetError;
+
+#[test]
+fn step_test() {
+    let (net, a, b, c) = workflow("]1/2,3]");
+    let mut simulator = DiscreteSimulator::new(&net);
+    assert!(simulator.firable().is_empty());
+    assert_eq!(simulator.fire(a), Err(NetError::NotFirable(a)));
+    for _ in 0..4 {
+        simulator.tick().unwrap();
+    }
+    assert_eq!(simulator.firable(), vec![a]);
+    assert_eq!(simulator.tick(), Err(NetError::UrgentTransition(a)));
+    simulator.fire(a).unwrap();
+    assert_eq!(simulator.state().clocks[b], 0);
+    assert_eq!(simulator.state().clocks[c], 4);
+    simulator.tick().unwrap();
+    assert_eq!(simulator.firable(), vec![b, c]);
+    assert_eq!(simulator.tick(), Err(NetError::UrgentTransition(c)));
+    simulator.fire(c).unwrap();
+    simulator.fire(b).unwrap();
+    assert_eq!(simulator.history(), &[(a, 4), (c, 5), (b, 5)]);
+    assert!(simulator.firable().is_empty());
+}
+
+#[test]
+fn eager_test() {
+    let (mut net, a, b, c) = workflow("]1/2,3]");
+    let mut simulator = DiscreteSimulator::new(&net);
+    assert_eq!(simulator.run_eager(10), 3);
+    assert_eq!(simulator.history(), &[(a, 2), (b, 3), (c, 5)]);
+    assert_eq!(simulator.state().time, 5);
+
+    // The timeout has priority over b, which can not be fired before it anymore
+    net.add_priority(c, b);
+    net[c].time = "[3,5]".parse().unwrap();
+    let mut simulator = DiscreteSimulator::new(&net);
+    simulator.run_eager(10);
+    assert_eq!(simulator.history(), &[(a, 2), (c, 3), (b, 3)]);
+}
+
+#[test]
+fn stopwatch_test() {
+    let (mut net, a, _, c) = workflow("]1/2,3]");
+    let pause = net.create_place();
+    net[pause].initial = 1;
+    net.add_arc(Kind::StopWatchInhibitor(pause, c, 1)).unwrap();
+    let mut simulator = DiscreteSimulator::new(&net);
+    simulator.run_eager(1);
+    assert_eq!(simulator.history(), &[(a, 2)]);
+    assert_eq!(simulator.state().clocks[c], 0);
+}
+
+#[test]
+fn suspended_eager_test() {
+    // `t` is enabled but suspended forever, time must not elapse endlessly
+    let mut net = Net::default();
+    let p = net.create_place();
+    let empty = net.create_place();
+    let t = net.create_transition();
+    net[p].initial = 1;
+    net[t].time = "[1,2]".parse().unwrap();
+    net.add_arc(Kind::Consume(p, t, 1)).unwrap();
+    net.add_arc(Kind::StopWatch(empty, t, 1)).unwrap();
+    let mut simulator = DiscreteSimulator::new(&net);
+    assert_eq!(simulator.run_eager(1), 0);
+    assert_eq!(simulator.state().time, 0);
+    assert_eq!(simulator.state().clocks[t], 0);
+
+    // `u` can never be fired either
+    let mut net = Net::default();
+    let p = net.create_place();
+    let u = net.create_transition();
+    net[p].initial = 1;
+    net[u].time.start = Bound::Infinity;
+    net.add_arc(Kind::Consume(p, u, 1)).unwrap();
+    let mut simulator = DiscreteSimulator::new(&net);
+    assert_eq!(simulator.run_eager(1), 0);
+    assert_eq!(simulator.state().time, 0);
+}
@@ -0,0 +1,147 @@
+use num_bigint::BigInt;
+use pnets::arc::Kind;
+use pnets::ilp::{BigRational, Goal, Problem, Solution};
+use pnets::predicate::{Comparison, Constraint, Predicate};
+use pnets::standard::structural::{check_state_equation, place_bound, place_bounds, Verdict};
+use pnets::standard::Net;
+use pnets::PlaceId;
+
+fn rational(v: isize) -> BigRational {
+    BigRational::from_integer(BigInt::from(v))
+}
+
+#[test]
+fn relaxation_test() {
+    // max x + y, x + 2y <= 4, 3x + y <= 6
+    let mut problem = Problem::new(2);
+    problem.add_constraint(&[(0, 1), (1, 2)], Comparison::LessEqual, 4);
+    problem.add_constraint(&[(0, 3), (1, 1)], Comparison::LessEqual, 6);
+    problem.set_objective(Goal::Maximize, &[(0, 1), (1, 1)]);
+    assert_eq!(
+        problem.solve_relaxation(),
+        Solution::Optimal {
+            value: BigRational::new(BigInt::from(14), BigInt::from(5)),
+            values: vec![
+                BigRational::new(BigInt::from(8), BigInt::from(5)),
+                BigRational::new(BigInt::from(6), BigInt::from(5)),
+            ],
+        }
+    );
+}
+
+#[test]
+fn branch_and_bound_test() {
+    // max x + y, x + 2y <= 4, 3x + y <= 6, integers
+    let mut problem = Problem::new(2);
+    problem.add_constraint(&[(0, 1), (1, 2)], Comparison::LessEqual, 4);
+    problem.add_constraint(&[(0, 3), (1, 1)], Comparison::LessEqual, 6);
+    problem.set_objective(Goal::Maximize, &[(0, 1), (1, 1)]);
+    match problem.solve() {
+        Solution::Optimal { value, values } => {
+            assert_eq!(value, rational(2));
+            assert!(values.iter().all(|v| v.is_integer()));
+        }
+        s => panic!("Unexpected solution {:?}", s),
+    }
+}
+
+#[test]
+fn equality_and_infeasible_test() {
+    // x - y = -2, x + y >= 3, min x
+    let mut problem = Problem::new(2);
+    problem.add_constraint(&[(0, 1), (1, -1)], Comparison::Equal, -2);
+    problem.add_constraint(&[(0, 1), (1, 1)], Comparison::GreaterEqual, 3);
+    problem.set_objective(Goal::Minimize, &[(0, 1)]);
+    match problem.solve() {
+        Solution::Optimal { value, values } => {
+            assert_eq!(value, rational(1));
+            assert_eq!(values, vec![rational(1), rational(3)]);
+        }
+        s => panic!("Unexpected solution {:?}", s),
+    }
+
+    // 2x = 1 has no integer solution
+    let mut problem = Problem::new(1);
+    problem.add_constraint(&[(0, 2)], Comparison::Equal, 1);
+    assert_eq!(problem.solve(), Solution::Infeasible);
+    assert!(matches!(
+        problem.solve_relaxation(),
+        Solution::Optimal { .. }
+    ));
+}
+
+#[test]
+fn unbounded_and_node_limit_test() {
+    let mut problem = Problem::new(2);
+    problem.add_constraint(&[(0, 1), (1, -1)], Comparison::LessEqual, 1);
+    problem.set_objective(Goal::Maximize, &[(0, 1)]);
+    assert_eq!(problem.solve(), Solution::Unbounded);
+
+    // 2x - 2y = 1 has an unbounded relaxation but no integer solution
+    let mut problem = Problem::new(2);
+    problem.add_constraint(&[(0, 2), (1, -2)], Comparison::Equal, 1);
+    problem.set_node_limit(Some(50));
+    assert_eq!(problem.solve(), Solution::Unknown);
+}
+
+/// Mutual exclusion between two processes with a shared resource
+fn mutex() -> (Net, Vec<PlaceId>) {
+    let mut net = Net::default();
+    let idle_0 = net.create_place();
+    let critical_0 = net.create_place();
+    let idle_1 = net.create_place();
+    let critical_1 = net.create_place();
+    let resource = net.create_place();
+    let unbounded = net.create_place();
+    net[idle_0].initial = 1;
+    net[idle_1].initial = 1;
+    net[resource].initial = 1;
+    for &(idle, critical) in &[(idle_0, critical_0), (idle_1, critical_1)] {
+        let enter = net.create_transition();
+        let leave = net.create_transition();
+        net.add_arc(Kind::Consume(idle, enter, 1)).unwrap();
+        net.add_arc(Kind::Consume(resource, enter, 1)).unwrap();
+        net.add_arc(Kind::Produce(critical, enter, 1)).unwrap();
+        net.add_arc(Kind::Consume(critical, leave, 1)).unwrap();
+        net.add_arc(Kind::Produce(idle, leave, 1)).unwrap();
+        net.add_arc(Kind::Produce(resource, leave, 1)).unwrap();
+        net.add_arc(Kind::Produce(unbounded, leave, 1)).unwrap();
+    }
+    (
+        net,
+        vec![idle_0, critical_0, idle_1, critical_1, resource, unbounded],
+    )
+}
+
+#[test]
+fn place_bounds_test() {
+    let (net, places) = mutex();
+    assert_eq!(place_bound(&net, places[1]), Ok(Some(1)));
+    let bounds = place_bounds(&net);
+    assert_eq!(bounds[places[4]], Some(1));
+    assert_eq!(bounds[places[5]], None);
+}
+
+#[test]
+fn state_equation_test() {
+    let (net, places) = mutex();
+    let both_critical = Predicate::default()
+        .and(Constraint::at_least(places[1], 1))
+        .and(Constraint::at_least(places[3], 1));
+    assert_eq!(
+        check_state_equation(&net, &both_critical),
+        Ok(Verdict::Infeasible)
+    );
+
+    let one_critical = Predicate::new(vec![Constraint::new(
+        vec![(places[1], 1), (places[3], 1)],
+        Comparison::Equal,
+        1,
+    )]);
+    match check_state_equation(&net, &one_critical) {
+        Ok(Verdict::Feasible(firing_count)) => {
+            assert_eq!(firing_count.iter().map(|&(_, c)| c).sum::<usize>(), 1)
+        }
+        v => panic!("Unexpected verdict {:?}", v),
+    }
+}
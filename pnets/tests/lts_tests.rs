@@ -0,0 +1,107 @@
+use pnets::arc::Kind;
+use pnets::lts::{Edge, Lts};
+use pnets::timed::state_class::StateClassGraph;
+use pnets::timed::zone::{Mode, ZoneGraph};
+use pnets::timed::Net;
+use pnets::NetError;
+
+fn edge(from: usize, label: &str, to: usize) -> Edge {
+    Edge {
+        from,
+        label: label.to_string(),
+        to,
+    }
+}
+
+fn read(aut: &str) -> Result<Lts, Box<dyn std::error::Error>> {
+    Lts::read_aut(&mut aut.as_bytes())
+}
+
+#[test]
+fn read_test() {
+    let lts =
+        read("des (1, 3, 3)\n(1, \"a, \\\"b\\\"\", 2)\n\n( 2 , i , 0 )\n(0,tr b,1)\n").unwrap();
+    assert_eq!(lts.initial, 1);
+    assert_eq!(lts.states, 3);
+    assert_eq!(
+        lts.edges,
+        vec![edge(1, "a, \"b\"", 2), edge(2, "i", 0), edge(0, "tr b", 1)]
+    );
+    assert_eq!(
+        lts.labels().into_iter().collect::<Vec<_>>(),
+        vec!["a, \"b\"", "i", "tr b"]
+    );
+    assert!(lts.deadlocks().is_empty());
+}
+
+#[test]
+fn write_test() {
+    let lts = Lts {
+        initial: 0,
+        states: 3,
+        edges: vec![edge(0, "a\\b", 1), edge(1, "\"c\"", 2)],
+    };
+    let mut out = vec![];
+    lts.write_aut(&mut out).unwrap();
+    let aut = String::from_utf8(out).unwrap();
+    assert_eq!(
+        aut,
+        "des (0, 2, 3)\n(0, \"a\\\\b\", 1)\n(1, \"\\\"c\\\"\", 2)\n"
+    );
+    assert_eq!(read(&aut).unwrap(), lts);
+    assert_eq!(lts.deadlocks(), vec![2]);
+}
+
+#[test]
+fn invalid_test() {
+    for (aut, line) in &[
+        ("", 0),
+        ("(0, a, 1)\n", 1),
+        ("des (0, 1, 2)\n(0, a)\n", 2),
+        ("des (0, 1, 2)\n(0, a, 2)\n", 2),
+        ("des (0, 1, 2)\n(x, a, 1)\n", 2),
+        ("des (0, 1, 2)\ndes (0, 1, 2)\n", 2),
+        ("des (0, 2, 2)\n(0, a, 1)\n", 2),
+        ("des (2, 0, 2)\n", 1),
+    ] {
+        match read(aut).unwrap_err().downcast::<NetError>() {
+            Ok(error) => assert!(
+                matches!(*error, NetError::InvalidLts(l, _) if l == *line),
+                "{}: {}",
+                aut,
+                error
+            ),
+            Err(error) => panic!("{}: {}", aut, error),
+        }
+    }
+}
+
+#[test]
+fn graph_test() {
+    let mut net = Net::default();
+    let p0 = net.create_place();
+    let p1 = net.create_place();
+    let t0 = net.create_transition();
+    let t1 = net.create_transition();
+    net.rename_node(t0.into(), "t0").unwrap();
+    net.rename_node(t1.into(), "t1").unwrap();
+    net[p0].initial = 1;
+    net[t0].time = "[1,2]".parse().unwrap();
+    net.add_arc(Kind::Consume(p0, t0, 1)).unwrap();
+    net.add_arc(Kind::Produce(p1, t0, 1)).unwrap();
+    net.add_arc(Kind::Consume(p1, t1, 1)).unwrap();
+    net.add_arc(Kind::Produce(p0, t1, 1)).unwrap();
+
+    let classes = StateClassGraph::build(&net, None).unwrap();
+    let lts = classes.lts(&net);
+    assert_eq!(lts.states, classes.class_count());
+    assert_eq!(lts.edges, vec![edge(0, "t0", 1), edge(1, "t1", 0)]);
+
+    // The written graph is read back
+    let mut out = vec![];
+    classes.write_aut(&net, &mut out).unwrap();
+    assert_eq!(Lts::read_aut(&mut out.as_slice()).unwrap(), lts);
+
+    let zones = ZoneGraph::build(&net, Mode::StrongClasses, None).unwrap();
+    assert_eq!(zones.lts(&net).labels(), lts.labels());
+}
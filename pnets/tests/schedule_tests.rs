@@ -0,0 +1,67 @@
+mod common;
+
+use common::workflow;
+use pnets::arc::Kind;
+use pnets::timed::schedule::Schedule;
+use pnets::timed::TimeRange;
+use pnets::NetError;
+
+fn range(s: &str) -> TimeRange {
+    s.parse().unwrap()
+}
+
+#[test]
+fn schedule_test() {
+    let (net, a, b, c) = workflow("[1,3]");
+    let schedule = Schedule::compute(&net, &[a, b]).unwrap().unwrap();
+    assert_eq!(schedule.firings[0].transition, a);
+    assert_eq!(schedule.firings[0].dates, range("[2,4]"));
+    // The timeout is still enabled, so b must be fired before it
+    assert_eq!(schedule.firings[1].dates, range("[3,5]"));
+    assert_eq!(schedule.completion(), range("[3,5]"));
+
+    let schedule = Schedule::compute(&net, &[a, c, b]).unwrap().unwrap();
+    assert_eq!(schedule.firings[1].dates, range("[5,5]"));
+    assert_eq!(schedule.completion(), range("[5,7]"));
+
+    assert!(Schedule::compute(&net, &[b]).unwrap().is_none());
+    assert_eq!(
+        Schedule::compute(&net, &[]).unwrap().unwrap().completion(),
+        range("[0,0]")
+    );
+}
+
+#[test]
+fn priority_test() {
+    let (mut net, a, _, c) = workflow("[1,3]");
+    net.add_priority(c, a);
+    net[c].time = "[3,5]".parse().unwrap();
+    let schedule = Schedule::compute(&net, &[a]).unwrap().unwrap();
+    assert_eq!(schedule.completion(), range("[2,3["));
+}
+
+#[test]
+fn large_bound_test() {
+    let (mut net, a, b, c) = workflow("[1,3]");
+    net[a].time = "[18446744073709551615,w[".parse().unwrap();
+    net[b].time = "[1,w[".parse().unwrap();
+    net[c].time = "[0,w[".parse().unwrap();
+    let schedule = Schedule::compute(&net, &[a]).unwrap().unwrap();
+    assert_eq!(schedule.completion(), range("[18446744073709551615,w["));
+    // The date of b does not fit in a time
+    assert_eq!(
+        Schedule::compute(&net, &[a, b]).unwrap_err(),
+        NetError::InvalidTimeRange
+    );
+}
+
+#[test]
+fn stopwatch_test() {
+    let (mut net, a, b, _) = workflow("[1,3]");
+    let place = net.create_place();
+    net.add_arc(Kind::StopWatch(place, b, 1)).unwrap();
+    assert_eq!(
+        Schedule::compute(&net, &[a]).unwrap_err(),
+        NetError::UnsupportedArc(Kind::StopWatch(place, b, 1))
+    );
+}
@@ -0,0 +1,98 @@
+use pnets::arc::Kind;
+use pnets::predicate::{Comparison, Constraint, Predicate};
+use pnets::standard::search::{Search, Witness};
+use pnets::standard::Net;
+use pnets::{Marking, NetError, PlaceId};
+
+/// Counter incremented by `inc`, increased by three by `jump` and decremented by `dec`
+fn counter() -> (Net, PlaceId) {
+    let mut net = Net::default();
+    let count = net.create_place();
+    let inc = net.create_transition();
+    let jump = net.create_transition();
+    let dec = net.create_transition();
+    net.rename_node(inc.into(), "inc").unwrap();
+    net.rename_node(jump.into(), "jump").unwrap();
+    net.rename_node(dec.into(), "dec").unwrap();
+    net[count].initial = 1;
+    net.add_arc(Kind::Produce(count, inc, 1)).unwrap();
+    net.add_arc(Kind::Consume(count, jump, 1)).unwrap();
+    net.add_arc(Kind::Produce(count, jump, 4)).unwrap();
+    net.add_arc(Kind::Consume(count, dec, 1)).unwrap();
+    (net, count)
+}
+
+#[test]
+fn firing_test() {
+    let (net, count) = counter();
+    let marking = net.initial_marking();
+    assert_eq!(marking[count], 1);
+    let jump = &net.transitions[1.into()];
+    assert!(jump.is_enabled(&marking));
+    let marking = jump.fire(&marking);
+    assert_eq!(marking[count], 4);
+
+    let dec = &net.transitions[2.into()];
+    let marking = (0..4).fold(marking, |marking, _| dec.fire(&marking));
+    assert_eq!(marking, Marking::default());
+    assert!(!dec.is_enabled(&marking));
+}
+
+#[test]
+fn shortest_test() {
+    let (net, count) = counter();
+    let target = Predicate::default().and(Constraint::exactly(count, 7));
+    assert_eq!(
+        Search::new(&net, target).run_names(),
+        Ok(Some(vec!["jump".to_string(), "jump".to_string()]))
+    );
+
+    let target = Predicate::default().and(Constraint::exactly(count, 0));
+    assert_eq!(
+        Search::new(&net, target).run_names(),
+        Ok(Some(vec!["dec".to_string()]))
+    );
+
+    let target = Predicate::default().and(Constraint::exactly(count, 1));
+    assert_eq!(Search::new(&net, target).run(), Ok(Witness::Found(vec![])));
+}
+
+#[test]
+fn unreachable_test() {
+    // Two tokens circulate between p0 and p1, p0 + p1 = 2 is invariant
+    let mut net = Net::default();
+    let p0 = net.create_place();
+    let p1 = net.create_place();
+    let t0 = net.create_transition();
+    let t1 = net.create_transition();
+    net[p0].initial = 2;
+    net.add_arc(Kind::Consume(p0, t0, 1)).unwrap();
+    net.add_arc(Kind::Produce(p1, t0, 1)).unwrap();
+    net.add_arc(Kind::Consume(p1, t1, 1)).unwrap();
+    net.add_arc(Kind::Produce(p0, t1, 1)).unwrap();
+
+    // Pruned by the state equation
+    let target = Predicate::default().and(Constraint::at_least(p1, 3));
+    assert_eq!(Search::new(&net, target).run(), Ok(Witness::Unreachable));
+
+    // Satisfies the state equation but needs the exploration of the whole graph
+    let target = Predicate::new(vec![Constraint::new(
+        vec![(p0, 2), (p1, -2)],
+        Comparison::Equal,
+        1,
+    )]);
+    assert_eq!(
+        Search::new(&net, target.clone()).run(),
+        Ok(Witness::Unreachable)
+    );
+    assert_eq!(
+        Search::new(&net, target).with_marking_limit(Some(1)).run(),
+        Ok(Witness::Unknown)
+    );
+
+    let target = Predicate::default().and(Constraint::at_least(PlaceId::from(5), 1));
+    assert_eq!(
+        Search::new(&net, target).run(),
+        Err(NetError::InvalidPlace(PlaceId::from(5)))
+    );
+}
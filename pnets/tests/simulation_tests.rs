@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+
+use pnets::arc::Kind;
+use pnets::simulation::{Firable, Policy, Simulate, Simulator, Step, Walk};
+use pnets::timed::{Bound, Time, TimeRange};
+use pnets::{standard, timed, TransitionId};
+
+/// p0 -> t0 -> p1 -> t1 -> p2 and t2 loops on p0
+fn standard_net() -> standard::Net {
+    let mut net = standard::Net::default();
+    let p0 = net.create_place();
+    let p1 = net.create_place();
+    let p2 = net.create_place();
+    let t0 = net.create_transition();
+    let t1 = net.create_transition();
+    let t2 = net.create_transition();
+    net.rename_node(t0.into(), "t0").unwrap();
+    net.rename_node(t1.into(), "t1").unwrap();
+    net.rename_node(t2.into(), "loop {2}").unwrap();
+    net[t2].label = Some("loop".to_string());
+    net[p0].initial = 1;
+    net.add_arc(Kind::Consume(p0, t0, 1)).unwrap();
+    net.add_arc(Kind::Produce(p1, t0, 1)).unwrap();
+    net.add_arc(Kind::Consume(p1, t1, 1)).unwrap();
+    net.add_arc(Kind::Produce(p2, t1, 1)).unwrap();
+    net.add_arc(Kind::Consume(p0, t2, 1)).unwrap();
+    net.add_arc(Kind::Produce(p0, t2, 1)).unwrap();
+    net
+}
+
+#[test]
+fn seed_test() {
+    let net = standard_net();
+    let simulator = Simulator::new(42).with_depth(20).with_restarts(9);
+    let report = simulator.run(&net);
+    assert_eq!(report.walks.len(), 10);
+    assert_eq!(report.walks, simulator.run(&net).walks);
+    // All markings are visited and the only deadlock is the marking of p2
+    assert_eq!(report.visited.len(), 3);
+    assert_eq!(report.deadlocks.len(), 1);
+    assert_eq!(report.deadlocks[0][2.into()], 1);
+    for walk in &report.walks {
+        if walk.deadlock {
+            assert_eq!(
+                &walk.steps[walk.steps.len() - 2..],
+                &[
+                    Step {
+                        transition: 0.into(),
+                        delay: 0
+                    },
+                    Step {
+                        transition: 1.into(),
+                        delay: 0
+                    }
+                ]
+            );
+        } else {
+            assert_eq!(walk.steps.len(), 20);
+        }
+    }
+}
+
+#[test]
+fn weighted_test() {
+    let net = standard_net();
+    let mut weights = HashMap::new();
+    weights.insert("loop".to_string(), 0);
+    let report = Simulator::new(7)
+        .with_policy(Policy::Weighted(weights))
+        .with_restarts(4)
+        .run(&net);
+    for walk in report.walks {
+        assert!(walk.deadlock);
+        assert_eq!(walk.steps.len(), 2);
+    }
+}
+
+#[test]
+fn export_test() {
+    let net = standard_net();
+    let walk = Walk {
+        steps: vec![
+            Step {
+                transition: 2.into(),
+                delay: 0,
+            },
+            Step {
+                transition: 0.into(),
+                delay: 3,
+            },
+        ],
+        deadlock: false,
+    };
+    let mut out = vec![];
+    walk.export(&net, &mut out).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), "{loop \\{2\\}} t0@3\n");
+}
+
+#[test]
+fn timed_test() {
+    // fast [0,1] and slow [2,3] compete for the token of p0, fast has priority over slow
+    let mut net = timed::Net::default();
+    let p0 = net.create_place();
+    let p1 = net.create_place();
+    let fast = net.create_transition();
+    let slow = net.create_transition();
+    net[p0].initial = 1;
+    net[fast].time = TimeRange {
+        start: Bound::Closed(Time::from_integer(0)),
+        end: Bound::Closed(Time::from_integer(1)),
+    };
+    net[slow].time = TimeRange {
+        start: Bound::Closed(Time::from_integer(2)),
+        end: Bound::Open(Time::from_integer(4)),
+    };
+    for &tr in &[fast, slow] {
+        net.add_arc(Kind::Consume(p0, tr, 1)).unwrap();
+        net.add_arc(Kind::Produce(p1, tr, 1)).unwrap();
+    }
+
+    // slow can never fire before the deadline of fast
+    let report = Simulator::new(3).with_restarts(19).run(&net);
+    for walk in &report.walks {
+        assert!(walk.deadlock);
+        assert_eq!(walk.steps.len(), 1);
+        assert_eq!(walk.steps[0].transition, fast);
+        assert!(walk.steps[0].delay <= 1);
+    }
+
+    net[fast].time = TimeRange {
+        start: Bound::Open(Time::from_integer(2)),
+        end: Bound::Infinity,
+    };
+    let report = Simulator::new(3).with_restarts(19).run(&net);
+    let fired: Vec<TransitionId> = report.walks.iter().map(|w| w.steps[0].transition).collect();
+    assert!(fired.contains(&fast) && fired.contains(&slow));
+    for walk in &report.walks {
+        let step = walk.steps[0];
+        if step.transition == fast {
+            assert_eq!(step.delay, 3);
+        } else {
+            assert!((2..=3).contains(&step.delay));
+        }
+    }
+
+    net.add_priority(fast, slow);
+    let report = Simulator::new(3)
+        .with_policy(Policy::Priority)
+        .with_restarts(19)
+        .run(&net);
+    assert!(report.walks.iter().all(|w| w.steps[0].transition == fast));
+}
+
+#[test]
+fn stopwatch_test() {
+    // stuck [0,0] is suspended by an empty place, it neither fires nor bounds the delay of go
+    let mut net = timed::Net::default();
+    let p0 = net.create_place();
+    let q = net.create_place();
+    let empty = net.create_place();
+    let go = net.create_transition();
+    let stuck = net.create_transition();
+    net[p0].initial = 1;
+    net[q].initial = 1;
+    net[go].time = "[2,3]".parse().unwrap();
+    net[stuck].time = "[0,0]".parse().unwrap();
+    net.add_arc(Kind::Consume(p0, go, 1)).unwrap();
+    net.add_arc(Kind::Consume(q, stuck, 1)).unwrap();
+    net.add_arc(Kind::StopWatch(empty, stuck, 1)).unwrap();
+
+    let state = net.initial_state();
+    assert_eq!(
+        net.firable(&state),
+        vec![Firable {
+            transition: go,
+            earliest: 2,
+            latest: Some(3),
+        }]
+    );
+    // The suspended clock does not progress during the delay
+    let state = net.fire(&state, go, 2);
+    assert_eq!(state.clocks[stuck], 0);
+}
+
+#[test]
+fn reset_test() {
+    // t resets and produces q again, which disables u during the firing
+    let mut net = timed::Net::default();
+    let p = net.create_place();
+    let q = net.create_place();
+    let t = net.create_transition();
+    let u = net.create_transition();
+    net[p].initial = 1;
+    net[q].initial = 1;
+    net[u].time = "[5,5]".parse().unwrap();
+    net.add_arc(Kind::Consume(p, t, 1)).unwrap();
+    net.add_arc(Kind::Reset(q, t, 1)).unwrap();
+    net.add_arc(Kind::Produce(q, t, 1)).unwrap();
+    net.add_arc(Kind::Test(q, u, 1)).unwrap();
+
+    let state = net.fire(&net.initial_state(), t, 2);
+    assert_eq!(state.clocks[u], 0);
+}
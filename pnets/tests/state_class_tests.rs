@@ -0,0 +1,119 @@
+use pnets::arc::Kind;
+use pnets::timed::state_class::StateClassGraph;
+use pnets::timed::{Bound, Net, Time, TimeRange};
+use pnets::{NetError, PlaceId, TransitionId};
+
+/// Two transitions with intervals `first` and `second` competing for the token of p0
+fn conflict(first: &str, second: &str) -> (Net, TransitionId, TransitionId) {
+    let mut net = Net::default();
+    let p0 = net.create_place();
+    let p1 = net.create_place();
+    let p2 = net.create_place();
+    let t0 = net.create_transition();
+    let t1 = net.create_transition();
+    net.rename_node(t0.into(), "t0").unwrap();
+    net.rename_node(t1.into(), "t1").unwrap();
+    net[p0].initial = 1;
+    net[t0].time = first.parse().unwrap();
+    net[t1].time = second.parse().unwrap();
+    net.add_arc(Kind::Consume(p0, t0, 1)).unwrap();
+    net.add_arc(Kind::Produce(p1, t0, 1)).unwrap();
+    net.add_arc(Kind::Consume(p0, t1, 1)).unwrap();
+    net.add_arc(Kind::Produce(p2, t1, 1)).unwrap();
+    (net, t0, t1)
+}
+
+#[test]
+fn conflict_test() {
+    // t1 can not fire because t0 must fire before 2
+    let (net, _, _) = conflict("[0,2]", "[3,5]");
+    let graph = StateClassGraph::build(&net, None).unwrap();
+    assert!(graph.complete);
+    assert_eq!(graph.class_count(), 2);
+    assert_eq!(graph.edge_count(), 1);
+    assert_eq!(graph.deadlocks(), vec![1]);
+    let mut out = vec![];
+    graph.write_aut(&net, &mut out).unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "des (0, 1, 2)\n(0, \"t0\", 1)\n"
+    );
+
+    let (net, _, _) = conflict("[1,3]", "[2,4]");
+    let graph = StateClassGraph::build(&net, None).unwrap();
+    assert_eq!(graph.class_count(), 3);
+    assert_eq!(graph.deadlocks().len(), 2);
+
+    // Open bounds: ]2,3] and [1,2] can not fire at the same time
+    let (net, _, _) = conflict("]2,3]", "[1,2]");
+    assert_eq!(StateClassGraph::build(&net, None).unwrap().class_count(), 2);
+}
+
+#[test]
+fn priority_test() {
+    let (mut net, t0, t1) = conflict("[1,1]", "[1,1]");
+    assert_eq!(StateClassGraph::build(&net, None).unwrap().class_count(), 3);
+    net.add_priority(t0, t1);
+    let graph = StateClassGraph::build(&net, None).unwrap();
+    assert_eq!(graph.class_count(), 2);
+    assert_eq!(graph.edges[0].transition, t0);
+}
+
+#[test]
+fn persistent_test() {
+    // ta [2,2] and tb [3,3] are concurrent, tb keeps its clock when ta fires
+    let mut net = Net::default();
+    let places: Vec<PlaceId> = (0..4).map(|_| net.create_place()).collect();
+    let ta = net.create_transition();
+    let tb = net.create_transition();
+    net[places[0]].initial = 1;
+    net[places[1]].initial = 1;
+    net[ta].time = "[2,2]".parse().unwrap();
+    net[tb].time = "[3,3]".parse().unwrap();
+    net.add_arc(Kind::Consume(places[0], ta, 1)).unwrap();
+    net.add_arc(Kind::Produce(places[2], ta, 1)).unwrap();
+    net.add_arc(Kind::Consume(places[1], tb, 1)).unwrap();
+    net.add_arc(Kind::Produce(places[3], tb, 1)).unwrap();
+
+    let graph = StateClassGraph::build(&net, None).unwrap();
+    assert_eq!(graph.class_count(), 3);
+    assert_eq!(graph.edges[0].transition, ta);
+    let class = &graph.classes[graph.edges[0].to];
+    assert_eq!(
+        class.firing_interval(tb),
+        Some((
+            Bound::Closed(Time::from_integer(1)),
+            Bound::Closed(Time::from_integer(1))
+        ))
+    );
+    assert_eq!(class.firing_interval(ta), None);
+    assert_eq!(graph.deadlocks(), vec![2]);
+}
+
+#[test]
+fn limit_test() {
+    // t produces a token in p1 each second, the graph is infinite
+    let mut net = Net::default();
+    let p0 = net.create_place();
+    let p1 = net.create_place();
+    let t = net.create_transition();
+    net[p0].initial = 1;
+    net[t].time = "[1,1]".parse().unwrap();
+    net.add_arc(Kind::Consume(p0, t, 1)).unwrap();
+    net.add_arc(Kind::Produce(p0, t, 1)).unwrap();
+    net.add_arc(Kind::Produce(p1, t, 1)).unwrap();
+
+    let graph = StateClassGraph::build(&net, Some(5)).unwrap();
+    assert!(!graph.complete);
+    assert_eq!(graph.class_count(), 5);
+    assert!(!graph.has_deadlock());
+
+    net[t].time = TimeRange {
+        start: Bound::Infinity,
+        end: Bound::Infinity,
+    };
+    assert_eq!(
+        StateClassGraph::build(&net, None).err(),
+        Some(NetError::InvalidTimeRange)
+    );
+}
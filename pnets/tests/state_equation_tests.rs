@@ -0,0 +1,83 @@
+use pnets::arc::Kind;
+use pnets::predicate::{Constraint, Predicate};
+use pnets::standard::state_equation::{Format, StateEquationExporterBuilder};
+use pnets::standard::Net;
+use pnets::{NetError, PlaceId};
+
+fn simple_net() -> (Net, PlaceId, PlaceId) {
+    let mut net = Net::default();
+    net.name = "simple".to_string();
+    let p0 = net.create_place();
+    let p1 = net.create_place();
+    let t0 = net.create_transition();
+    let t1 = net.create_transition();
+    net.rename_node(p0.into(), "p0").unwrap();
+    net.rename_node(p1.into(), "p-1").unwrap();
+    net.rename_node(t0.into(), "t0").unwrap();
+    net.rename_node(t1.into(), "t1").unwrap();
+    net[p0].initial = 1;
+    net.add_arc(Kind::Consume(p0, t0, 1)).unwrap();
+    net.add_arc(Kind::Produce(p1, t0, 2)).unwrap();
+    net.add_arc(Kind::Consume(p1, t1, 2)).unwrap();
+    net.add_arc(Kind::Produce(p0, t1, 1)).unwrap();
+    // Self loop, it must not appear in the equation
+    net.add_arc(Kind::Consume(p0, t1, 1)).unwrap();
+    net.add_arc(Kind::Produce(p0, t1, 1)).unwrap();
+    (net, p0, p1)
+}
+
+#[test]
+fn lp_export() {
+    let (net, _, p1) = simple_net();
+    let mut out = vec![];
+    StateEquationExporterBuilder::new(&mut out)
+        .with_target(Predicate::default().and(Constraint::at_least(p1, 3)))
+        .build()
+        .export(&net)
+        .unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "\\ State equation of net simple\n\
+         Minimize\n obj: s_t0 + s_t1\n\
+         Subject To\n \
+         eq_p0: m_p0 + s_t0 - s_t1 = 1\n \
+         eq_p_1: m_p_1 - 2 s_t0 + 2 s_t1 = 0\n \
+         target_0: m_p_1 >= 3\n\
+         General\n m_p0\n m_p_1\n s_t0\n s_t1\n\
+         End\n"
+    );
+}
+
+#[test]
+fn smtlib_export() {
+    let (net, p0, _) = simple_net();
+    let mut out = vec![];
+    StateEquationExporterBuilder::new(&mut out)
+        .with_format(Format::SmtLib)
+        .with_target(Predicate::default().and(Constraint::exactly(p0, 0)))
+        .build()
+        .export(&net)
+        .unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.contains("(set-logic QF_LIA)\n"));
+    assert!(out.contains("(declare-const s_t1 Int)\n(assert (>= s_t1 0))\n"));
+    assert!(out.contains("(assert (! (= (+ m_p0 s_t0 (- s_t1)) 1) :named eq_p0))\n"));
+    assert!(out.contains("(assert (! (= (+ m_p_1 (* (- 2) s_t0) (* 2 s_t1)) 0) :named eq_p_1))\n"));
+    assert!(out.contains("(assert (! (= m_p0 0) :named target_0))\n"));
+    assert!(out.ends_with("(check-sat)\n(get-model)\n"));
+}
+
+#[test]
+fn invalid_target() {
+    let (mut net, p0, _) = simple_net();
+    net.delete_place(p0);
+    let mut out = vec![];
+    let result = StateEquationExporterBuilder::new(&mut out)
+        .with_target(Predicate::default().and(Constraint::at_least(p0, 1)))
+        .build()
+        .export(&net);
+    assert_eq!(
+        result.unwrap_err().downcast_ref::<NetError>(),
+        Some(&NetError::InvalidPlace(p0))
+    );
+}
@@ -0,0 +1,181 @@
+use pnets::timed::{Bound, Time, TimeRange};
+use pnets::NetError;
+use proptest::prelude::*;
+
+/// Times used as bounds, multiples of 1/12 up to 20
+fn time() -> impl Strategy<Value = Time> {
+    (0_u64..=240).prop_map(|n| Time::new(n, 12))
+}
+
+fn bound() -> impl Strategy<Value = Bound> {
+    prop_oneof![
+        4 => time().prop_map(Bound::Closed),
+        4 => time().prop_map(Bound::Open),
+        1 => Just(Bound::Infinity),
+    ]
+}
+
+fn range() -> impl Strategy<Value = TimeRange> {
+    (bound(), bound()).prop_map(|(start, end)| TimeRange { start, end })
+}
+
+/// Every non-empty range with bounds generated by [`time`] contains a multiple of 1/24 below 21
+fn grid() -> impl Iterator<Item = Time> {
+    (0..=21 * 24).map(|n| Time::new(n, 24))
+}
+
+#[test]
+fn parse_test() {
+    assert_eq!(
+        "[0.5, 3/2]".parse::<TimeRange>(),
+        Ok(TimeRange {
+            start: Bound::Closed(Time::new(1, 2)),
+            end: Bound::Closed(Time::new(3, 2)),
+        })
+    );
+    assert_eq!(
+        "]1K,w[".parse::<TimeRange>(),
+        Ok(TimeRange {
+            start: Bound::Open(Time::from_integer(1_000)),
+            end: Bound::Infinity,
+        })
+    );
+    assert_eq!(
+        "[(2M),18446744073709551615[".parse::<TimeRange>(),
+        Ok(TimeRange {
+            start: Bound::Closed(Time::from_integer(2_000_000)),
+            end: Bound::Open(Time::from_integer(u64::MAX)),
+        })
+    );
+    for invalid in &[
+        "[1,2",
+        "[1/0,2]",
+        "[,2]",
+        "(1,2)",
+        "[1,2] x",
+        "[1,99999999999999999999]",
+    ] {
+        assert_eq!(
+            invalid.parse::<TimeRange>(),
+            Err(NetError::InvalidTimeRange),
+            "{}",
+            invalid
+        );
+    }
+    assert_eq!(TimeRange::default().to_string(), "[0,w[");
+
+    // Ranges starting at infinity are printed and parsed back
+    let never = TimeRange {
+        start: Bound::Infinity,
+        end: Bound::Infinity,
+    };
+    assert_eq!(never.to_string(), "]w,w[");
+    assert_eq!("]w,w[".parse::<TimeRange>(), Ok(never));
+    assert_eq!(
+        "[w,w[".parse::<TimeRange>(),
+        Err(NetError::InvalidTimeRange)
+    );
+}
+
+#[test]
+fn operations_test() {
+    let range: TimeRange = "[1,3[".parse().unwrap();
+    assert!(range.contains(Time::from_integer(1)));
+    assert!(!range.contains(Time::from_integer(3)));
+    assert!(range.intersect("]3,4]".parse().unwrap()).is_empty());
+    assert!(!range.intersect("[2.5,4]".parse().unwrap()).is_empty());
+    assert_eq!(
+        range.shift(Time::new(1, 2)).unwrap().to_string(),
+        "[3/2,7/2["
+    );
+    assert_eq!(
+        range.sum("]0,w[".parse().unwrap()).unwrap().to_string(),
+        "]1,w["
+    );
+    assert_eq!(range.hull("]5,6]".parse().unwrap()).to_string(), "[1,6]");
+    assert!(!TimeRange {
+        start: Bound::Infinity,
+        end: Bound::Infinity,
+    }
+    .contains(Time::from_integer(0)));
+
+    // Overflowing bounds are rejected
+    let large: TimeRange = "[18446744073709551615,w[".parse().unwrap();
+    assert_eq!(
+        large.shift(Time::from_integer(1)),
+        Err(NetError::InvalidTimeRange)
+    );
+    assert_eq!(large.sum(range), Err(NetError::InvalidTimeRange));
+}
+
+#[test]
+fn order_test() {
+    let mut ranges: Vec<TimeRange> = ["]1,2]", "[1,w[", "[1,2]", "[0,5]", "[1,2["]
+        .iter()
+        .map(|range| range.parse().unwrap())
+        .collect();
+    ranges.sort();
+    let sorted: Vec<String> = ranges.iter().map(ToString::to_string).collect();
+    assert_eq!(sorted, vec!["[0,5]", "[1,2[", "[1,2]", "[1,w[", "]1,2]"]);
+}
+
+proptest! {
+    #[test]
+    fn intersect_contains(a in range(), b in range()) {
+        let intersection = a.intersect(b);
+        for t in grid() {
+            prop_assert_eq!(intersection.contains(t), a.contains(t) && b.contains(t));
+        }
+    }
+
+    #[test]
+    fn empty_contains_nothing(a in range()) {
+        prop_assert_eq!(a.is_empty(), !grid().any(|t| a.contains(t)));
+    }
+
+    #[test]
+    fn includes_contains(a in range(), b in range()) {
+        prop_assert_eq!(a.includes(b), grid().all(|t| !b.contains(t) || a.contains(t)));
+    }
+
+    #[test]
+    fn hull_includes_both(a in range(), b in range()) {
+        let hull = a.hull(b);
+        prop_assert!(hull.includes(a));
+        prop_assert!(hull.includes(b));
+        prop_assert_eq!(hull.is_empty(), a.is_empty() && b.is_empty());
+    }
+
+    #[test]
+    fn shift_contains(a in range(), delay in time()) {
+        let shifted = a.shift(delay).unwrap();
+        for t in grid() {
+            prop_assert_eq!(shifted.contains(t + delay), a.contains(t));
+        }
+    }
+
+    #[test]
+    fn sum_contains(a in range(), b in range(), x in time(), y in time()) {
+        let sum = a.sum(b).unwrap();
+        if a.contains(x) && b.contains(y) {
+            prop_assert!(sum.contains(x + y));
+        }
+        prop_assert_eq!(sum.is_empty(), a.is_empty() || b.is_empty());
+    }
+
+    #[test]
+    fn order_by_bounds(a in range(), b in range()) {
+        prop_assert_eq!(a.cmp(&b) == std::cmp::Ordering::Equal, a == b);
+        prop_assert_eq!(a.cmp(&b), b.cmp(&a).reverse());
+        // A range which starts earlier contains a time before the start of the other
+        if !a.is_empty() && !b.is_empty() && a.start != b.start {
+            let earlier = grid().find(|&t| a.contains(t) || b.contains(t)).unwrap();
+            prop_assert_eq!(a < b, a.contains(earlier));
+        }
+    }
+
+    #[test]
+    fn print_parse(a in range()) {
+        prop_assert_eq!(a.to_string().parse::<TimeRange>(), Ok(a));
+    }
+}
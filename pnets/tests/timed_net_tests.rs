@@ -148,6 +148,65 @@ fn update_priorities_test() {
     assert_eq!(net[t3].priorities, vec![]);
 }
 
+#[test]
+fn cyclic_priorities_test() {
+    let mut net = Net::default();
+    assert!(net.update_priorities().is_ok());
+
+    let t0 = net.create_transition();
+    let t1 = net.create_transition();
+    let t2 = net.create_transition();
+    net.rename_node(NodeId::Transition(t1), "high").unwrap();
+    net.rename_node(NodeId::Transition(t2), "low").unwrap();
+    net.add_priority(t0, t1);
+    net.add_priority(t1, t2);
+    net.add_priority(t2, t1);
+
+    let graph = net.priority_graph();
+    assert_eq!(graph.find_cycle(), Some(vec![t1, t2]));
+    let error = net.update_priorities().unwrap_err();
+    assert_eq!(
+        error.downcast_ref::<NetError>(),
+        Some(&NetError::CyclicPriorities(vec![
+            "high".to_string(),
+            "low".to_string()
+        ]))
+    );
+    assert_eq!(
+        error.to_string(),
+        "Cyclic priorities in Petri net: high > low > high"
+    );
+}
+
+#[test]
+fn priority_reduction_test() {
+    let mut net = Net::default();
+    let t0 = net.create_transition();
+    let t1 = net.create_transition();
+    let t2 = net.create_transition();
+    let t3 = net.create_transition();
+    net.add_priority(t0, t1);
+    net.add_priority(t0, t2);
+    net.add_priority(t0, t3);
+    net.add_priority(t1, t3);
+    net.add_priority(t2, t3);
+
+    let graph = net.priority_graph();
+    assert!(graph.find_cycle().is_none());
+    let closure = graph.transitive_closure().unwrap();
+    assert!(closure.has_priority(t0, t3));
+    assert!(!closure.has_priority(t3, t0));
+    let reduction = graph.transitive_reduction().unwrap();
+    assert_eq!(reduction.priorities(t0), &[t1, t2]);
+    assert_eq!(reduction.priorities(t1), &[t3]);
+    assert_eq!(reduction.priorities(t2), &[t3]);
+    assert_eq!(reduction.edge_count(), 4);
+    assert_eq!(
+        reduction.transitive_closure().unwrap().edge_count(),
+        closure.edge_count()
+    );
+}
+
 #[test]
 fn node_transition_cast_test() {
     let tr = TransitionId::from(0);
@@ -164,3 +223,25 @@ fn node_place_cast_test() {
     assert_eq!(NodeId::Place(pl).as_transition(), None);
     assert_eq!(NodeId::Place(pl).as_place(), Some(PlaceId::from(0)));
 }
+
+#[test]
+fn reset_test() {
+    let mut net = Net::default();
+    let p = net.create_place();
+    let q = net.create_place();
+    let tr = net.create_transition();
+    net[p].initial = 3;
+    net[q].initial = 2;
+    net.add_arc(Kind::Consume(p, tr, 1)).unwrap();
+    net.add_arc(Kind::Reset(q, tr, 1)).unwrap();
+    net.add_arc(Kind::Produce(q, tr, 1)).unwrap();
+
+    // Reset places are emptied before the production
+    let marking = net[tr].fire(&net.initial_marking());
+    assert_eq!(marking[p], 2);
+    assert_eq!(marking[q], 1);
+    assert_eq!(net[tr].intermediate(&net.initial_marking())[q], 0);
+
+    net.delete_place(q);
+    assert!(net[tr].resets.is_empty());
+}
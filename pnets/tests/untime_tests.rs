@@ -0,0 +1,82 @@
+use pnets::arc::Kind;
+use pnets::timed::discrete::DiscreteSimulator;
+use pnets::timed::{Net, TimeRange};
+use pnets::{NetError, NodeId, TransitionId};
+
+/// `urgent` [0,2] and `late` [1,3] both have priority over `slow` [2,5], `watched` [0,1] has a
+/// stopwatch arc and priority over `late`
+fn net() -> (Net, [TransitionId; 4]) {
+    let mut net = Net::default();
+    let source = net.create_place();
+    let sink = net.create_place();
+    let guard = net.create_place();
+    let urgent = net.create_transition();
+    let late = net.create_transition();
+    let slow = net.create_transition();
+    let watched = net.create_transition();
+    net.rename_node(NodeId::Transition(urgent), "urgent")
+        .unwrap();
+    net[source].initial = 2;
+    net[guard].initial = 1;
+    net[urgent].time = "[0,2]".parse().unwrap();
+    net[late].time = "[1,3]".parse().unwrap();
+    net[slow].time = "[2,5]".parse().unwrap();
+    net[watched].time = "[0,1]".parse().unwrap();
+    for &tr in &[urgent, late, slow] {
+        net.add_arc(Kind::Consume(source, tr, 1)).unwrap();
+        net.add_arc(Kind::Produce(sink, tr, 1)).unwrap();
+    }
+    net.add_arc(Kind::Test(guard, urgent, 1)).unwrap();
+    net.add_arc(Kind::Inhibitor(sink, urgent, 1)).unwrap();
+    net.add_arc(Kind::Consume(sink, watched, 1)).unwrap();
+    net.add_arc(Kind::StopWatch(guard, watched, 1)).unwrap();
+    net.add_priority(urgent, slow);
+    net.add_priority(late, slow);
+    net.add_priority(watched, late);
+    (net, [urgent, late, slow, watched])
+}
+
+#[test]
+fn untime_test() {
+    let (net, [urgent, late, slow, watched]) = net();
+    let untimed = net.untimed().unwrap();
+    assert_eq!(untimed.places.len(), 3);
+    assert_eq!(untimed.transitions.len(), 4);
+    assert_eq!(
+        untimed.get_index_by_name("urgent"),
+        Some(NodeId::Transition(urgent))
+    );
+    for transition in untimed.transitions.iter() {
+        assert_eq!(transition.time, TimeRange::default());
+        assert!(transition.stopwatches.is_empty());
+    }
+    assert_eq!(untimed[urgent].conditions.len(), 1);
+    assert_eq!(untimed[urgent].inhibitors.len(), 1);
+    assert_eq!(untimed[watched].consume.len(), 1);
+    // Only `urgent` is firable as soon as it is enabled
+    assert_eq!(untimed[urgent].priorities, vec![slow]);
+    assert!(untimed[late].priorities.is_empty());
+    assert!(untimed[watched].priorities.is_empty());
+}
+
+#[test]
+fn over_approximation_test() {
+    let (net, _) = net();
+    let untimed = net.untimed().unwrap();
+    let mut timed = DiscreteSimulator::new(&net);
+    timed.run_eager(10);
+    assert!(!timed.history().is_empty());
+    // Every timed run can be replayed without time
+    let mut simulator = DiscreteSimulator::new(&untimed);
+    for &(tr, _) in timed.history() {
+        simulator.fire(tr).unwrap();
+    }
+    assert_eq!(simulator.state().marking, timed.state().marking);
+}
+
+#[test]
+fn cyclic_untime_test() {
+    let (mut net, [urgent, _, slow, _]) = net();
+    net.add_priority(slow, urgent);
+    assert!(matches!(net.untimed(), Err(NetError::CyclicPriorities(_))));
+}
@@ -0,0 +1,116 @@
+use pnets::arc::Kind;
+use pnets::timed::state_class::StateClassGraph;
+use pnets::timed::zone::{Mode, ZoneGraph};
+use pnets::timed::{Bound, Net, Time};
+use pnets::TransitionId;
+
+/// Task `a` [2,2] and task `b` [1,1] run concurrently, `b` is suspended while `a` runs if
+/// `preemptive` is true
+fn tasks(preemptive: bool) -> (Net, TransitionId, TransitionId) {
+    let mut net = Net::default();
+    let a_ready = net.create_place();
+    let b_ready = net.create_place();
+    let a_done = net.create_place();
+    let b_done = net.create_place();
+    let a = net.create_transition();
+    let b = net.create_transition();
+    net.rename_node(a.into(), "a").unwrap();
+    net.rename_node(b.into(), "b").unwrap();
+    net[a_ready].initial = 1;
+    net[b_ready].initial = 1;
+    net[a].time = "[2,2]".parse().unwrap();
+    net[b].time = "[1,1]".parse().unwrap();
+    net.add_arc(Kind::Consume(a_ready, a, 1)).unwrap();
+    net.add_arc(Kind::Produce(a_done, a, 1)).unwrap();
+    net.add_arc(Kind::Consume(b_ready, b, 1)).unwrap();
+    net.add_arc(Kind::Produce(b_done, b, 1)).unwrap();
+    if preemptive {
+        net.add_arc(Kind::StopWatchInhibitor(a_ready, b, 1))
+            .unwrap();
+    }
+    (net, a, b)
+}
+
+#[test]
+fn stopwatch_test() {
+    for &mode in &[Mode::StrongClasses, Mode::Zones] {
+        let (net, _, b) = tasks(false);
+        let graph = ZoneGraph::build(&net, mode, None).unwrap();
+        assert!(graph.complete);
+        assert_eq!(graph.zone_count(), 3);
+        assert_eq!(graph.edges[0].transition, b);
+        assert_eq!(graph.deadlocks(), vec![2]);
+
+        let (net, a, b) = tasks(true);
+        let graph = ZoneGraph::build(&net, mode, None).unwrap();
+        assert_eq!(graph.zone_count(), 3);
+        assert_eq!(graph.edge_count(), 2);
+        assert_eq!(graph.edges[0].transition, a);
+        assert_eq!(graph.edges[1].transition, b);
+        // The clock of b did not progress while a was running
+        let zone = &graph.zones[graph.edges[0].to];
+        let (start, _) = zone.clock_interval(b).unwrap();
+        assert_eq!(start, Bound::Closed(Time::from_integer(0)));
+        if mode == Mode::StrongClasses {
+            assert_eq!(
+                zone.clock_interval(b),
+                Some((
+                    Bound::Closed(Time::from_integer(0)),
+                    Bound::Closed(Time::from_integer(0))
+                ))
+            );
+        }
+    }
+}
+
+#[test]
+fn without_stopwatch_test() {
+    // Strong state classes and state classes give the same behaviour on nets without stopwatch
+    let mut net = Net::default();
+    let p0 = net.create_place();
+    let p1 = net.create_place();
+    let t0 = net.create_transition();
+    let t1 = net.create_transition();
+    net[p0].initial = 1;
+    net[t0].time = "[1,3]".parse().unwrap();
+    net[t1].time = "[2,4]".parse().unwrap();
+    net.add_arc(Kind::Consume(p0, t0, 1)).unwrap();
+    net.add_arc(Kind::Produce(p1, t0, 1)).unwrap();
+    net.add_arc(Kind::Consume(p0, t1, 1)).unwrap();
+    net.add_arc(Kind::Produce(p1, t1, 1)).unwrap();
+    let classes = StateClassGraph::build(&net, None).unwrap();
+    let strong = ZoneGraph::build(&net, Mode::StrongClasses, None).unwrap();
+    assert_eq!(classes.edge_count(), strong.edge_count());
+    assert_eq!(strong.zone_count(), 2);
+    let mut out = vec![];
+    strong.write_aut(&net, &mut out).unwrap();
+    assert!(String::from_utf8(out)
+        .unwrap()
+        .starts_with("des (0, 2, 2)\n"));
+}
+
+#[test]
+fn extrapolation_test() {
+    // loop fires every second forever while the clock of wait grows without bound
+    let mut net = Net::default();
+    let p0 = net.create_place();
+    let p1 = net.create_place();
+    let p2 = net.create_place();
+    let looping = net.create_transition();
+    let wait = net.create_transition();
+    net[p0].initial = 1;
+    net[p1].initial = 1;
+    net[looping].time = "[1,1]".parse().unwrap();
+    net[wait].time = "[5,w[".parse().unwrap();
+    net.add_arc(Kind::Consume(p0, looping, 1)).unwrap();
+    net.add_arc(Kind::Produce(p0, looping, 1)).unwrap();
+    net.add_arc(Kind::Consume(p1, wait, 1)).unwrap();
+    net.add_arc(Kind::Produce(p2, wait, 1)).unwrap();
+
+    for &mode in &[Mode::StrongClasses, Mode::Zones] {
+        let graph = ZoneGraph::build(&net, mode, Some(100)).unwrap();
+        assert!(graph.complete);
+        assert!(!graph.has_deadlock());
+        assert!(graph.edges.iter().any(|edge| edge.transition == wait));
+    }
+}
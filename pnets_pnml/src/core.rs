@@ -1,10 +1,11 @@
+use serde::ser::SerializeStruct;
 use serde::{Deserialize, Serialize, Serializer};
 use std::iter::FilterMap;
 use std::slice::Iter;
 
 pub type Decimal = f64;
 pub type Color = String;
-#[derive(Deserialize)]
+#[derive(Deserialize, Clone)]
 pub struct PositiveDecimal(pub f64);
 
 impl Serialize for PositiveDecimal {
@@ -41,21 +42,21 @@ pub struct SimpleText {
     pub text: String,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 #[serde(rename = "position")]
 pub struct Position {
     pub x: Decimal,
     pub y: Decimal,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 #[serde(rename = "offset")]
 pub struct Offset {
     pub x: Decimal,
     pub y: Decimal,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Clone)]
 pub enum Shape {
     #[serde(rename = "line")]
     Line,
@@ -75,7 +76,7 @@ impl Serialize for Shape {
     }
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 #[serde(rename = "line")]
 pub struct Line {
     pub shape: Option<Shape>,
@@ -83,7 +84,7 @@ pub struct Line {
     pub width: Option<PositiveDecimal>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Clone)]
 pub enum Rotation {
     #[serde(rename = "vertical")]
     Vertical,
@@ -106,7 +107,7 @@ impl Serialize for Rotation {
     }
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 #[serde(rename = "fill")]
 pub struct Fill {
     pub color: Option<Color>,
@@ -117,7 +118,7 @@ pub struct Fill {
     pub image: Option<String>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Clone)]
 pub enum Decoration {
     #[serde(rename = "underline")]
     Underline,
@@ -140,7 +141,7 @@ impl Serialize for Decoration {
     }
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Clone)]
 pub enum Align {
     #[serde(rename = "left")]
     Left,
@@ -163,7 +164,7 @@ impl Serialize for Align {
     }
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 #[serde(rename = "font")]
 pub struct Font {
     pub family: Option<String>,
@@ -175,7 +176,7 @@ pub struct Font {
     pub rotation: Option<Decimal>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 #[serde(rename = "graphics")]
 pub struct AnnotationGraphics {
     pub offset: Offset,
@@ -184,7 +185,7 @@ pub struct AnnotationGraphics {
     pub line: Option<Line>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 #[serde(rename = "graphics")]
 pub struct EdgeGraphics {
     #[serde(rename = "position", default)]
@@ -192,7 +193,7 @@ pub struct EdgeGraphics {
     line: Option<Line>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 #[serde(rename = "graphics")]
 pub struct NodeGraphics {
     pub position: Position,
@@ -201,7 +202,7 @@ pub struct NodeGraphics {
     pub line: Option<Line>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 #[serde(rename = "dimension")]
 pub struct Dimension {
     pub x: PositiveDecimal,
@@ -226,7 +227,7 @@ pub struct PlaceReference {
 }
 
 #[derive(Serialize, Deserialize)]
-#[serde(rename = "referencePlace")]
+#[serde(rename = "referenceTransition")]
 pub struct TransitionReference {
     pub id: String,
     #[serde(rename = "ref")]
@@ -235,13 +236,43 @@ pub struct TransitionReference {
     pub graphics: Option<NodeGraphics>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Deserialize)]
 #[serde(rename = "page")]
 pub struct Page<Place, Transition, Arc> {
     #[serde(rename = "$value")]
     pub(crate) items: Vec<PageItem<Place, Transition, Arc>>,
 }
 
+/// Items are written as elements named after their kind, without a wrapper for the variant
+impl<Place, Transition, Arc> Serialize for Page<Place, Transition, Arc>
+where
+    Place: Serialize,
+    Transition: Serialize,
+    Arc: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut page = serializer.serialize_struct("page", self.items.len())?;
+        for item in &self.items {
+            match item {
+                PageItem::Page(item) => page.serialize_field("page", item)?,
+                PageItem::Place(item) => page.serialize_field("place", item)?,
+                PageItem::Transition(item) => page.serialize_field("transition", item)?,
+                PageItem::PlaceReference(item) => page.serialize_field("referencePlace", item)?,
+                PageItem::TransitionReference(item) => {
+                    page.serialize_field("referenceTransition", item)?
+                }
+                PageItem::Arc(item) => page.serialize_field("arc", item)?,
+                PageItem::Name(item) => page.serialize_field("name", item)?,
+                PageItem::Toolspecific => {}
+            }
+        }
+        page.end()
+    }
+}
+
 impl<Place, Transition, Arc> Page<Place, Transition, Arc> {
     pub(crate) fn pages(
         &self,
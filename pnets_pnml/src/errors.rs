@@ -0,0 +1,22 @@
+use std::fmt::{Display, Formatter};
+use std::{error, fmt};
+
+/// Errors returned when a pnml net is converted into a net
+#[derive(Debug)]
+pub enum PnmlError {
+    /// This type of arc has no equivalent in nets, with the id of the arc and its type
+    /// (`transport` for example)
+    UnsupportedArcType(String, String),
+}
+
+impl Display for PnmlError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            PnmlError::UnsupportedArcType(id, type_) => {
+                write!(f, "Unsupported type {} of arc {}", type_, id)
+            }
+        }
+    }
+}
+
+impl error::Error for PnmlError {}
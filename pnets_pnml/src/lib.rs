@@ -1,3 +1,6 @@
+pub use errors::PnmlError;
+
 pub mod core;
+mod errors;
 pub mod pnml;
 pub mod ptnet;
@@ -2,7 +2,8 @@ pub use crate::core::{
     AnnotationGraphics, EdgeGraphics, Name, NodeGraphics, PlaceReference, TransitionReference,
 };
 use crate::core::{Net, NotNul, Page, PageItem, PositiveInteger, SimpleText};
-use pnets::{NetError, NodeId};
+use crate::PnmlError;
+use pnets::{standard, timed, Marking, NetError, NodeId, PlaceId, TransitionId};
 use serde::{Deserialize, Serialize};
 use std::convert::TryInto;
 use std::error::Error;
@@ -48,29 +49,271 @@ pub struct Arc {
     pub name: Option<Name>,
     pub graphics: Option<EdgeGraphics>,
     pub inscription: Option<ArcAnnotation>,
+    #[serde(rename = "type")]
+    pub type_: Option<ArcType>,
+}
+
+/// Type of an arc, an extension used by tools such as TAPAAL and ITS
+///
+/// `normal`, `read` (or `test`), `inhibitor` (or `tapnInhibitor`, the TAPAAL spelling) and
+/// `reset` arcs are supported. `stopwatch` and `stopwatch-inhibitor` are pnets-only types, no other tool
+/// reads them: they are only written for the stopwatch arcs of timed nets, so that pnets reads
+/// them back.
+#[derive(Serialize, Deserialize, Default)]
+#[serde(rename = "type")]
+pub struct ArcType {
+    pub value: String,
+}
+
+/// Graphics of a net read from a pnml file
+///
+/// [`Ptnet::nets_with_graphics`] keeps them so that [`Ptnet::from_nets_with_graphics`] can
+/// write them back, arcs are designated by their source and target.
+#[derive(Default, Clone)]
+pub struct Graphics {
+    /// Graphics of places and transitions
+    pub nodes: HashMap<NodeId, NodeGraphics>,
+    /// Graphics of the names of places and transitions
+    pub names: HashMap<NodeId, AnnotationGraphics>,
+    /// Graphics of initial markings
+    pub markings: HashMap<PlaceId, AnnotationGraphics>,
+    /// Graphics of arcs
+    pub arcs: HashMap<(NodeId, NodeId), EdgeGraphics>,
+    /// Graphics of arc inscriptions
+    pub inscriptions: HashMap<(NodeId, NodeId), AnnotationGraphics>,
+}
+
+/// Nets which are read from and written to pnml files
+///
+/// Standard nets are built directly, so reading a file does not build a timed net first.
+trait PnmlNet: Default {
+    fn name(&self) -> &str;
+    fn set_name(&mut self, name: String);
+    fn create_place(&mut self) -> PlaceId;
+    fn create_transition(&mut self) -> TransitionId;
+    fn get_index_by_name(&self, name: &str) -> Option<NodeId>;
+    fn get_name_by_index(&self, index: &NodeId) -> Option<String>;
+    fn rename_node(&mut self, id: NodeId, name: &str) -> Result<(), NetError>;
+    fn set_initial(&mut self, pl: PlaceId, initial: usize);
+    fn set_label(&mut self, node: NodeId, label: String);
+    fn add_arc(&mut self, arc: Kind) -> Result<(), Box<dyn Error>>;
+    /// Places with their initial marking and label
+    fn places(&self) -> Box<dyn Iterator<Item = (PlaceId, usize, &Option<String>)> + '_>;
+    /// Transitions with their label
+    fn transitions(&self) -> Box<dyn Iterator<Item = (TransitionId, &Option<String>)> + '_>;
+    /// Arcs of a transition, arcs to the transition come first
+    fn arcs(&self, tr: TransitionId) -> Box<dyn Iterator<Item = Kind> + '_>;
+}
+
+impl PnmlNet for standard::Net {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+
+    fn create_place(&mut self) -> PlaceId {
+        self.create_place()
+    }
+
+    fn create_transition(&mut self) -> TransitionId {
+        self.create_transition()
+    }
+
+    fn get_index_by_name(&self, name: &str) -> Option<NodeId> {
+        self.get_index_by_name(name)
+    }
+
+    fn get_name_by_index(&self, index: &NodeId) -> Option<String> {
+        self.get_name_by_index(index)
+    }
+
+    fn rename_node(&mut self, id: NodeId, name: &str) -> Result<(), NetError> {
+        self.rename_node(id, name)
+    }
+
+    fn set_initial(&mut self, pl: PlaceId, initial: usize) {
+        self[pl].initial = initial;
+    }
+
+    fn set_label(&mut self, node: NodeId, label: String) {
+        match node {
+            NodeId::Place(pl) => self[pl].label = Some(label),
+            NodeId::Transition(tr) => self[tr].label = Some(label),
+        }
+    }
+
+    /// Typed arcs are rejected with [`NetError::UnsupportedArc`]
+    fn add_arc(&mut self, arc: Kind) -> Result<(), Box<dyn Error>> {
+        Ok(self.add_arc(arc)?)
+    }
+
+    fn places(&self) -> Box<dyn Iterator<Item = (PlaceId, usize, &Option<String>)> + '_> {
+        Box::new(
+            self.places
+                .iter_enumerated()
+                .map(|(pl, place)| (pl, place.initial, &place.label)),
+        )
+    }
+
+    fn transitions(&self) -> Box<dyn Iterator<Item = (TransitionId, &Option<String>)> + '_> {
+        Box::new(
+            self.transitions
+                .iter_enumerated()
+                .map(|(tr, transition)| (tr, &transition.label)),
+        )
+    }
+
+    fn arcs(&self, tr: TransitionId) -> Box<dyn Iterator<Item = Kind> + '_> {
+        let transition = &self[tr];
+        Box::new(arcs(&transition.consume, tr, Kind::Consume).chain(arcs(
+            &transition.produce,
+            tr,
+            Kind::Produce,
+        )))
+    }
+}
+
+impl PnmlNet for timed::Net {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+
+    fn create_place(&mut self) -> PlaceId {
+        self.create_place()
+    }
+
+    fn create_transition(&mut self) -> TransitionId {
+        self.create_transition()
+    }
+
+    fn get_index_by_name(&self, name: &str) -> Option<NodeId> {
+        self.get_index_by_name(name)
+    }
+
+    fn get_name_by_index(&self, index: &NodeId) -> Option<String> {
+        self.get_name_by_index(index)
+    }
+
+    fn rename_node(&mut self, id: NodeId, name: &str) -> Result<(), NetError> {
+        self.rename_node(id, name)
+    }
+
+    fn set_initial(&mut self, pl: PlaceId, initial: usize) {
+        self[pl].initial = initial;
+    }
+
+    fn set_label(&mut self, node: NodeId, label: String) {
+        match node {
+            NodeId::Place(pl) => self[pl].label = Some(label),
+            NodeId::Transition(tr) => self[tr].label = Some(label),
+        }
+    }
+
+    fn add_arc(&mut self, arc: Kind) -> Result<(), Box<dyn Error>> {
+        self.add_arc(arc)
+    }
+
+    fn places(&self) -> Box<dyn Iterator<Item = (PlaceId, usize, &Option<String>)> + '_> {
+        Box::new(
+            self.places
+                .iter_enumerated()
+                .map(|(pl, place)| (pl, place.initial, &place.label)),
+        )
+    }
+
+    fn transitions(&self) -> Box<dyn Iterator<Item = (TransitionId, &Option<String>)> + '_> {
+        Box::new(
+            self.transitions
+                .iter_enumerated()
+                .map(|(tr, transition)| (tr, &transition.label)),
+        )
+    }
+
+    fn arcs(&self, tr: TransitionId) -> Box<dyn Iterator<Item = Kind> + '_> {
+        let transition = &self[tr];
+        Box::new(
+            arcs(&transition.consume, tr, Kind::Consume)
+                .chain(arcs(&transition.conditions, tr, Kind::Test))
+                .chain(arcs(&transition.inhibitors, tr, Kind::Inhibitor))
+                .chain(arcs(&transition.stopwatches, tr, Kind::StopWatch))
+                .chain(arcs(
+                    &transition.stopwatch_inhibitors,
+                    tr,
+                    Kind::StopWatchInhibitor,
+                ))
+                .chain(arcs(&transition.resets, tr, Kind::Reset))
+                .chain(arcs(&transition.produce, tr, Kind::Produce)),
+        )
+    }
+}
+
+/// Arcs of a transition stored in a marking
+fn arcs(
+    marking: &Marking<PlaceId>,
+    tr: TransitionId,
+    kind: fn(PlaceId, TransitionId, usize) -> Kind,
+) -> impl Iterator<Item = Kind> + '_ {
+    marking.iter().map(move |&(pl, w)| kind(pl, tr, w))
+}
+
+/// Follow references until a node of the net
+fn resolve<N: PnmlNet>(
+    net: &N,
+    reference_map: &HashMap<String, String>,
+    id: &str,
+) -> Result<NodeId, NetError> {
+    let mut id = id;
+    loop {
+        match net.get_index_by_name(id) {
+            Some(node) => return Ok(node),
+            None => match reference_map.get(id) {
+                Some(reference) if reference != id => id = reference,
+                _ => return Err(NetError::UnknownIdentifier(id.to_string())),
+            },
+        }
+    }
 }
 
 impl Page<Place, Transition, Arc> {
-    fn concat_places_transitions_to_net(
+    fn concat_places_transitions_to_net<N: PnmlNet>(
         &self,
-        net: &mut pnets::standard::Net,
+        net: &mut N,
         reference_map: &mut HashMap<String, String>,
+        graphics: &mut Graphics,
     ) -> Result<(), Box<dyn Error>> {
         for page in self.pages() {
-            page.concat_places_transitions_to_net(net, reference_map)?;
+            page.concat_places_transitions_to_net(net, reference_map, graphics)?;
         }
 
         for place in self.places() {
             let pl = net.create_place();
             net.rename_node(NodeId::Place(pl), &place.id)?;
             if let Some(marking) = &place.marking {
-                net[pl].initial = marking.positive.value;
+                net.set_initial(pl, marking.positive.value);
+                if let Some(marking) = &marking.graphics {
+                    graphics.markings.insert(pl, marking.clone());
+                }
             }
+            if let Some(name) = &place.name {
+                net.set_label(pl.into(), name.text.text.clone());
+            }
+            Self::node_graphics(graphics, pl.into(), &place.name, &place.graphics);
         }
 
         for transition in self.transitions() {
             let tr = net.create_transition();
             net.rename_node(NodeId::Transition(tr), &transition.id)?;
+            if let Some(name) = &transition.name {
+                net.set_label(tr.into(), name.text.text.clone());
+            }
+            Self::node_graphics(graphics, tr.into(), &transition.name, &transition.graphics);
         }
 
         for pl_ref in self.place_references() {
@@ -83,211 +326,214 @@ impl Page<Place, Transition, Arc> {
         Ok(())
     }
 
-    fn concat_arcs_to_net(
+    /// Keep the graphics of a node and of its name
+    fn node_graphics(
+        graphics: &mut Graphics,
+        node: NodeId,
+        name: &Option<Name>,
+        node_graphics: &Option<NodeGraphics>,
+    ) {
+        if let Some(node_graphics) = node_graphics {
+            graphics.nodes.insert(node, node_graphics.clone());
+        }
+        if let Some(Name {
+            graphics: Some(name),
+            ..
+        }) = name
+        {
+            graphics.names.insert(node, name.clone());
+        }
+    }
+
+    fn concat_arcs_to_net<N: PnmlNet>(
         &self,
-        net: &mut pnets::standard::Net,
+        net: &mut N,
         reference_map: &HashMap<String, String>,
-    ) -> Result<(), NetError> {
+        graphics: &mut Graphics,
+    ) -> Result<(), Box<dyn Error>> {
+        for page in self.pages() {
+            page.concat_arcs_to_net(net, reference_map, graphics)?;
+        }
+
         for arc in self.arcs() {
-            let source = {
-                let mut id = None;
-                let mut source = arc.source.clone();
-                while id.is_none() {
-                    id = match net.get_index_by_name(&arc.source) {
-                        None => {
-                            source = match reference_map.get(&source) {
-                                None => return Err(NetError::UnknownIdentifier(source)),
-                                Some(source) => source.clone(),
-                            };
-                            None
-                        }
-                        Some(id) => Some(id),
+            let source = resolve(net, reference_map, &arc.source)?;
+            let target = resolve(net, reference_map, &arc.target)?;
+            let weight = arc
+                .inscription
+                .as_ref()
+                .map_or(1, |inscription| inscription.value.value);
+            let type_ = arc.type_.as_ref().map_or("normal", |type_| &type_.value);
+            let kind = match (source, target) {
+                (NodeId::Place(pl), NodeId::Transition(tr)) => match type_ {
+                    "normal" => Kind::Consume(pl, tr, weight),
+                    "read" | "test" => Kind::Test(pl, tr, weight),
+                    "inhibitor" | "tapnInhibitor" => Kind::Inhibitor(pl, tr, weight),
+                    "stopwatch" => Kind::StopWatch(pl, tr, weight),
+                    "stopwatch-inhibitor" => Kind::StopWatchInhibitor(pl, tr, weight),
+                    "reset" => Kind::Reset(pl, tr, weight),
+                    _ => {
+                        return Err(Box::new(PnmlError::UnsupportedArcType(
+                            arc.id.clone(),
+                            type_.to_string(),
+                        )))
                     }
+                },
+                (NodeId::Transition(tr), NodeId::Place(pl)) if type_ == "normal" => {
+                    Kind::Produce(pl, tr, weight)
                 }
-                id
-            };
-            let target = {
-                let mut id = None;
-                let mut target = arc.target.clone();
-                while id.is_none() {
-                    id = match net.get_index_by_name(&arc.target) {
-                        None => {
-                            target = match reference_map.get(&target) {
-                                None => return Err(NetError::UnknownIdentifier(target)),
-                                Some(target) => target.clone(),
-                            };
-                            None
-                        }
-                        Some(id) => Some(id),
-                    }
+                (NodeId::Transition(_), NodeId::Place(_)) => {
+                    return Err(Box::new(PnmlError::UnsupportedArcType(
+                        arc.id.clone(),
+                        type_.to_string(),
+                    )))
                 }
-                id
+                _ => return Err(Box::new(NetError::InvalidArc)),
             };
-            match (source, target) {
-                (Some(NodeId::Place(pl)), Some(NodeId::Transition(tr))) => {
-                    net.add_arc(Kind::Consume(
-                        pl,
-                        tr,
-                        arc.inscription
-                            .as_ref()
-                            .unwrap_or(&ArcAnnotation {
-                                value: NotNul { value: 1 },
-                                ..ArcAnnotation::default()
-                            })
-                            .value
-                            .value,
-                    ))?;
-                }
-                (Some(NodeId::Transition(tr)), Some(NodeId::Place(pl))) => {
-                    net.add_arc(Kind::Produce(
-                        pl,
-                        tr,
-                        arc.inscription
-                            .as_ref()
-                            .unwrap_or(&ArcAnnotation {
-                                value: NotNul { value: 1 },
-                                ..ArcAnnotation::default()
-                            })
-                            .value
-                            .value,
-                    ))?;
-                }
-                _ => return Err(NetError::InvalidArc),
+            net.add_arc(kind)?;
+            if let Some(edge) = &arc.graphics {
+                graphics.arcs.insert((source, target), edge.clone());
+            }
+            if let Some(ArcAnnotation {
+                graphics: Some(inscription),
+                ..
+            }) = &arc.inscription
+            {
+                graphics
+                    .inscriptions
+                    .insert((source, target), inscription.clone());
             }
         }
         Ok(())
     }
 }
 
-impl TryInto<pnets::standard::Net> for &crate::core::Net<Place, Transition, Arc> {
-    type Error = Box<dyn Error>;
-
-    fn try_into(self) -> Result<pnets::standard::Net, Self::Error> {
-        let mut net = pnets::standard::Net::default();
+impl crate::core::Net<Place, Transition, Arc> {
+    /// Convert the net and keep its graphics
+    fn net_with_graphics<N: PnmlNet>(&self) -> Result<(N, Graphics), Box<dyn Error>> {
+        let mut net = N::default();
         if let Some(name) = &self.name {
-            net.name = name.text.text.clone();
+            net.set_name(name.text.text.clone());
         }
         let mut reference_map: HashMap<String, String> = HashMap::default();
+        let mut graphics = Graphics::default();
         for page in &self.pages {
-            page.concat_places_transitions_to_net(&mut net, &mut reference_map)?;
+            page.concat_places_transitions_to_net(&mut net, &mut reference_map, &mut graphics)?;
         }
         for page in &self.pages {
-            page.concat_arcs_to_net(&mut net, &reference_map)?;
+            page.concat_arcs_to_net(&mut net, &reference_map, &mut graphics)?;
         }
-        Ok(net)
+        Ok((net, graphics))
     }
 }
 
-impl TryInto<Vec<pnets::standard::Net>> for &Ptnet {
-    type Error = Box<dyn Error>;
-
-    fn try_into(self) -> Result<Vec<pnets::standard::Net>, Self::Error> {
-        let mut nets = vec![];
+impl Ptnet {
+    /// Convert the nets of the file into timed nets and keep their graphics
+    ///
+    /// Names of places and transitions become their labels. Typed arcs become test, inhibitor,
+    /// reset and stopwatch arcs, other types such as `transport` are rejected with
+    /// [`PnmlError::UnsupportedArcType`].
+    pub fn timed_nets_with_graphics(&self) -> Result<Vec<(timed::Net, Graphics)>, Box<dyn Error>> {
+        self.nets.iter().map(Net::net_with_graphics).collect()
+    }
 
-        for net in &self.nets {
-            nets.push(net.try_into()?);
-        }
-        Ok(nets)
+    /// Convert the nets of the file and keep their graphics
+    ///
+    /// Names of places and transitions become their labels. Typed arcs are rejected with
+    /// [`NetError::UnsupportedArc`], use [`Ptnet::timed_nets_with_graphics`] to read them.
+    pub fn nets_with_graphics(&self) -> Result<Vec<(standard::Net, Graphics)>, Box<dyn Error>> {
+        self.nets.iter().map(Net::net_with_graphics).collect()
     }
-}
 
-impl From<&Vec<pnets::standard::Net>> for Ptnet {
-    fn from(nets: &Vec<pnets::standard::Net>) -> Self {
+    /// Write nets with their graphics, the `i`-th graphics are the graphics of the `i`-th net
+    fn from_pnml_nets<N: PnmlNet>(nets: &[N], graphics: &[Graphics]) -> Self {
         let mut pnml = Ptnet {
             xmlns: "http://www.pnml.org/version-2009/grammar/pnml".to_string(),
             nets: vec![],
         };
+        let no_graphics = Graphics::default();
         let mut net_count = 0;
         let mut arc_count = 0;
-        for net in nets {
+        for (index, net) in nets.iter().enumerate() {
+            let graphics = graphics.get(index).unwrap_or(&no_graphics);
             net_count += 1;
             let mut new_net = Net::<Place, Transition, Arc> {
                 type_: "http://www.pnml.org/version-2009/grammar/ptnet".to_string(),
                 ..Net::default()
             };
-            if net.name.is_empty() {
+            if net.name().is_empty() {
                 new_net.id = format!("net-auto-{}", net_count);
             } else {
-                new_net.id = net.name.clone();
+                new_net.id = net.name().to_string();
                 new_net.name = Some(Name {
                     text: SimpleText {
-                        text: net.name.clone(),
+                        text: net.name().to_string(),
                     },
                     graphics: None,
                 })
             }
+            let name = |node: NodeId, label: &Option<String>| {
+                label.as_ref().map(|label| Name {
+                    text: SimpleText {
+                        text: label.clone(),
+                    },
+                    graphics: graphics.names.get(&node).cloned(),
+                })
+            };
 
             let mut page = Page::<Place, Transition, Arc> { items: vec![] };
 
-            for (pl, place) in net.places.iter_enumerated() {
-                let mut new_place = Place {
+            for (pl, initial, label) in net.places() {
+                let new_place = Place {
                     id: net.get_name_by_index(&pl.into()).unwrap(),
-                    ..Place::default()
+                    name: name(pl.into(), label),
+                    graphics: graphics.nodes.get(&pl.into()).cloned(),
+                    marking: if initial == 0 {
+                        None
+                    } else {
+                        Some(PTMarking {
+                            positive: PositiveInteger { value: initial },
+                            graphics: graphics.markings.get(&pl).cloned(),
+                        })
+                    },
                 };
-                if place.label.is_some() {
-                    {
-                        new_place.name = Some(Name {
-                            text: SimpleText {
-                                text: place.label.as_ref().unwrap().clone(),
-                            },
-                            graphics: None,
-                        });
-                    }
-                }
                 page.items.push(PageItem::Place(new_place));
             }
-            for (tr, transition) in net.transitions.iter_enumerated() {
-                let mut new_transition = Transition {
+            for (tr, label) in net.transitions() {
+                let new_transition = Transition {
                     id: net.get_name_by_index(&tr.into()).unwrap(),
-                    ..Transition::default()
+                    name: name(tr.into(), label),
+                    graphics: graphics.nodes.get(&tr.into()).cloned(),
                 };
-                if transition.label.is_some() {
-                    {
-                        new_transition.name = Some(Name {
-                            text: SimpleText {
-                                text: transition.label.as_ref().unwrap().clone(),
-                            },
-                            graphics: None,
-                        });
-                    }
-                }
-                for &(pl, w) in transition.consume.iter() {
+                for arc in net.arcs(tr) {
+                    let (source, target, w, type_) = match arc {
+                        Kind::Consume(pl, tr, w) => (pl.into(), tr.into(), w, None),
+                        Kind::Produce(pl, tr, w) => (tr.into(), pl.into(), w, None),
+                        Kind::Test(pl, tr, w) => (pl.into(), tr.into(), w, Some("read")),
+                        Kind::Inhibitor(pl, tr, w) => (pl.into(), tr.into(), w, Some("inhibitor")),
+                        Kind::StopWatch(pl, tr, w) => (pl.into(), tr.into(), w, Some("stopwatch")),
+                        Kind::StopWatchInhibitor(pl, tr, w) => {
+                            (pl.into(), tr.into(), w, Some("stopwatch-inhibitor"))
+                        }
+                        Kind::Reset(pl, tr, w) => (pl.into(), tr.into(), w, Some("reset")),
+                    };
                     arc_count += 1;
+                    let source_name = net.get_name_by_index(&source).unwrap();
+                    let target_name = net.get_name_by_index(&target).unwrap();
                     page.items.push(PageItem::Arc(Arc {
                         id: format!(
                             "{}-arcs-{}-{}-{}",
-                            new_net.id,
-                            net.get_name_by_index(&(pl.into())).unwrap(),
-                            net.get_name_by_index(&tr.into()).unwrap(),
-                            arc_count
+                            new_net.id, source_name, target_name, arc_count
                         ),
-                        source: net.get_name_by_index(&(pl.into())).unwrap(),
-                        target: net.get_name_by_index(&tr.into()).unwrap(),
+                        source: source_name,
+                        target: target_name,
                         name: None,
-                        graphics: None,
+                        graphics: graphics.arcs.get(&(source, target)).cloned(),
                         inscription: Some(ArcAnnotation {
                             value: NotNul { value: w },
-                            graphics: None,
+                            graphics: graphics.inscriptions.get(&(source, target)).cloned(),
                         }),
-                    }))
-                }
-                for &(pl, w) in transition.produce.iter() {
-                    arc_count += 1;
-                    page.items.push(PageItem::Arc(Arc {
-                        id: format!(
-                            "{}-arcs-{}-{}-{}",
-                            new_net.id,
-                            net.get_name_by_index(&tr.into()).unwrap(),
-                            net.get_name_by_index(&(pl.into())).unwrap(),
-                            arc_count
-                        ),
-                        target: net.get_name_by_index(&(pl.into())).unwrap(),
-                        source: net.get_name_by_index(&tr.into()).unwrap(),
-                        name: None,
-                        graphics: None,
-                        inscription: Some(ArcAnnotation {
-                            value: NotNul { value: w },
-                            graphics: None,
+                        type_: type_.map(|type_| ArcType {
+                            value: type_.to_string(),
                         }),
                     }))
                 }
@@ -298,4 +544,74 @@ impl From<&Vec<pnets::standard::Net>> for Ptnet {
         }
         pnml
     }
+
+    /// Write timed nets with their graphics, the `i`-th graphics are the graphics of the `i`-th
+    /// net
+    ///
+    /// Labels are written as names, nets without graphics are written without positions. Time
+    /// ranges and priorities have no equivalent in pnml and are not written.
+    pub fn from_timed_nets_with_graphics(nets: &[timed::Net], graphics: &[Graphics]) -> Self {
+        Self::from_pnml_nets(nets, graphics)
+    }
+
+    /// Write nets with their graphics, the `i`-th graphics are the graphics of the `i`-th net
+    ///
+    /// Labels are written as names, nets without graphics are written without positions.
+    pub fn from_nets_with_graphics(nets: &[standard::Net], graphics: &[Graphics]) -> Self {
+        Self::from_pnml_nets(nets, graphics)
+    }
+}
+
+impl TryInto<standard::Net> for &crate::core::Net<Place, Transition, Arc> {
+    type Error = Box<dyn Error>;
+
+    fn try_into(self) -> Result<standard::Net, Self::Error> {
+        Ok(self.net_with_graphics()?.0)
+    }
+}
+
+impl TryInto<timed::Net> for &crate::core::Net<Place, Transition, Arc> {
+    type Error = Box<dyn Error>;
+
+    fn try_into(self) -> Result<timed::Net, Self::Error> {
+        Ok(self.net_with_graphics()?.0)
+    }
+}
+
+impl TryInto<Vec<standard::Net>> for &Ptnet {
+    type Error = Box<dyn Error>;
+
+    fn try_into(self) -> Result<Vec<standard::Net>, Self::Error> {
+        let mut nets = vec![];
+
+        for net in &self.nets {
+            nets.push(net.try_into()?);
+        }
+        Ok(nets)
+    }
+}
+
+impl TryInto<Vec<timed::Net>> for &Ptnet {
+    type Error = Box<dyn Error>;
+
+    fn try_into(self) -> Result<Vec<timed::Net>, Self::Error> {
+        let mut nets = vec![];
+
+        for net in &self.nets {
+            nets.push(net.try_into()?);
+        }
+        Ok(nets)
+    }
+}
+
+impl From<&Vec<standard::Net>> for Ptnet {
+    fn from(nets: &Vec<standard::Net>) -> Self {
+        Ptnet::from_nets_with_graphics(nets, &[])
+    }
+}
+
+impl From<&Vec<timed::Net>> for Ptnet {
+    fn from(nets: &Vec<timed::Net>) -> Self {
+        Ptnet::from_timed_nets_with_graphics(nets, &[])
+    }
 }
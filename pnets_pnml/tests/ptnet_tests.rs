@@ -0,0 +1,181 @@
+use pnets::arc::Kind;
+use pnets::{standard, timed, NetError, NodeId};
+use pnets_pnml::ptnet::Ptnet;
+use pnets_pnml::PnmlError;
+use std::convert::TryInto;
+
+const PTNET: &str = r#"<?xml version="1.0"?>
+<pnml xmlns="http://www.pnml.org/version-2009/grammar/pnml">
+  <net id="demo" type="http://www.pnml.org/version-2009/grammar/ptnet">
+    <name><text>demo</text></name>
+    <page id="page0">
+      <place id="p0">
+        <name><text>ready</text><graphics><offset x="-10" y="5"/></graphics></name>
+        <graphics><position x="20" y="30"/></graphics>
+        <initialMarking><text>3</text><graphics><offset x="1" y="2"/></graphics></initialMarking>
+      </place>
+      <transition id="t0">
+        <name><text>go</text></name>
+        <graphics><position x="60" y="30"/></graphics>
+      </transition>
+      <arc id="a0" source="p0" target="t0">
+        <graphics><position x="40" y="10"/></graphics>
+        <inscription><text>2</text></inscription>
+      </arc>
+      <page id="page1">
+        <place id="p1"/>
+        <referencePlace id="r1" ref="p1"/>
+      </page>
+      <arc id="a1" source="t0" target="r1"/>
+    </page>
+  </net>
+</pnml>"#;
+
+fn write(nets: &[standard::Net], graphics: &[pnets_pnml::ptnet::Graphics]) -> String {
+    quick_xml::se::to_string(&Ptnet::from_nets_with_graphics(nets, graphics)).unwrap()
+}
+
+#[test]
+fn import_test() {
+    let ptnet: Ptnet = quick_xml::de::from_str(PTNET).unwrap();
+    let nets: Vec<standard::Net> = (&ptnet).try_into().unwrap();
+    let net = &nets[0];
+    let (p0, p1, t0) = match (
+        net.get_index_by_name("p0"),
+        net.get_index_by_name("p1"),
+        net.get_index_by_name("t0"),
+    ) {
+        (Some(NodeId::Place(p0)), Some(NodeId::Place(p1)), Some(NodeId::Transition(t0))) => {
+            (p0, p1, t0)
+        }
+        _ => panic!("p0, p1 and t0 must be nodes of the net"),
+    };
+    assert_eq!(net[p0].initial, 3);
+    assert_eq!(net[p0].label.as_deref(), Some("ready"));
+    assert_eq!(net[t0].label.as_deref(), Some("go"));
+    assert_eq!(net[t0].consume[p0], 2);
+    // The arc to the reference place produces in the referenced place
+    assert_eq!(net[t0].produce[p1], 1);
+}
+
+#[test]
+fn round_trip_test() {
+    let ptnet: Ptnet = quick_xml::de::from_str(PTNET).unwrap();
+    let (nets, graphics): (Vec<_>, Vec<_>) =
+        ptnet.nets_with_graphics().unwrap().into_iter().unzip();
+    assert_eq!(graphics[0].nodes.len(), 2);
+    assert_eq!(graphics[0].arcs.len(), 1);
+    let written = write(&nets, &graphics);
+    assert!(written.contains(
+        "<initialMarking><text>3</text><graphics><offset x=\"1\" y=\"2\"/></graphics></initialMarking>"
+    ));
+    assert!(written.contains("<name><text>go</text></name>"));
+    assert!(written.contains("<graphics><position x=\"20\" y=\"30\"/></graphics>"));
+
+    // Reading the written file gives the same nets and graphics
+    let ptnet: Ptnet = quick_xml::de::from_str(&written).unwrap();
+    let (nets, graphics): (Vec<_>, Vec<_>) =
+        ptnet.nets_with_graphics().unwrap().into_iter().unzip();
+    assert_eq!(write(&nets, &graphics), written);
+}
+
+#[test]
+fn marking_test() {
+    let mut net = standard::Net::default();
+    let pl = net.create_place();
+    net[pl].initial = 4;
+    let written = quick_xml::se::to_string(&Ptnet::from(&vec![net])).unwrap();
+    assert!(written.contains("<initialMarking><text>4</text></initialMarking>"));
+}
+
+fn typed_arcs(type_: &str) -> String {
+    format!(
+        r#"<?xml version="1.0"?>
+<pnml xmlns="http://www.pnml.org/version-2009/grammar/pnml">
+  <net id="typed" type="http://www.pnml.org/version-2009/grammar/ptnet">
+    <page id="page0">
+      <place id="p0"/>
+      <place id="p1"/>
+      <transition id="t0"/>
+      <arc id="a0" source="p0" target="t0"><type value="read"/></arc>
+      <arc id="a1" source="p1" target="t0">
+        <inscription><text>2</text></inscription>
+        <type value="{}"/>
+      </arc>
+      <arc id="a2" source="t0" target="p0"><type value="normal"/></arc>
+    </page>
+  </net>
+</pnml>"#,
+        type_
+    )
+}
+
+#[test]
+fn typed_arcs_test() {
+    let ptnet: Ptnet = quick_xml::de::from_str(&typed_arcs("inhibitor")).unwrap();
+    let nets: Vec<timed::Net> = (&ptnet).try_into().unwrap();
+    let net = &nets[0];
+    let (p0, p1, t0) = match (
+        net.get_index_by_name("p0"),
+        net.get_index_by_name("p1"),
+        net.get_index_by_name("t0"),
+    ) {
+        (Some(NodeId::Place(p0)), Some(NodeId::Place(p1)), Some(NodeId::Transition(t0))) => {
+            (p0, p1, t0)
+        }
+        _ => panic!("p0, p1 and t0 must be nodes of the net"),
+    };
+    assert_eq!(net[t0].conditions[p0], 1);
+    assert_eq!(net[t0].inhibitors[p1], 2);
+    assert_eq!(net[t0].produce[p0], 1);
+    assert!(net[t0].consume.is_empty());
+
+    // Typed arcs are written back
+    let written = quick_xml::se::to_string(&Ptnet::from(&nets)).unwrap();
+    assert!(written.contains("<type value=\"inhibitor\"/>"));
+    assert!(written.contains("<type value=\"read\"/>"));
+    let ptnet: Ptnet = quick_xml::de::from_str(&written).unwrap();
+    let parsed: Vec<timed::Net> = (&ptnet).try_into().unwrap();
+    assert_eq!(
+        quick_xml::se::to_string(&Ptnet::from(&parsed)).unwrap(),
+        written
+    );
+
+    // TAPAAL spelling of inhibitor arcs
+    let ptnet: Ptnet = quick_xml::de::from_str(&typed_arcs("tapnInhibitor")).unwrap();
+    let nets: Vec<timed::Net> = (&ptnet).try_into().unwrap();
+    assert_eq!(nets[0][t0].inhibitors[p1], 2);
+
+    // Reset arcs are kept in timed nets
+    let ptnet: Ptnet = quick_xml::de::from_str(&typed_arcs("reset")).unwrap();
+    let nets: Vec<timed::Net> = (&ptnet).try_into().unwrap();
+    assert_eq!(nets[0][t0].resets[p1], 2);
+    let written = quick_xml::se::to_string(&Ptnet::from(&nets)).unwrap();
+    assert!(written.contains("<type value=\"reset\"/>"));
+}
+
+#[test]
+fn unsupported_arcs_test() {
+    // Standard nets have no inhibitor arcs
+    let ptnet: Ptnet = quick_xml::de::from_str(&typed_arcs("inhibitor")).unwrap();
+    let error = TryInto::<Vec<standard::Net>>::try_into(&ptnet).unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<NetError>(),
+        Some(NetError::UnsupportedArc(_))
+    ));
+    // nor reset arcs
+    let reset = typed_arcs("reset").replace("<type value=\"read\"/>", "");
+    let ptnet: Ptnet = quick_xml::de::from_str(&reset).unwrap();
+    let error = TryInto::<Vec<standard::Net>>::try_into(&ptnet).unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<NetError>(),
+        Some(NetError::UnsupportedArc(Kind::Reset(..)))
+    ));
+
+    let ptnet: Ptnet = quick_xml::de::from_str(&typed_arcs("transport")).unwrap();
+    let error = TryInto::<Vec<timed::Net>>::try_into(&ptnet).unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<PnmlError>(),
+        Some(PnmlError::UnsupportedArcType(id, type_)) if id == "a1" && type_ == "transport"
+    ));
+}
@@ -0,0 +1,163 @@
+use std::error::Error;
+use std::io::Write;
+
+use pnets::arc;
+use pnets::timed::{Bound, Net};
+use pnets::{NetError, NodeId, PlaceId, TransitionId};
+
+/// Exporter for the XML format of [Romeo](https://romeo.rts-software.org)
+///
+/// It consume a net ([`pnets::timed::Net`]) and it write its representation in the writer.
+/// Nodes are labelled with their name, and are placed on a grid: places on the first row and
+/// transitions on the second one.
+pub struct Exporter<'w> {
+    writer: &'w mut dyn Write,
+}
+
+/// Escape XML special characters in attributes
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+impl<'w> Exporter<'w> {
+    /// Create a new exporter which writes in `writer`
+    pub fn new(writer: &'w mut dyn Write) -> Self {
+        Self { writer }
+    }
+
+    /// Write the graphics of a node
+    fn graphics(&mut self, x: usize, y: usize) -> Result<(), Box<dyn Error>> {
+        self.writer.write_all(
+            format!(
+                "    <graphics color=\"0\">\n      <position x=\"{}\" y=\"{}\"/>\n      \
+                 <deltaLabel deltax=\"10\" deltay=\"10\"/>\n    </graphics>\n",
+                x, y
+            )
+            .as_ref(),
+        )?;
+        Ok(())
+    }
+
+    /// Write an arc
+    fn arc(
+        &mut self,
+        place: PlaceId,
+        transition: TransitionId,
+        kind: &str,
+        weight: usize,
+    ) -> Result<(), Box<dyn Error>> {
+        self.writer.write_all(
+            format!(
+                "  <arc place=\"{}\" transition=\"{}\" type=\"{}\" weight=\"{}\">\n    \
+                 <nail xnail=\"0\" ynail=\"0\"/>\n    <graphics color=\"0\"></graphics>\n  </arc>\n",
+                place, transition, kind, weight
+            )
+            .as_ref(),
+        )?;
+        Ok(())
+    }
+
+    /// Export a timed net
+    ///
+    /// # Errors
+    /// Return [`NetError::UnsupportedArc`] if the net has stopwatch arcs, Romeo only suspends
+    /// clocks with inhibitor arcs, and [`NetError::InvalidTimeRange`] if a time range has an
+    /// open or infinite start or an open end, Romeo intervals are closed
+    pub fn export(&mut self, net: &Net) -> Result<(), Box<dyn Error>> {
+        for transition in net.transitions.iter() {
+            if let Some(&(pl, w)) = transition.stopwatches.iter().next() {
+                return Err(Box::new(NetError::UnsupportedArc(arc::Kind::StopWatch(
+                    pl,
+                    transition.id(),
+                    w,
+                ))));
+            }
+        }
+        self.writer.write_all(
+            format!(
+                "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<TPN name=\"{}\">\n",
+                escape(&net.name)
+            )
+            .as_ref(),
+        )?;
+        for (i, (pl, place)) in net.places.iter_enumerated().enumerate() {
+            self.writer.write_all(
+                format!(
+                    "  <place id=\"{}\" label=\"{}\" initialMarking=\"{}\">\n",
+                    pl,
+                    escape(&net.get_name_by_index(&NodeId::Place(pl)).unwrap()),
+                    place.initial
+                )
+                .as_ref(),
+            )?;
+            self.graphics(100 * (i + 1), 100)?;
+            self.writer
+                .write_all("    <scheduling gamma=\"0\" omega=\"0\"/>\n  </place>\n".as_ref())?;
+        }
+
+        for (i, (tr, transition)) in net.transitions.iter_enumerated().enumerate() {
+            let eft = match transition.time.start {
+                Bound::Closed(a) => a,
+                _ => return Err(Box::new(NetError::InvalidTimeRange)),
+            };
+            let lft = match transition.time.end {
+                Bound::Closed(b) => b.to_string(),
+                Bound::Infinity => "inf".to_string(),
+                Bound::Open(_) => return Err(Box::new(NetError::InvalidTimeRange)),
+            };
+            self.writer.write_all(
+                format!(
+                    "  <transition id=\"{}\" label=\"{}\" eft=\"{}\" lft=\"{}\" speed=\"1\" \
+                     cost=\"0\" unctrl=\"0\" obs=\"1\" guard=\"\" update=\"\">\n",
+                    tr,
+                    escape(&net.get_name_by_index(&NodeId::Transition(tr)).unwrap()),
+                    eft,
+                    lft
+                )
+                .as_ref(),
+            )?;
+            self.graphics(100 * (i + 1), 300)?;
+            self.writer.write_all("  </transition>\n".as_ref())?;
+        }
+
+        for (tr, transition) in net.transitions.iter_enumerated() {
+            for &(pl, w) in transition.consume.iter() {
+                self.arc(pl, tr, "PlaceTransition", w)?;
+            }
+            for &(pl, w) in transition.produce.iter() {
+                self.arc(pl, tr, "TransitionPlace", w)?;
+            }
+            for &(pl, w) in transition.conditions.iter() {
+                self.arc(pl, tr, "read", w)?;
+            }
+            for &(pl, w) in transition.inhibitors.iter() {
+                self.arc(pl, tr, "logicalInhibitor", w)?;
+            }
+            for &(pl, w) in transition.stopwatch_inhibitors.iter() {
+                self.arc(pl, tr, "inhibitor", w)?;
+            }
+            for &(pl, w) in transition.resets.iter() {
+                self.arc(pl, tr, "flush", w)?;
+            }
+        }
+
+        // Only the priorities which are not implied by transitivity are written
+        let priorities = net.priority_graph().transitive_reduction()?;
+        for (tr, _) in net.transitions.iter_enumerated() {
+            for &less in priorities.priorities(tr) {
+                self.writer.write_all(
+                    format!(
+                        "  <priority priorityMore=\"{}\" priorityLess=\"{}\"/>\n",
+                        tr, less
+                    )
+                    .as_ref(),
+                )?;
+            }
+        }
+        self.writer.write_all("</TPN>\n".as_ref())?;
+        Ok(())
+    }
+}
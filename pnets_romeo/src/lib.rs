@@ -0,0 +1,20 @@
+#![forbid(missing_docs)]
+//! This crate provides parser and writer of the XML format of
+//! [Romeo](https://romeo.rts-software.org) time Petri nets for
+//! [pnets](https://crates.io/crates/pnets) framework.
+//!
+//! Arcs are mapped to Romeo arc types as follows:
+//! - consume arcs are `PlaceTransition` arcs and produce arcs are `TransitionPlace` arcs
+//! - test arcs are `read` arcs
+//! - inhibitor arcs are `logicalInhibitor` arcs
+//! - stopwatch inhibitor arcs are `inhibitor` arcs, which suspend the clock of the transition
+//! - reset arcs are `flush` arcs
+//!
+//! Stopwatch arcs have no Romeo equivalent and are rejected by the exporter.
+
+pub use export::Exporter;
+pub use parser::Parser;
+
+mod export;
+mod model;
+mod parser;
@@ -0,0 +1,77 @@
+//! Elements of a Romeo file, only the attributes used by pnets are read
+use serde::Deserialize;
+
+/// Element whose content is not used
+#[derive(Deserialize, Default)]
+pub(crate) struct Ignored {}
+
+#[derive(Deserialize)]
+#[serde(rename = "place")]
+pub(crate) struct Place {
+    pub id: usize,
+    #[serde(default)]
+    pub label: String,
+    #[serde(rename = "initialMarking", default)]
+    pub initial: usize,
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "transition")]
+pub(crate) struct Transition {
+    pub id: usize,
+    #[serde(default)]
+    pub label: String,
+    /// Earliest firing time
+    pub eft: Option<String>,
+    /// Latest firing time, `inf` if unbounded
+    pub lft: Option<String>,
+}
+
+fn one() -> usize {
+    1
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "arc")]
+pub(crate) struct Arc {
+    pub place: usize,
+    pub transition: usize,
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(default = "one")]
+    pub weight: usize,
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "priority")]
+pub(crate) struct Priority {
+    /// Transition with priority
+    #[serde(rename = "priorityMore")]
+    pub more: usize,
+    /// Transition over which `more` has priority
+    #[serde(rename = "priorityLess")]
+    pub less: usize,
+}
+
+#[derive(Deserialize)]
+pub(crate) enum Item {
+    #[serde(rename = "place")]
+    Place(Place),
+    #[serde(rename = "transition")]
+    Transition(Transition),
+    #[serde(rename = "arc")]
+    Arc(Arc),
+    #[serde(rename = "priority")]
+    Priority(Priority),
+    #[serde(rename = "preferences")]
+    Preferences(Ignored),
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "TPN")]
+pub(crate) struct Tpn {
+    #[serde(default)]
+    pub name: String,
+    #[serde(rename = "$value", default)]
+    pub items: Vec<Item>,
+}
@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::io::Read;
+
+use pnets::timed::{Net, TimeRange};
+use pnets::{arc, NetError, NodeId, PlaceId, TransitionId};
+
+use crate::model::{Item, Tpn};
+
+/// Parser for the XML format of [Romeo](https://romeo.rts-software.org)
+///
+/// It consume a reader and creates a [`pnets::timed::Net`]. Nodes are named after their Romeo
+/// label, or automatically when they have none.
+pub struct Parser<R: Read> {
+    reader: R,
+}
+
+impl<R: Read> Parser<R> {
+    /// Create a new parser from reader
+    pub fn new(reader: R) -> Self {
+        Self { reader }
+    }
+
+    /// Parse a timed net from a reader
+    pub fn parse(self) -> Result<Net, Box<dyn Error>> {
+        let tpn: Tpn = serde_xml_rs::from_reader(self.reader)?;
+        let mut net = Net::default();
+        net.name = tpn.name;
+        let mut places: HashMap<usize, PlaceId> = HashMap::new();
+        let mut transitions: HashMap<usize, TransitionId> = HashMap::new();
+
+        // Arcs and priorities can reference nodes defined after them
+        for item in &tpn.items {
+            match item {
+                Item::Place(place) => {
+                    let pl = net.create_place();
+                    if !place.label.is_empty() {
+                        net.rename_node(NodeId::Place(pl), &place.label)?;
+                    }
+                    net[pl].initial = place.initial;
+                    if places.insert(place.id, pl).is_some() {
+                        return Err(Box::new(NetError::DuplicatedName(place.id.to_string())));
+                    }
+                }
+                Item::Transition(transition) => {
+                    let tr = net.create_transition();
+                    if !transition.label.is_empty() {
+                        net.rename_node(NodeId::Transition(tr), &transition.label)?;
+                    }
+                    let eft = transition.eft.as_deref().unwrap_or("0");
+                    net[tr].time = match transition.lft.as_deref() {
+                        None | Some("inf") | Some("") => format!("[{},w[", eft),
+                        Some(lft) => format!("[{},{}]", eft, lft),
+                    }
+                    .parse::<TimeRange>()?;
+                    if transitions.insert(transition.id, tr).is_some() {
+                        return Err(Box::new(NetError::DuplicatedName(
+                            transition.id.to_string(),
+                        )));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        for item in &tpn.items {
+            match item {
+                Item::Arc(romeo_arc) => {
+                    let pl = *places.get(&romeo_arc.place).ok_or_else(|| {
+                        NetError::UnknownIdentifier(format!("place {}", romeo_arc.place))
+                    })?;
+                    let tr = *transitions.get(&romeo_arc.transition).ok_or_else(|| {
+                        NetError::UnknownIdentifier(format!("transition {}", romeo_arc.transition))
+                    })?;
+                    let w = romeo_arc.weight;
+                    net.add_arc(match romeo_arc.kind.as_str() {
+                        "PlaceTransition" => arc::Kind::Consume(pl, tr, w),
+                        "TransitionPlace" => arc::Kind::Produce(pl, tr, w),
+                        "read" => arc::Kind::Test(pl, tr, w),
+                        "logicalInhibitor" => arc::Kind::Inhibitor(pl, tr, w),
+                        "inhibitor" => arc::Kind::StopWatchInhibitor(pl, tr, w),
+                        "flush" => arc::Kind::Reset(pl, tr, w),
+                        _ => return Err(Box::new(NetError::InvalidArc)),
+                    })?;
+                }
+                Item::Priority(priority) => {
+                    let more = *transitions.get(&priority.more).ok_or_else(|| {
+                        NetError::UnknownIdentifier(format!("transition {}", priority.more))
+                    })?;
+                    let less = *transitions.get(&priority.less).ok_or_else(|| {
+                        NetError::UnknownIdentifier(format!("transition {}", priority.less))
+                    })?;
+                    net.add_priority(more, less);
+                }
+                _ => {}
+            }
+        }
+        // Priorities are transitive and must not be cyclic
+        net.update_priorities()?;
+        Ok(net)
+    }
+}
@@ -0,0 +1,145 @@
+use pnets::arc::Kind;
+use pnets::timed::{Net, TimeRange};
+use pnets::{NetError, NodeId};
+use pnets_romeo::{Exporter, Parser};
+
+const ROMEO: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<TPN name="demo">
+  <place id="0" label="p0" initialMarking="2">
+    <graphics color="0"><position x="100" y="100"/><deltaLabel deltax="10" deltay="10"/></graphics>
+    <scheduling gamma="0" omega="0"/>
+  </place>
+  <place id="3" label="p1"/>
+  <transition id="0" label="t0" eft="1" lft="3" speed="1" cost="0" unctrl="0" obs="1" guard="" update="">
+    <graphics color="0"><position x="100" y="300"/><deltaLabel deltax="10" deltay="10"/></graphics>
+  </transition>
+  <transition id="1" label="t1" eft="0" lft="inf"/>
+  <arc place="0" transition="0" type="PlaceTransition" weight="2">
+    <nail xnail="0" ynail="0"/>
+    <graphics color="0"></graphics>
+  </arc>
+  <arc place="3" transition="0" type="TransitionPlace" weight="1"/>
+  <arc place="3" transition="1" type="read" weight="1"/>
+  <arc place="0" transition="1" type="logicalInhibitor" weight="1"/>
+  <arc place="3" transition="1" type="inhibitor" weight="3"/>
+  <arc place="0" transition="1" type="flush" weight="1"/>
+  <priority priorityMore="1" priorityLess="0"/>
+  <preferences>
+    <colorPlace c0="SkyBlue2" c1="gray" c2="cyan" c3="green"/>
+  </preferences>
+</TPN>
+"#;
+
+fn names(
+    net: &Net,
+) -> (
+    pnets::PlaceId,
+    pnets::PlaceId,
+    pnets::TransitionId,
+    pnets::TransitionId,
+) {
+    let p0 = match net.get_index_by_name("p0") {
+        Some(NodeId::Place(pl)) => pl,
+        _ => panic!("p0 not found"),
+    };
+    let p1 = match net.get_index_by_name("p1") {
+        Some(NodeId::Place(pl)) => pl,
+        _ => panic!("p1 not found"),
+    };
+    let t0 = match net.get_index_by_name("t0") {
+        Some(NodeId::Transition(tr)) => tr,
+        _ => panic!("t0 not found"),
+    };
+    let t1 = match net.get_index_by_name("t1") {
+        Some(NodeId::Transition(tr)) => tr,
+        _ => panic!("t1 not found"),
+    };
+    (p0, p1, t0, t1)
+}
+
+fn check(net: &Net) {
+    let (p0, p1, t0, t1) = names(net);
+    assert_eq!(net.name, "demo");
+    assert_eq!(net[p0].initial, 2);
+    assert_eq!(net[p1].initial, 0);
+    assert_eq!(net[t0].time, "[1,3]".parse::<TimeRange>().unwrap());
+    assert_eq!(net[t1].time, "[0,w[".parse::<TimeRange>().unwrap());
+    assert!(net[t0].consume.iter().any(|&arc| arc == (p0, 2)));
+    assert!(net[t0].produce.iter().any(|&arc| arc == (p1, 1)));
+    assert!(net[t1].conditions.iter().any(|&arc| arc == (p1, 1)));
+    assert!(net[t1].inhibitors.iter().any(|&arc| arc == (p0, 1)));
+    assert!(net[t1]
+        .stopwatch_inhibitors
+        .iter()
+        .any(|&arc| arc == (p1, 3)));
+    assert!(net[t1].resets.iter().any(|&arc| arc == (p0, 1)));
+    assert_eq!(net[t1].priorities, vec![t0]);
+}
+
+#[test]
+fn parse_test() {
+    let net = Parser::new(ROMEO.as_bytes()).parse().unwrap();
+    check(&net);
+}
+
+#[test]
+fn round_trip_test() {
+    let mut net = Parser::new(ROMEO.as_bytes()).parse().unwrap();
+    let (_, _, t0, _) = names(&net);
+    net[t0].time = "[1/2,3]".parse().unwrap();
+
+    let mut buffer = Vec::new();
+    Exporter::new(&mut buffer).export(&net).unwrap();
+    let mut exported = Parser::new(buffer.as_slice()).parse().unwrap();
+    let (_, _, t0, _) = names(&exported);
+    assert_eq!(exported[t0].time, "[1/2,3]".parse::<TimeRange>().unwrap());
+    exported[t0].time = "[1,3]".parse().unwrap();
+    check(&exported);
+}
+
+#[test]
+fn errors_test() {
+    let mut net = Parser::new(ROMEO.as_bytes()).parse().unwrap();
+    let (_, _, t0, _) = names(&net);
+    net[t0].time = "]1,3]".parse().unwrap();
+    let mut buffer = Vec::new();
+    let err = Exporter::new(&mut buffer).export(&net).unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<NetError>(),
+        Some(&NetError::InvalidTimeRange)
+    );
+
+    // Romeo has no stopwatch arc
+    let mut net = Parser::new(ROMEO.as_bytes()).parse().unwrap();
+    let (p0, _, t0, _) = names(&net);
+    net.add_arc(Kind::StopWatch(p0, t0, 1)).unwrap();
+    let err = Exporter::new(&mut Vec::new()).export(&net).unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<NetError>(),
+        Some(&NetError::UnsupportedArc(Kind::StopWatch(p0, t0, 1)))
+    );
+
+    let unknown = ROMEO.replace(
+        "place=\"3\" transition=\"1\" type=\"read\"",
+        "place=\"7\" transition=\"1\" type=\"read\"",
+    );
+    let err = Parser::new(unknown.as_bytes()).parse().unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<NetError>(),
+        Some(&NetError::UnknownIdentifier("place 7".to_string()))
+    );
+
+    let invalid = ROMEO.replace("type=\"read\"", "type=\"transfer\"");
+    let err = Parser::new(invalid.as_bytes()).parse().unwrap_err();
+    assert_eq!(err.downcast_ref::<NetError>(), Some(&NetError::InvalidArc));
+
+    let cyclic = ROMEO.replace(
+        "<preferences>",
+        "<priority priorityMore=\"0\" priorityLess=\"1\"/><preferences>",
+    );
+    let err = Parser::new(cyclic.as_bytes()).parse().unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<NetError>(),
+        Some(NetError::CyclicPriorities(_))
+    ));
+}
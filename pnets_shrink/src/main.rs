@@ -427,7 +427,7 @@ fn write_output(
         .with_all_places(!args.clean)
         .with_disconnected_transitions(args.clean)
         .build()
-        .export(&net.into())?;
+        .export(net)?;
     info!("Writing done: {:?}.", now.elapsed()?);
     Ok(())
 }
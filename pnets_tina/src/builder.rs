@@ -0,0 +1,202 @@
+use std::error::Error;
+
+use pnets::metadata::{Anchor, Note};
+use pnets::timed::{Net, TimeRange};
+use pnets::{arc, NetError, NodeId, PlaceId, TransitionId};
+
+use crate::visitor::{ArcKind, Visitor};
+
+/// Visitor which builds a [`pnets::timed::Net`], used by [`Parser::parse`](crate::Parser::parse)
+#[derive(Default)]
+pub struct NetBuilder {
+    pub net: Net,
+    /// Notes of the file, they are attached to nodes once all nodes are known
+    notes: Vec<Note>,
+    /// Comment lines waiting for the next declaration
+    comments: Vec<String>,
+    /// Last declared element, which receives inline comments
+    declared: Option<Declaration>,
+}
+
+/// Element declared by a line, comments are attached to it
+#[derive(Copy, Clone)]
+enum Declaration {
+    Anchor(Anchor),
+    Note(usize),
+}
+
+impl NetBuilder {
+    /// Attach the remaining comments and notes, and check priorities
+    pub fn finish(&mut self) -> Result<(), Box<dyn Error>> {
+        for comment in self.comments.drain(..) {
+            self.net.metadata.add_comment(Anchor::End, comment);
+        }
+        // A note named after a node documents this node
+        for note in self.notes.drain(..) {
+            match self.net.get_index_by_name(&note.name) {
+                Some(node) => self.net.metadata.add_node_note(node, note),
+                None => self.net.metadata.notes.push(note),
+            }
+        }
+        // Priorities are transitive and must not be cyclic
+        self.net.update_priorities()
+    }
+
+    /// Attach the pending comments to a declaration
+    fn declare(&mut self, declaration: Declaration) {
+        for comment in std::mem::take(&mut self.comments) {
+            self.attach(declaration, comment);
+        }
+        self.declared = Some(declaration);
+    }
+
+    /// Attach a comment to a declaration
+    fn attach(&mut self, declaration: Declaration, comment: String) {
+        match declaration {
+            Declaration::Anchor(anchor) => self.net.metadata.add_comment(anchor, comment),
+            Declaration::Note(note) => self.notes[note].comments.push(comment),
+        }
+    }
+
+    /// Get transition from net or create one
+    fn get_or_create_transition(&mut self, name: &str) -> Result<TransitionId, Box<dyn Error>> {
+        match self.net.get_index_by_name(name) {
+            Some(NodeId::Transition(id)) => Ok(id),
+            Some(NodeId::Place(_)) => Err(Box::new(NetError::DuplicatedName(name.to_string()))),
+            None => {
+                let tr = self.net.create_transition();
+                self.net.rename_node(NodeId::Transition(tr), name)?;
+                Ok(tr)
+            }
+        }
+    }
+
+    /// Get place from net or create one
+    fn get_or_create_place(&mut self, name: &str) -> Result<PlaceId, Box<dyn Error>> {
+        match self.net.get_index_by_name(name) {
+            Some(NodeId::Place(id)) => Ok(id),
+            Some(NodeId::Transition(_)) => {
+                Err(Box::new(NetError::DuplicatedName(name.to_string())))
+            }
+            None => {
+                let pl = self.net.create_place();
+                self.net.rename_node(NodeId::Place(pl), name)?;
+                Ok(pl)
+            }
+        }
+    }
+}
+
+impl Visitor for NetBuilder {
+    fn net(&mut self, name: &str) -> Result<(), Box<dyn Error>> {
+        self.net.name = name.to_string();
+        self.declare(Declaration::Anchor(Anchor::Net));
+        Ok(())
+    }
+
+    fn place(
+        &mut self,
+        name: &str,
+        label: Option<&str>,
+        marking: usize,
+    ) -> Result<(), Box<dyn Error>> {
+        let pl = self.get_or_create_place(name)?;
+        if let Some(label) = label {
+            self.net[pl].label = Some(label.to_string());
+        }
+        self.net[pl].initial += marking;
+        self.declare(Declaration::Anchor(Anchor::Node(NodeId::Place(pl))));
+        Ok(())
+    }
+
+    fn transition(
+        &mut self,
+        name: &str,
+        label: Option<&str>,
+        time: Option<TimeRange>,
+    ) -> Result<(), Box<dyn Error>> {
+        let tr = self.get_or_create_transition(name)?;
+        if let Some(label) = label {
+            self.net[tr].label = Some(label.to_string());
+        }
+        if let Some(time) = time {
+            self.net[tr].time = self.net[tr].time.intersect(time);
+        }
+        self.declare(Declaration::Anchor(Anchor::Node(NodeId::Transition(tr))));
+        Ok(())
+    }
+
+    fn arc(
+        &mut self,
+        place: &str,
+        transition: &str,
+        kind: ArcKind,
+        weight: usize,
+    ) -> Result<(), Box<dyn Error>> {
+        let pl = self.get_or_create_place(place)?;
+        let tr = self.get_or_create_transition(transition)?;
+        self.net.add_arc(match kind {
+            ArcKind::Consume => arc::Kind::Consume(pl, tr, weight),
+            ArcKind::Produce => arc::Kind::Produce(pl, tr, weight),
+            ArcKind::Test => arc::Kind::Test(pl, tr, weight),
+            ArcKind::Inhibitor => arc::Kind::Inhibitor(pl, tr, weight),
+            ArcKind::StopWatch => arc::Kind::StopWatch(pl, tr, weight),
+            ArcKind::StopWatchInhibitor => arc::Kind::StopWatchInhibitor(pl, tr, weight),
+        })?;
+        Ok(())
+    }
+
+    fn priority(&mut self, higher: &[&str], lower: &[&str]) -> Result<(), Box<dyn Error>> {
+        let mut pre = vec![];
+        for name in higher {
+            pre.push(self.get_or_create_transition(name)?);
+        }
+        let mut post = vec![];
+        for name in lower {
+            post.push(self.get_or_create_transition(name)?);
+        }
+        for &id in &pre {
+            for &id_post in &post {
+                self.net.add_priority(id, id_post);
+            }
+        }
+        // The line documents its first transition with priority
+        self.declare(Declaration::Anchor(match pre.first() {
+            Some(&tr) => Anchor::Node(NodeId::Transition(tr)),
+            None => Anchor::Net,
+        }));
+        Ok(())
+    }
+
+    fn label(&mut self, node: &str, label: &str) -> Result<(), Box<dyn Error>> {
+        let index = match self.net.get_index_by_name(node) {
+            None => return Err(Box::new(NetError::UnknownIdentifier(node.to_string()))),
+            Some(index) => index,
+        };
+        match index {
+            NodeId::Place(pl) => self.net[pl].label = Some(label.to_string()),
+            NodeId::Transition(tr) => self.net[tr].label = Some(label.to_string()),
+        }
+        self.declare(Declaration::Anchor(Anchor::Node(index)));
+        Ok(())
+    }
+
+    fn note(&mut self, name: &str, justification: usize, text: &str) -> Result<(), Box<dyn Error>> {
+        self.notes.push(Note {
+            name: name.to_string(),
+            justification,
+            text: text.to_string(),
+            comments: vec![],
+        });
+        self.declare(Declaration::Note(self.notes.len() - 1));
+        Ok(())
+    }
+
+    fn comment(&mut self, comment: &str, inline: bool) -> Result<(), Box<dyn Error>> {
+        match self.declared {
+            Some(declaration) if inline => self.attach(declaration, comment.to_string()),
+            _ => self.comments.push(comment.to_string()),
+        }
+        Ok(())
+    }
+}
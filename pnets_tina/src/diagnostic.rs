@@ -0,0 +1,78 @@
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+
+use crate::parser::Position;
+
+/// Location of an error in the source
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Span {
+    /// Line and column of the first character
+    pub position: Position,
+    /// Byte range in the source
+    pub bytes: Range<usize>,
+}
+
+/// Error found while parsing with its location
+#[derive(Debug)]
+pub struct Diagnostic {
+    /// Error found
+    pub error: Box<dyn Error>,
+    /// Location of the error, [`None`] for errors about the whole net such as cyclic priorities
+    pub span: Option<Span>,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.error)
+    }
+}
+
+impl Diagnostic {
+    /// Render the diagnostic with the offending line of `source` and a caret under the error
+    ///
+    /// ```text
+    /// error: Invalid char found at line 2 column 8: expected >
+    ///  --> line 2, column 8
+    ///   |
+    /// 2 | tr t0 p -x
+    ///   |        ^
+    /// ```
+    #[must_use]
+    pub fn render(&self, source: &str) -> String {
+        let mut rendered = format!("error: {}\n", self.error);
+        let span = match &self.span {
+            Some(span) => span,
+            None => return rendered,
+        };
+        let start = span.bytes.start.min(source.len());
+        let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = source[start..]
+            .find('\n')
+            .map_or(source.len(), |i| start + i);
+        let line = source[line_start..line_end].trim_end_matches('\r');
+        let end = span.bytes.end.min(line_start + line.len()).max(start);
+        // Tabs are kept so the caret is aligned with the line
+        let offset: String = source[line_start..start]
+            .chars()
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        let width = source[start..end].chars().count().max(1);
+        let number = span.position.line.to_string();
+        let margin = " ".repeat(number.len());
+        rendered.push_str(&format!(
+            "{}--> line {}, column {}\n{} |\n{} | {}\n{} | {}{}\n",
+            margin,
+            span.position.line,
+            span.position.column,
+            margin,
+            number,
+            line,
+            margin,
+            offset,
+            "^".repeat(width)
+        ));
+        rendered
+    }
+}
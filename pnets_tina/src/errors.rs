@@ -14,11 +14,11 @@ pub enum ParserError {
     /// Invalid character at this place (example: `-e` return this error because a `-` must be followed by `>`)
     InvalidChar(Position, String),
     /// Unexpected token at this place, for example an interval at the beginning of a line
-    UnexpectedToken(token::Token, String),
+    UnexpectedToken(token::Token<'static>, String),
     /// Unexpected identifier
     UnexpectedIdentifier(Position, String),
     /// Unexpected arc
-    UnexpectedArc(Position, token::Kind),
+    UnexpectedArc(Position, token::Kind<'static>),
     /// Unsupported arc
     UnsupportedArc(Position, arc::Kind),
 }
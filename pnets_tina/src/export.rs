@@ -1,25 +1,239 @@
 use std::error::Error;
 use std::io::Write;
 
+use pnets::metadata::{Anchor, Metadata, Note};
+use pnets::timed::priority::PriorityGraph;
 use pnets::timed::{Bound, TimeRange};
-use pnets::{timed, NetError, NodeId};
+use pnets::{arc, standard, timed, NetError, NodeId, PlaceId, TransitionId};
+
+use crate::visitor::ArcKind;
+
+/// Order of the places, transitions, arcs and priorities in an exported net
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Order {
+    /// Nodes are written in the order of their index
+    Index,
+    /// Nodes are written in the lexicographic order of their name
+    Name,
+}
+
+/// Net which can be written in the tina format
+///
+/// It is implemented by [`timed::Net`] and [`standard::Net`], so a standard net is written
+/// without being converted. A standard net has no time ranges, priorities or arcs other than
+/// consume and produce arcs.
+pub trait Export {
+    /// Name of the net
+    fn net_name(&self) -> &str;
+
+    /// Notes and comments of the net
+    fn net_metadata(&self) -> &Metadata;
+
+    /// Name of a place or a transition
+    fn node_name(&self, node: NodeId) -> String;
+
+    /// All places in the order of their index
+    fn place_ids(&self) -> Vec<PlaceId>;
+
+    /// All transitions in the order of their index
+    fn transition_ids(&self) -> Vec<TransitionId>;
+
+    /// Label of a place or a transition
+    fn node_label(&self, node: NodeId) -> Option<&str>;
+
+    /// Initial marking of a place
+    fn place_initial(&self, place: PlaceId) -> usize;
+
+    /// Returns [`true`] if a place or a transition has no arc
+    fn is_node_disconnected(&self, node: NodeId) -> bool;
+
+    /// Returns [`true`] if a place or a transition was removed from the net
+    fn is_node_deleted(&self, _node: NodeId) -> bool {
+        false
+    }
+
+    /// Time range of a transition
+    fn transition_time(&self, _transition: TransitionId) -> TimeRange {
+        TimeRange::default()
+    }
+
+    /// Arcs of a kind between a transition and places, sorted by place
+    fn transition_arcs(&self, transition: TransitionId, kind: ArcKind) -> &[(PlaceId, usize)];
+
+    /// Priorities between transitions
+    fn priorities(&self) -> Option<PriorityGraph> {
+        None
+    }
+
+    /// An arc which has no equivalent in tina formats, such as a reset arc
+    fn unsupported_arc(&self) -> Option<arc::Kind> {
+        None
+    }
+}
+
+impl Export for timed::Net {
+    fn net_name(&self) -> &str {
+        &self.name
+    }
+
+    fn net_metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    fn node_name(&self, node: NodeId) -> String {
+        self.get_name_by_index(&node).unwrap()
+    }
+
+    fn place_ids(&self) -> Vec<PlaceId> {
+        self.places.iter_enumerated().map(|(pl, _)| pl).collect()
+    }
+
+    fn transition_ids(&self) -> Vec<TransitionId> {
+        self.transitions
+            .iter_enumerated()
+            .map(|(tr, _)| tr)
+            .collect()
+    }
+
+    fn node_label(&self, node: NodeId) -> Option<&str> {
+        match node {
+            NodeId::Place(pl) => self[pl].label.as_deref(),
+            NodeId::Transition(tr) => self[tr].label.as_deref(),
+        }
+    }
+
+    fn place_initial(&self, place: PlaceId) -> usize {
+        self[place].initial
+    }
+
+    fn is_node_disconnected(&self, node: NodeId) -> bool {
+        match node {
+            NodeId::Place(pl) => self[pl].is_disconnected(),
+            NodeId::Transition(tr) => self[tr].is_disconnected(),
+        }
+    }
+
+    fn transition_time(&self, transition: TransitionId) -> TimeRange {
+        self[transition].time
+    }
+
+    fn transition_arcs(&self, transition: TransitionId, kind: ArcKind) -> &[(PlaceId, usize)] {
+        let transition = &self[transition];
+        match kind {
+            ArcKind::Consume => &transition.consume,
+            ArcKind::Produce => &transition.produce,
+            ArcKind::Test => &transition.conditions,
+            ArcKind::Inhibitor => &transition.inhibitors,
+            ArcKind::StopWatch => &transition.stopwatches,
+            ArcKind::StopWatchInhibitor => &transition.stopwatch_inhibitors,
+        }
+        .iter()
+        .as_slice()
+    }
+
+    fn priorities(&self) -> Option<PriorityGraph> {
+        Some(self.priority_graph())
+    }
+
+    fn unsupported_arc(&self) -> Option<arc::Kind> {
+        self.transitions.iter().find_map(|transition| {
+            transition
+                .resets
+                .iter()
+                .next()
+                .map(|&(pl, w)| arc::Kind::Reset(pl, transition.id(), w))
+        })
+    }
+}
+
+impl Export for standard::Net {
+    fn net_name(&self) -> &str {
+        &self.name
+    }
+
+    fn net_metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    fn node_name(&self, node: NodeId) -> String {
+        self.get_name_by_index(&node).unwrap()
+    }
+
+    fn place_ids(&self) -> Vec<PlaceId> {
+        self.places.iter().map(|place| place.id()).collect()
+    }
+
+    fn transition_ids(&self) -> Vec<TransitionId> {
+        self.transitions
+            .iter()
+            .map(|transition| transition.id())
+            .collect()
+    }
+
+    fn node_label(&self, node: NodeId) -> Option<&str> {
+        match node {
+            NodeId::Place(pl) => self[pl].label.as_deref(),
+            NodeId::Transition(tr) => self[tr].label.as_deref(),
+        }
+    }
+
+    fn place_initial(&self, place: PlaceId) -> usize {
+        self[place].initial
+    }
+
+    fn is_node_disconnected(&self, node: NodeId) -> bool {
+        match node {
+            NodeId::Place(pl) => self[pl].is_disconnected(),
+            NodeId::Transition(tr) => self[tr].is_disconnected(),
+        }
+    }
+
+    fn is_node_deleted(&self, node: NodeId) -> bool {
+        match node {
+            NodeId::Place(pl) => self[pl].deleted,
+            NodeId::Transition(tr) => self[tr].deleted,
+        }
+    }
+
+    fn transition_arcs(&self, transition: TransitionId, kind: ArcKind) -> &[(PlaceId, usize)] {
+        match kind {
+            ArcKind::Consume => self[transition].consume.iter().as_slice(),
+            ArcKind::Produce => self[transition].produce.iter().as_slice(),
+            _ => &[],
+        }
+    }
+}
 
 /// Create a new tina exporter from parameters
 pub struct ExporterBuilder<'w> {
     writer: &'w mut dyn Write,
     without_disconnected_transitions: bool,
     with_all_places: bool,
+    order: Order,
+    minimal_quoting: bool,
+    label_lines: bool,
+    arc_lines: bool,
+    with_labels: bool,
+    with_metadata: bool,
 }
 
 impl<'w> ExporterBuilder<'w> {
     /// Create a new builder
     ///
-    /// By default all disconnected transitions and places are exported
+    /// By default all disconnected transitions and places are exported in [`Order::Index`], all
+    /// names are quoted and labels, notes and comments are written. Labels and arcs are written
+    /// on the line of their node.
     pub fn new(writer: &'w mut dyn Write) -> Self {
         Self {
             writer,
             without_disconnected_transitions: false,
             with_all_places: false,
+            order: Order::Index,
+            minimal_quoting: false,
+            label_lines: false,
+            arc_lines: false,
+            with_labels: true,
+            with_metadata: true,
         }
     }
     /// Set if export should keep all disconnected transition or not
@@ -36,12 +250,54 @@ impl<'w> ExporterBuilder<'w> {
             ..self
         }
     }
+    /// Set the order of nodes, arcs and priorities
+    pub fn with_order(self, order: Order) -> Self {
+        Self { order, ..self }
+    }
+    /// Set if names are quoted with `{}` only when they are not valid identifiers
+    pub fn with_minimal_quoting(self, minimal_quoting: bool) -> Self {
+        Self {
+            minimal_quoting,
+            ..self
+        }
+    }
+    /// Set if labels are written on separate `lb` lines instead of inline
+    pub fn with_label_lines(self, label_lines: bool) -> Self {
+        Self {
+            label_lines,
+            ..self
+        }
+    }
+    /// Set if each arc of a transition is written on its own `tr` line
+    pub fn with_arc_lines(self, arc_lines: bool) -> Self {
+        Self { arc_lines, ..self }
+    }
+    /// Set if the labels of nodes are written
+    pub fn with_labels(self, with_labels: bool) -> Self {
+        Self {
+            with_labels,
+            ..self
+        }
+    }
+    /// Set if the notes and comments of the [metadata][`timed::Net::metadata`] are written
+    pub fn with_metadata(self, with_metadata: bool) -> Self {
+        Self {
+            with_metadata,
+            ..self
+        }
+    }
     /// Build the exporter
     pub fn build(self) -> Exporter<'w> {
         Exporter {
             writer: self.writer,
             without_disconnected_transition: self.without_disconnected_transitions,
             with_all_places: self.with_all_places,
+            order: self.order,
+            minimal_quoting: self.minimal_quoting,
+            label_lines: self.label_lines,
+            arc_lines: self.arc_lines,
+            with_labels: self.with_labels,
+            with_metadata: self.with_metadata,
         }
     }
 }
@@ -54,6 +310,12 @@ pub struct Exporter<'w> {
     writer: &'w mut dyn Write,
     without_disconnected_transition: bool,
     with_all_places: bool,
+    order: Order,
+    minimal_quoting: bool,
+    label_lines: bool,
+    arc_lines: bool,
+    with_labels: bool,
+    with_metadata: bool,
 }
 
 impl<'w> Exporter<'w> {
@@ -66,157 +328,244 @@ impl<'w> Exporter<'w> {
         )
     }
 
-    /// Export a timed net
-    pub fn export(&mut self, net: &timed::Net) -> Result<(), Box<dyn Error>> {
-        if !net.name.is_empty() {
-            self.writer
-                .write_all(format!("net {}\n", Self::escape(&net.name)).as_ref())?;
+    /// Returns [`true`] if a name can be written without `{}`
+    ///
+    /// Keywords and names starting with a digit, which would be read as a weight, are quoted.
+    fn is_plain(s: &str) -> bool {
+        let keyword = ["tr", "pl", "net", "lb", "nt", "na", "pr"]
+            .iter()
+            .any(|k| s.eq_ignore_ascii_case(k));
+        match s.chars().next() {
+            Some(first) if !first.is_numeric() && !keyword => s
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == '\''),
+            _ => false,
         }
-        for (pl, place) in net.places.iter_enumerated() {
-            if self.with_all_places
-                | (!place.is_disconnected() && (place.label.is_some() | (place.initial != 0)))
-            {
-                self.writer.write_all(
-                    format!(
-                        "pl {} ",
-                        Self::escape(&net.get_name_by_index(&NodeId::Place(pl)).unwrap())
-                    )
-                    .as_ref(),
-                )?;
-                if place.label.is_some() {
-                    self.writer.write_all(
-                        format!(": {} ", Self::escape(place.label.as_ref().unwrap())).as_ref(),
-                    )?;
-                }
-                if place.initial != 0 {
-                    self.writer
-                        .write_all(format!("({})", place.initial).as_ref())?;
-                }
-                self.writer.write_all("\n".as_ref())?;
-            }
+    }
+
+    /// Quote a name if needed
+    fn quote(&self, s: &str) -> String {
+        if self.minimal_quoting && Self::is_plain(s) {
+            s.to_string()
+        } else {
+            Self::escape(s)
         }
+    }
 
-        for (tr, transition) in net.transitions.iter_enumerated() {
-            if self.without_disconnected_transition && transition.is_disconnected() {
-                continue;
-            }
+    /// Quoted name of a node
+    fn name(&self, net: &dyn Export, node: NodeId) -> String {
+        self.quote(&net.node_name(node))
+    }
+
+    /// Sort nodes according to the order of the exporter
+    fn sort<T: Copy>(&self, net: &dyn Export, nodes: &mut [T], node: impl Fn(T) -> NodeId) {
+        if self.order == Order::Name {
+            nodes.sort_by_cached_key(|&n| net.node_name(node(n)));
+        }
+    }
+
+    /// Write arcs of a kind to a list of arcs, the weight is omitted when it is `1` and
+    /// `implicit` is set
+    fn arcs(
+        &self,
+        net: &dyn Export,
+        arcs: &mut Vec<String>,
+        places: &[(PlaceId, usize)],
+        symbol: &str,
+        implicit: bool,
+    ) {
+        let mut places = places.to_vec();
+        self.sort(net, &mut places, |(pl, _)| NodeId::Place(pl));
+        for (pl, w) in places {
+            let name = self.name(net, NodeId::Place(pl));
+            arcs.push(if implicit && w == 1 {
+                name
+            } else {
+                format!("{}{}{}", name, symbol, w)
+            });
+        }
+    }
+
+    /// Write comment lines
+    fn comments(&mut self, comments: &[String]) -> Result<(), Box<dyn Error>> {
+        if !self.with_metadata {
+            return Ok(());
+        }
+        for comment in comments {
+            self.writer.write_all(format!("#{}\n", comment).as_ref())?;
+        }
+        Ok(())
+    }
+
+    /// Write notes with their comments, notes of a node are named after it
+    fn notes(&mut self, notes: &[Note], node: Option<String>) -> Result<(), Box<dyn Error>> {
+        if !self.with_metadata {
+            return Ok(());
+        }
+        for note in notes {
+            self.comments(&note.comments)?;
             self.writer.write_all(
                 format!(
-                    "tr {} ",
-                    Self::escape(&net.get_name_by_index(&NodeId::Transition(tr)).unwrap())
+                    "nt {} {} {}\n",
+                    self.quote(node.as_ref().unwrap_or(&note.name)),
+                    note.justification,
+                    Self::escape(&note.text)
                 )
                 .as_ref(),
             )?;
-            if transition.label.is_some() {
+        }
+        Ok(())
+    }
+
+    /// Write the label of a node, inline or on its own line
+    fn label(
+        &mut self,
+        net: &dyn Export,
+        node: NodeId,
+        inline: bool,
+    ) -> Result<(), Box<dyn Error>> {
+        if let (Some(label), true) = (net.node_label(node), self.with_labels) {
+            if !self.label_lines && inline {
+                self.writer
+                    .write_all(format!(": {} ", Self::escape(label)).as_ref())?;
+            } else if self.label_lines && !inline {
                 self.writer.write_all(
-                    format!(": {} ", Self::escape(transition.label.as_ref().unwrap())).as_ref(),
+                    format!("lb {} {}\n", self.name(net, node), Self::escape(label)).as_ref(),
                 )?;
             }
-            if (transition.time
-                != TimeRange {
-                    start: Bound::Closed(0),
-                    end: Bound::Infinity,
-                })
+        }
+        Ok(())
+    }
+
+    /// Export a net
+    ///
+    /// Comments and notes of the [metadata][`timed::Net::metadata`] of the net are written with
+    /// the declarations they are attached to, places with comments or notes are always written.
+    ///
+    /// # Errors
+    /// Return [`NetError::UnsupportedArc`] if the net has reset arcs
+    pub fn export(&mut self, net: &dyn Export) -> Result<(), Box<dyn Error>> {
+        if let Some(arc) = net.unsupported_arc() {
+            return Err(Box::new(NetError::UnsupportedArc(arc)));
+        }
+        let metadata = net.net_metadata();
+        self.comments(metadata.comments(Anchor::Net))?;
+        if !net.net_name().is_empty() {
+            self.writer
+                .write_all(format!("net {}\n", self.quote(net.net_name())).as_ref())?;
+        }
+        let mut places = net.place_ids();
+        self.sort(net, &mut places, NodeId::Place);
+        for pl in places {
+            let node = NodeId::Place(pl);
+            let initial = net.place_initial(pl);
+            let documented = self.with_metadata && metadata.is_documented(node);
+            let labelled = self.with_labels && net.node_label(node).is_some();
+            if self.with_all_places
+                | documented
+                | (!net.is_node_disconnected(node) && (labelled | (initial != 0)))
             {
-                match transition.time.start {
-                    Bound::Closed(v) => self.writer.write_all(format!("[{},", v).as_ref())?,
-                    Bound::Open(v) => self.writer.write_all(format!("]{},", v).as_ref())?,
-                    Bound::Infinity => {
-                        return Err(Box::new(NetError::InvalidTimeRange));
-                    }
-                };
-                match transition.time.end {
-                    Bound::Closed(v) => self.writer.write_all(format!("{}] ", v).as_ref())?,
-                    Bound::Open(v) => self.writer.write_all(format!("{}[ ", v).as_ref())?,
-                    Bound::Infinity => self.writer.write_all("w[ ".as_ref())?,
-                };
+                self.comments(metadata.comments(Anchor::Node(node)))?;
+                self.writer
+                    .write_all(format!("pl {} ", self.name(net, node)).as_ref())?;
+                self.label(net, node, true)?;
+                if initial != 0 {
+                    self.writer.write_all(format!("({})", initial).as_ref())?;
+                }
+                self.writer.write_all("\n".as_ref())?;
+                self.label(net, node, false)?;
+                self.notes(metadata.node_notes(node), Some(net.node_name(node)))?;
             }
+        }
 
-            for &(pl, w) in transition.inhibitors.iter() {
-                self.writer.write_all(
-                    format!(
-                        "{}?-{} ",
-                        Self::escape(&net.get_name_by_index(&NodeId::Place(pl)).unwrap()),
-                        w
-                    )
-                    .as_ref(),
-                )?;
+        // Only the priorities which are not implied by transitivity are written
+        let priorities = match net.priorities() {
+            Some(priorities) => Some(priorities.transitive_reduction()?),
+            None => None,
+        };
+        let mut transitions = net.transition_ids();
+        self.sort(net, &mut transitions, NodeId::Transition);
+        for tr in transitions {
+            let node = NodeId::Transition(tr);
+            if self.without_disconnected_transition && net.is_node_disconnected(node) {
+                continue;
             }
-
-            for &(pl, w) in transition.consume.iter() {
-                match w {
-                    1 => self.writer.write_all(
-                        format!(
-                            "{} ",
-                            Self::escape(&net.get_name_by_index(&NodeId::Place(pl)).unwrap())
-                        )
-                        .as_ref(),
-                    )?,
-                    w => self.writer.write_all(
-                        format!(
-                            "{}*{} ",
-                            Self::escape(&net.get_name_by_index(&NodeId::Place(pl)).unwrap()),
-                            w
-                        )
-                        .as_ref(),
-                    )?,
+            self.comments(metadata.comments(Anchor::Node(node)))?;
+            let name = self.name(net, node);
+            self.writer.write_all(format!("tr {} ", name).as_ref())?;
+            self.label(net, node, true)?;
+            let time = net.transition_time(tr);
+            if time != TimeRange::default() {
+                if time.start == Bound::Infinity {
+                    return Err(Box::new(NetError::InvalidTimeRange));
                 }
+                self.writer.write_all(format!("{} ", time).as_ref())?;
             }
 
-            for &(pl, w_cond) in transition.conditions.iter() {
-                self.writer.write_all(
-                    format!(
-                        "{}?{} ",
-                        Self::escape(&net.get_name_by_index(&NodeId::Place(pl)).unwrap()),
-                        w_cond
-                    )
-                    .as_ref(),
-                )?;
-                self.writer.write_all(" ".as_ref())?;
+            let mut inputs = vec![];
+            for &(kind, symbol, implicit) in &[
+                (ArcKind::Inhibitor, "?-", false),
+                (ArcKind::Consume, "*", true),
+                (ArcKind::Test, "?", false),
+                (ArcKind::StopWatch, "!", false),
+                (ArcKind::StopWatchInhibitor, "!-", false),
+            ] {
+                self.arcs(
+                    net,
+                    &mut inputs,
+                    net.transition_arcs(tr, kind),
+                    symbol,
+                    implicit,
+                );
             }
-            self.writer.write_all("-> ".as_ref())?;
-            for &(pl, w_produced) in transition.produce.iter() {
-                match w_produced {
-                    1 => self.writer.write_all(
-                        format!(
-                            "{} ",
-                            Self::escape(&net.get_name_by_index(&NodeId::Place(pl)).unwrap()),
-                        )
-                        .as_ref(),
-                    )?,
-                    w_produced => self.writer.write_all(
-                        format!(
-                            "{}*{} ",
-                            Self::escape(&net.get_name_by_index(&NodeId::Place(pl)).unwrap()),
-                            w_produced
-                        )
-                        .as_ref(),
-                    )?,
+            let mut outputs = vec![];
+            self.arcs(
+                net,
+                &mut outputs,
+                net.transition_arcs(tr, ArcKind::Produce),
+                "*",
+                true,
+            );
+
+            if self.arc_lines {
+                self.writer.write_all("\n".as_ref())?;
+                for input in inputs {
+                    self.writer
+                        .write_all(format!("tr {} {} -> \n", name, input).as_ref())?;
+                }
+                for output in outputs {
+                    self.writer
+                        .write_all(format!("tr {} -> {} \n", name, output).as_ref())?;
+                }
+            } else {
+                for input in inputs {
+                    self.writer.write_all(format!("{} ", input).as_ref())?;
+                }
+                self.writer.write_all("-> ".as_ref())?;
+                for output in outputs {
+                    self.writer.write_all(format!("{} ", output).as_ref())?;
                 }
+                self.writer.write_all("\n".as_ref())?;
             }
-            self.writer.write_all("\n".as_ref())?;
-            if !transition.priorities.is_empty() {
-                self.writer.write_all(
-                    format!(
-                        "pr {} > ",
-                        Self::escape(&net.get_name_by_index(&NodeId::Transition(tr)).unwrap()),
-                    )
-                    .as_ref(),
-                )?;
+            self.label(net, node, false)?;
 
-                for &pr in &transition.priorities {
+            let mut lower = priorities
+                .as_ref()
+                .map_or(vec![], |priorities| priorities.priorities(tr).to_vec());
+            self.sort(net, &mut lower, NodeId::Transition);
+            if !lower.is_empty() {
+                self.writer.write_all(format!("pr {} > ", name).as_ref())?;
+                for pr in lower {
                     self.writer.write_all(
-                        format!(
-                            "{} ",
-                            Self::escape(&net.get_name_by_index(&NodeId::Transition(pr)).unwrap())
-                        )
-                        .as_ref(),
+                        format!("{} ", self.name(net, NodeId::Transition(pr))).as_ref(),
                     )?;
                 }
-
                 self.writer.write_all("\n".as_ref())?;
             }
+            self.notes(metadata.node_notes(node), Some(net.node_name(node)))?;
         }
+        self.notes(&metadata.notes, None)?;
+        self.comments(metadata.comments(Anchor::End))?;
         Ok(())
     }
 }
@@ -1,7 +1,9 @@
+use std::borrow::Cow;
 use std::error::Error;
-use std::io::BufRead;
+use std::ops::Range;
 
-use pnets::timed::Bound;
+use pnets::timed::{Bound, TimeRange};
+use pnets::NetError;
 
 use crate::parser::Position;
 use crate::reader::Reader;
@@ -9,28 +11,37 @@ use crate::token::{Kind, Token};
 use crate::ParserError;
 
 /// Lexer for net files
-pub struct Lexer<R: BufRead> {
-    reader: Reader<R>,
-    pub current_token: Token,
-    next_token: Option<Token>,
-    tmp_string: String,
+///
+/// Identifiers and comments are borrowed from the source, only escaped identifiers are copied.
+pub struct Lexer<'s> {
+    reader: Reader<'s>,
+    pub current_token: Token<'s>,
+    /// Byte range of the current token
+    pub current_span: Range<usize>,
+    next_token: Option<Token<'s>>,
+    /// Byte range of the next token
+    next_span: Range<usize>,
+    /// Set when the last token could not be read, the reader is in the middle of a token
+    failed: bool,
 }
 
-impl<R: BufRead> Lexer<R> {
+impl<'s> Lexer<'s> {
     /// Create a new lexer for reading .net files
     ///
     /// ```ignore
-    /// let lexer = Reader::new(&"This string will be lexed".as_bytes());
+    /// let lexer = Lexer::new("This string will be lexed");
     /// ```
-    pub fn new(reader: R) -> Self {
+    pub fn new(source: &'s str) -> Self {
         Self {
-            reader: Reader::new(reader),
+            reader: Reader::new(source),
             current_token: Token {
                 kind: Kind::EndOfFile,
                 position: Position { line: 1, column: 0 },
             },
+            current_span: 0..0,
             next_token: None,
-            tmp_string: "".to_string(),
+            next_span: 0..0,
+            failed: false,
         }
     }
 
@@ -40,135 +51,146 @@ impl<R: BufRead> Lexer<R> {
     }
 
     /// Try to parse some text token or generate an ANAME Identifier
-    fn parse_identifer(&mut self) -> Result<Kind, Box<dyn Error>> {
-        self.tmp_string.clear();
-        self.tmp_string.push(self.reader.read()?);
+    fn parse_identifer(&mut self) -> Result<Kind<'s>, Box<dyn Error>> {
+        let start = self.reader.next_offset;
+        self.reader.read();
         // Bug probable pour le parser GO: "{ \} }" => Donne "{ \}" au lieu de "{ \} }"
-        while Self::is_ident_char(self.reader.peek()?) {
-            self.tmp_string.push(self.reader.read()?);
-        }
-
-        Ok(match self.tmp_string.as_str() {
-            "TR" | "Tr" | "tR" | "tr" => Kind::Transition,
-            "NET" | "NEt" | "NeT" | "nET" | "Net" | "nEt" | "neT" | "net" => Kind::Net,
-            "LB" | "Lb" | "lB" | "lb" => Kind::Label,
-            "NT" | "Nt" | "nT" | "nt" | "na" | "NA" | "Na" | "nA" => Kind::Note,
-            "PL" | "Pl" | "pL" | "pl" => Kind::Place,
-            "PR" | "Pr" | "pR" | "pr" => Kind::Priority,
-            _ => Kind::Identifier(self.tmp_string.clone()),
+        self.reader.read_while(Self::is_ident_char);
+        let text = self.reader.slice(start);
+
+        // Keywords are case insensitive
+        let keyword = |k: &str| text.eq_ignore_ascii_case(k);
+        Ok(if keyword("tr") {
+            Kind::Transition
+        } else if keyword("net") {
+            Kind::Net
+        } else if keyword("lb") {
+            Kind::Label
+        } else if keyword("nt") || keyword("na") {
+            Kind::Note
+        } else if keyword("pl") {
+            Kind::Place
+        } else if keyword("pr") {
+            Kind::Priority
+        } else {
+            Kind::Identifier(Cow::Borrowed(text))
         })
     }
 
     /// Parse int from reader
     fn parse_small_int(&mut self) -> Result<usize, Box<dyn Error>> {
-        self.tmp_string.clear();
-        while self.reader.peek()?.is_numeric() {
-            self.tmp_string.push(self.reader.read()?);
+        let position = self.reader.next_position;
+        let digits = self.reader.read_while(|c| c.is_ascii_digit());
+        if digits.is_empty() {
+            return Err(Box::new(ParserError::InvalidChar(
+                position,
+                "expected an integer".to_string(),
+            )));
         }
-
-        Ok(self.tmp_string.parse()?)
+        // Digits are accumulated directly, without going through `str::parse`
+        digits
+            .bytes()
+            .try_fold(0_usize, |value, digit| {
+                value
+                    .checked_mul(10)?
+                    .checked_add(usize::from(digit - b'0'))
+            })
+            .ok_or_else(|| {
+                Box::new(ParserError::InvalidChar(
+                    position,
+                    format!("integer too large: {}", digits),
+                )) as Box<dyn Error>
+            })
     }
 
     /// Parse time interval
-    fn parse_time_interval(&mut self) -> Result<Kind, Box<dyn Error>> {
-        let open = match self.reader.read()? {
-            '[' => Bound::Closed(self.parse_int()?),
-            ']' => Bound::Open(self.parse_int()?),
-            _ => {
-                return Err(Box::new(ParserError::InvalidChar(
-                    self.reader.current_position,
-                    "[, ]".to_string(),
-                )));
+    fn parse_time_interval(&mut self) -> Result<Kind<'s>, Box<dyn Error>> {
+        let start = self.reader.next_offset;
+        self.reader.read();
+        // The interval ends with the first bracket after the comma
+        let mut comma = false;
+        loop {
+            let c = self.reader.peek();
+            if matches!(c, '\n' | '\u{0}') {
+                break;
+            }
+            self.reader.read();
+            match c {
+                ',' => comma = true,
+                '[' | ']' if comma => break,
+                _ => {}
             }
-        };
-
-        if self.reader.read()? != ',' {
-            return Err(Box::new(ParserError::InvalidChar(
-                self.reader.current_position,
-                ",".to_string(),
-            )));
         }
-
-        let close = if self.reader.peek()? == 'w' {
-            self.reader.read()?;
-            if self.reader.read()? != '[' {
-                return Err(Box::new(ParserError::InvalidChar(
-                    self.reader.current_position,
-                    "[".to_string(),
-                )));
-            }
-            Bound::Infinity
-        } else {
-            let end = self.parse_int()?;
-            match self.reader.read()? {
-                ']' => Bound::Closed(end),
-                '[' => Bound::Open(end),
-                _ => {
-                    return Err(Box::new(ParserError::InvalidChar(
-                        self.reader.current_position,
-                        "[, ]".to_string(),
-                    )));
-                }
-            }
-        };
-
-        Ok(Kind::TimeInterval(open, close))
+        let text = self.reader.slice(start);
+        // Tina has no range starting at infinity
+        let range = text
+            .parse::<TimeRange>()
+            .and_then(|range| match range.start {
+                Bound::Infinity => Err(NetError::InvalidTimeRange),
+                _ => Ok(range),
+            })
+            .map_err(|e: NetError| {
+                ParserError::InvalidChar(self.reader.current_position, format!("{}: {}", e, text))
+            })?;
+        Ok(Kind::TimeInterval(range.start, range.end))
     }
 
     /// Parse int with unit
     fn parse_int(&mut self) -> Result<usize, Box<dyn Error>> {
-        let complex = if self.reader.peek()? == '(' {
-            self.reader.read()?;
+        let position = self.reader.next_position;
+        let complex = if self.reader.peek() == '(' {
+            self.reader.read();
             true
         } else {
             false
         };
-        let r = self.parse_small_int()?
-            * (match self.reader.peek()? {
-                'K' => {
-                    self.reader.read()?;
-                    1_000
-                }
-                'M' => {
-                    self.reader.read()?;
-                    1_000_000
-                }
-                _ => 1,
-            });
-        if complex & (self.reader.peek()? == ')') {
-            self.reader.read()?;
+        let value = self.parse_small_int()?;
+        let unit = match self.reader.peek() {
+            'K' => {
+                self.reader.read();
+                1_000
+            }
+            'M' => {
+                self.reader.read();
+                1_000_000
+            }
+            _ => 1,
+        };
+        if complex & (self.reader.peek() == ')') {
+            self.reader.read();
         }
-        Ok(r)
+        value.checked_mul(unit).ok_or_else(|| {
+            Box::new(ParserError::InvalidChar(
+                position,
+                "integer too large".to_string(),
+            )) as Box<dyn Error>
+        })
     }
 
     /// Parse comment line
-    fn parse_comment(&mut self) -> Result<Kind, Box<dyn Error>> {
-        self.tmp_string.clear();
-        self.reader.read()?; // Remove trailing #
+    fn parse_comment(&mut self) -> Result<Kind<'s>, Box<dyn Error>> {
+        self.reader.read(); // Remove trailing #
 
-        while self.reader.peek()? != '\n' {
-            self.tmp_string.push(self.reader.read()?);
-        }
-
-        Ok(Kind::Comment(self.tmp_string.clone()))
+        Ok(Kind::Comment(Cow::Borrowed(
+            self.reader.read_while(|c| c != '\n'),
+        )))
     }
 
     /// Parse next token
-    fn parse_next_token(&mut self) -> Result<Token, Box<dyn Error>> {
+    fn parse_next_token(&mut self) -> Result<Token<'s>, Box<dyn Error>> {
         // Remove whitespaces
-        while matches!(self.reader.peek()?, ' ' | '\t' | '\r') {
-            self.reader.read()?;
-        }
+        self.reader.read_while(|c| matches!(c, ' ' | '\t' | '\r'));
+        self.next_span.start = self.reader.next_offset;
         Ok(Token {
             position: self.reader.next_position,
-            kind: match (&self.current_token.kind, self.reader.peek()?) {
+            kind: match (&self.current_token.kind, self.reader.peek()) {
                 (_, '\n') => {
-                    self.reader.read()?;
+                    self.reader.read();
                     Kind::NewLine
                 }
                 (_, '-') => {
-                    self.reader.read()?;
-                    if self.reader.read()? == '>' {
+                    self.reader.read();
+                    if self.reader.read() == '>' {
                         Kind::Arrow
                     } else {
                         return Err(Box::new(ParserError::InvalidChar(
@@ -179,27 +201,30 @@ impl<R: BufRead> Lexer<R> {
                 }
                 (_, '[') | (_, ']') => self.parse_time_interval()?,
                 (_, ':') => {
-                    self.reader.read()?;
+                    self.reader.read();
                     Kind::InlineLabel
                 }
                 (_, '*') => {
-                    self.reader.read()?;
+                    self.reader.read();
                     Kind::NormalArc
                 }
                 (_, '?') => {
-                    self.reader.read()?;
-                    match self.reader.peek()? {
+                    self.reader.read();
+                    match self.reader.peek() {
                         '-' => {
-                            self.reader.read()?;
+                            self.reader.read();
                             Kind::InhibitorArc
                         }
                         _ => Kind::TestArc,
                     }
                 }
                 (_, '!') => {
-                    self.reader.read()?;
-                    match self.reader.peek()? {
-                        '-' => Kind::StopWatchInhibitorArc,
+                    self.reader.read();
+                    match self.reader.peek() {
+                        '-' => {
+                            self.reader.read();
+                            Kind::StopWatchInhibitorArc
+                        }
                         _ => Kind::StopWatchArc,
                     }
                 }
@@ -218,11 +243,11 @@ impl<R: BufRead> Lexer<R> {
                 (_, '{') => self.parse_escaped_identifer()?,
                 (_, '\u{0}') => Kind::EndOfFile,
                 (_, '>') => {
-                    self.reader.read()?;
+                    self.reader.read();
                     Kind::GreaterThan
                 }
                 (_, '<') => {
-                    self.reader.read()?;
+                    self.reader.read();
                     Kind::LessThan
                 }
                 (_, _) => self.parse_identifer()?,
@@ -236,50 +261,133 @@ impl<R: BufRead> Lexer<R> {
         })
     }
 
-    fn parse_escaped_identifer(&mut self) -> Result<Kind, Box<dyn Error>> {
-        self.tmp_string.clear();
-        let mut last = ' ';
-        self.reader.read()?;
-        while (self.reader.peek()? != '}') || ((self.reader.peek()? == '}') & (last == '\\')) {
-            match (last, self.reader.peek()?) {
-                ('\\', '\\') => self.tmp_string.push('\\'),
-                ('\\', '{') => self.tmp_string.push('{'),
-                ('\\', '}') => self.tmp_string.push('}'),
-                (_, '{') | (_, '}') => {
+    fn parse_escaped_identifer(&mut self) -> Result<Kind<'s>, Box<dyn Error>> {
+        self.reader.read();
+        let start = self.reader.next_offset;
+        // The identifier is only copied if it contains escaped characters
+        let mut unescaped: Option<String> = None;
+        // Set after a `\`, an escaped `\` does not escape the next character
+        let mut escaped = false;
+        loop {
+            match (escaped, self.reader.peek()) {
+                (_, '\u{0}') => {
+                    return Err(Box::new(ParserError::InvalidChar(
+                        self.reader.current_position,
+                        "expected }".to_string(),
+                    )));
+                }
+                (true, c) => {
+                    unescaped.get_or_insert_with(String::new).push(c);
+                    escaped = false;
+                }
+                (false, '}') => break,
+                (false, '{') => {
                     return Err(Box::new(ParserError::InvalidChar(
                         self.reader.current_position,
                         "\\, { and } must be precedeed by".to_string(),
                     )));
                 }
-                (_, '\\') => {}
-                (_, c) => self.tmp_string.push(c),
+                (false, '\\') => {
+                    unescaped.get_or_insert_with(|| self.reader.slice(start).to_string());
+                    escaped = true;
+                }
+                (false, c) => {
+                    if let Some(unescaped) = &mut unescaped {
+                        unescaped.push(c)
+                    }
+                }
             }
-            last = self.reader.read()?;
+            self.reader.read();
         }
-        self.reader.read()?;
-        Ok(Kind::Identifier(self.tmp_string.clone()))
+        let identifier = match unescaped {
+            Some(unescaped) => Cow::Owned(unescaped),
+            None => Cow::Borrowed(self.reader.slice(start)),
+        };
+        self.reader.read();
+        Ok(Kind::Identifier(identifier))
     }
 
     /// Peek next token
-    pub fn peek(&mut self) -> Result<Token, Box<dyn Error>> {
+    pub fn peek(&mut self) -> Result<Token<'s>, Box<dyn Error>> {
         if self.next_token.is_none() {
-            self.next_token = Some(self.parse_next_token()?);
+            match self.parse_next_token() {
+                Ok(token) => {
+                    self.next_span.end = self.reader.next_offset;
+                    self.next_token = Some(token);
+                }
+                Err(e) => {
+                    self.failed = true;
+                    return Err(e);
+                }
+            }
         }
         Ok(self.next_token.as_ref().unwrap().clone())
     }
 
     /// Read next token
-    pub fn read(&mut self) -> Result<Token, Box<dyn Error>> {
+    pub fn read(&mut self) -> Result<Token<'s>, Box<dyn Error>> {
         let t = self.peek()?;
         self.current_token = self.next_token.clone().unwrap();
+        self.current_span = self.next_span.clone();
         self.next_token = None;
         Ok(t)
     }
+
+    /// Position and byte range of the token at a position
+    ///
+    /// The token is the current or the peeked one, or the last read character if the next token
+    /// could not be read.
+    pub fn span(&self, position: Position) -> (Position, Range<usize>) {
+        match &self.next_token {
+            Some(token) if token.position == position => (position, self.next_span.clone()),
+            _ if self.failed => (
+                self.reader.current_position,
+                self.reader.current_offset..self.reader.next_offset,
+            ),
+            _ => (self.current_token.position, self.current_span.clone()),
+        }
+    }
+
+    /// Skip the rest of the current line after an error
+    ///
+    /// Nothing is skipped when the error is at the end of a line.
+    pub fn recover(&mut self) -> Result<(), Box<dyn Error>> {
+        if !self.failed {
+            match self.next_token.as_ref().map(|token| &token.kind) {
+                Some(Kind::NewLine) => {
+                    self.read()?;
+                    return Ok(());
+                }
+                Some(Kind::EndOfFile) => return Ok(()),
+                // The error is on the token after a new line
+                None if matches!(self.current_token.kind, Kind::NewLine | Kind::EndOfFile) => {
+                    return Ok(());
+                }
+                _ => {}
+            }
+        }
+        self.failed = false;
+        self.next_token = None;
+        loop {
+            match self.reader.read() {
+                '\u{0}' => break,
+                '\n' => {
+                    self.current_token = Token {
+                        kind: Kind::NewLine,
+                        position: self.reader.current_position,
+                    };
+                    break;
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
 }
 
 #[allow(unused_imports)]
 mod tests {
-    use pnets::timed::Bound;
+    use pnets::timed::{Bound, Time};
 
     use crate::lexer::Lexer;
     use crate::parser::Position;
@@ -288,19 +396,26 @@ mod tests {
 
     #[test]
     fn test_complex_identifier() {
-        let mut lexer = Lexer::new("{Complex \\{\\}\\\\ identifier}".as_bytes());
+        let mut lexer = Lexer::new("{Complex \\{\\}\\\\ identifier}");
         assert_eq!(
             lexer.read().unwrap(),
             Token {
-                kind: Kind::Identifier("Complex {}\\ identifier".to_string()),
+                kind: Kind::Identifier("Complex {}\\ identifier".into()),
                 position: Position { line: 1, column: 1 },
             }
         )
     }
 
+    #[test]
+    fn test_escaped_backslash() {
+        let mut lexer = Lexer::new("{a\\\\\\}} {\\\\}");
+        assert_eq!(lexer.read().unwrap().kind, Kind::Identifier("a\\}".into()));
+        assert_eq!(lexer.read().unwrap().kind, Kind::Identifier("\\".into()));
+    }
+
     #[test]
     fn test_invalid_identifier() {
-        let mut lexer = Lexer::new("{{}".as_bytes());
+        let mut lexer = Lexer::new("{{}");
         let r = lexer.read();
         assert!(r.is_err());
     }
@@ -308,7 +423,7 @@ mod tests {
     #[test]
     #[allow(unused_must_use)]
     fn test_peek() {
-        let mut lexer = Lexer::new("\nnet *".as_bytes());
+        let mut lexer = Lexer::new("\nnet *");
         lexer.peek();
         assert_eq!(
             lexer.peek().unwrap(),
@@ -329,7 +444,7 @@ mod tests {
 
     #[test]
     fn test_newline() {
-        let mut lexer = Lexer::new("\n\n".as_bytes());
+        let mut lexer = Lexer::new("\n\n");
         assert_eq!(
             lexer.parse_next_token().unwrap(),
             Token {
@@ -348,7 +463,7 @@ mod tests {
 
     #[test]
     fn test_whitespace() {
-        let mut lexer = Lexer::new("   \r\n\t\t\r\n".as_bytes());
+        let mut lexer = Lexer::new("   \r\n\t\t\r\n");
         assert_eq!(
             lexer.parse_next_token().unwrap(),
             Token {
@@ -367,7 +482,7 @@ mod tests {
 
     #[test]
     fn test_tr() {
-        let mut lexer = Lexer::new("tr".as_bytes());
+        let mut lexer = Lexer::new("tr");
         assert_eq!(
             lexer.parse_next_token().unwrap(),
             Token {
@@ -379,7 +494,7 @@ mod tests {
 
     #[test]
     fn test_gt() {
-        let mut lexer = Lexer::new(">".as_bytes());
+        let mut lexer = Lexer::new(">");
         assert_eq!(
             lexer.parse_next_token().unwrap(),
             Token {
@@ -391,7 +506,7 @@ mod tests {
 
     #[test]
     fn test_lt() {
-        let mut lexer = Lexer::new("<".as_bytes());
+        let mut lexer = Lexer::new("<");
         assert_eq!(
             lexer.parse_next_token().unwrap(),
             Token {
@@ -403,11 +518,11 @@ mod tests {
 
     #[test]
     fn test_comments() {
-        let mut lexer = Lexer::new("#tr\nnet  net".as_bytes());
+        let mut lexer = Lexer::new("#tr\nnet  net");
         assert_eq!(
             lexer.parse_next_token().unwrap(),
             Token {
-                kind: Kind::Comment("tr".to_string()),
+                kind: Kind::Comment("tr".into()),
                 position: Position { line: 1, column: 1 },
             }
         );
@@ -429,7 +544,7 @@ mod tests {
 
     #[test]
     fn test_net() {
-        let mut lexer = Lexer::new("net".as_bytes());
+        let mut lexer = Lexer::new("net");
         assert_eq!(
             lexer.parse_next_token().unwrap(),
             Token {
@@ -441,7 +556,7 @@ mod tests {
 
     #[test]
     fn test_pl() {
-        let mut lexer = Lexer::new("pl".as_bytes());
+        let mut lexer = Lexer::new("pl");
         assert_eq!(
             lexer.parse_next_token().unwrap(),
             Token {
@@ -453,7 +568,7 @@ mod tests {
 
     #[test]
     fn test_nt() {
-        let mut lexer = Lexer::new("nt".as_bytes());
+        let mut lexer = Lexer::new("nt");
         assert_eq!(
             lexer.parse_next_token().unwrap(),
             Token {
@@ -465,7 +580,7 @@ mod tests {
 
     #[test]
     fn test_lb() {
-        let mut lexer = Lexer::new("lb".as_bytes());
+        let mut lexer = Lexer::new("lb");
         assert_eq!(
             lexer.parse_next_token().unwrap(),
             Token {
@@ -477,7 +592,7 @@ mod tests {
 
     #[test]
     fn test_pr() {
-        let mut lexer = Lexer::new("pr".as_bytes());
+        let mut lexer = Lexer::new("pr");
         assert_eq!(
             lexer.parse_next_token().unwrap(),
             Token {
@@ -489,7 +604,7 @@ mod tests {
 
     #[test]
     fn test_stopwatch() {
-        let mut lexer = Lexer::new("!".as_bytes());
+        let mut lexer = Lexer::new("!");
         assert_eq!(
             lexer.parse_next_token().unwrap(),
             Token {
@@ -501,7 +616,7 @@ mod tests {
 
     #[test]
     fn test_stopwatchinhibitor() {
-        let mut lexer = Lexer::new("!-".as_bytes());
+        let mut lexer = Lexer::new("!-");
         assert_eq!(
             lexer.parse_next_token().unwrap(),
             Token {
@@ -513,11 +628,11 @@ mod tests {
 
     #[test]
     fn test_identifier() {
-        let mut lexer = Lexer::new("label_comp'lex".as_bytes());
+        let mut lexer = Lexer::new("label_comp'lex");
         assert_eq!(
             lexer.parse_next_token().unwrap(),
             Token {
-                kind: Kind::Identifier("label_comp'lex".to_string()),
+                kind: Kind::Identifier("label_comp'lex".into()),
                 position: Position { line: 1, column: 1 },
             }
         );
@@ -525,25 +640,31 @@ mod tests {
 
     #[test]
     fn test_time_interval() {
-        let mut lexer = Lexer::new("[0,1][0,w[]0,1[".as_bytes());
+        let mut lexer = Lexer::new("[0,1][0,w[]0,1[");
         assert_eq!(
             lexer.parse_next_token().unwrap(),
             Token {
-                kind: Kind::TimeInterval(Bound::Closed(0), Bound::Closed(1)),
+                kind: Kind::TimeInterval(
+                    Bound::Closed(Time::from_integer(0)),
+                    Bound::Closed(Time::from_integer(1))
+                ),
                 position: Position { line: 1, column: 1 },
             }
         );
         assert_eq!(
             lexer.parse_next_token().unwrap(),
             Token {
-                kind: Kind::TimeInterval(Bound::Closed(0), Bound::Infinity),
+                kind: Kind::TimeInterval(Bound::Closed(Time::from_integer(0)), Bound::Infinity),
                 position: Position { line: 1, column: 6 },
             }
         );
         assert_eq!(
             lexer.parse_next_token().unwrap(),
             Token {
-                kind: Kind::TimeInterval(Bound::Open(0), Bound::Open(1)),
+                kind: Kind::TimeInterval(
+                    Bound::Open(Time::from_integer(0)),
+                    Bound::Open(Time::from_integer(1))
+                ),
                 position: Position {
                     line: 1,
                     column: 11,
@@ -552,9 +673,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_rational_time_interval() {
+        let mut lexer = Lexer::new("[0.5, 3/2] ]1K , (2/4M)[ [1/0,2]");
+        assert_eq!(
+            lexer.parse_next_token().unwrap().kind,
+            Kind::TimeInterval(
+                Bound::Closed(Time::new(1, 2)),
+                Bound::Closed(Time::new(3, 2))
+            )
+        );
+        assert_eq!(
+            lexer.parse_next_token().unwrap().kind,
+            Kind::TimeInterval(
+                Bound::Open(Time::from_integer(1_000)),
+                Bound::Open(Time::from_integer(500_000))
+            )
+        );
+        assert!(lexer.parse_next_token().is_err());
+    }
+
+    #[test]
+    fn test_large_time_interval() {
+        let mut lexer = Lexer::new("[0,18446744073709551615] [0,18446744073709551615K]");
+        assert_eq!(
+            lexer.parse_next_token().unwrap().kind,
+            Kind::TimeInterval(
+                Bound::Closed(Time::from_integer(0)),
+                Bound::Closed(Time::from_integer(u64::MAX))
+            )
+        );
+        assert!(lexer.parse_next_token().is_err());
+    }
+
     #[test]
     fn test_arrow() {
-        let mut lexer = Lexer::new("->".as_bytes());
+        let mut lexer = Lexer::new("->");
         assert_eq!(
             lexer.parse_next_token().unwrap(),
             Token {
@@ -566,13 +720,13 @@ mod tests {
 
     #[test]
     fn test_invalid_arrow() {
-        let mut lexer = Lexer::new("-a".as_bytes());
+        let mut lexer = Lexer::new("-a");
         assert!(lexer.parse_next_token().is_err());
     }
 
     #[test]
     fn test_full_line() {
-        let mut lexer = Lexer::new("tr t0 : a [1,1] p0*3 -> p1".as_bytes());
+        let mut lexer = Lexer::new("tr t0 : a [1,1] p0*3 -> p1");
         assert_eq!(
             lexer.parse_next_token().unwrap(),
             Token {
@@ -583,7 +737,7 @@ mod tests {
         assert_eq!(
             lexer.parse_next_token().unwrap(),
             Token {
-                kind: Kind::Identifier("t0".to_string()),
+                kind: Kind::Identifier("t0".into()),
                 position: Position { line: 1, column: 4 },
             }
         );
@@ -598,14 +752,17 @@ mod tests {
         assert_eq!(
             lexer.parse_next_token().unwrap(),
             Token {
-                kind: Kind::Identifier("a".to_string()),
+                kind: Kind::Identifier("a".into()),
                 position: Position { line: 1, column: 9 },
             }
         );
         assert_eq!(
             lexer.parse_next_token().unwrap(),
             Token {
-                kind: Kind::TimeInterval(Bound::Closed(1), Bound::Closed(1)),
+                kind: Kind::TimeInterval(
+                    Bound::Closed(Time::from_integer(1)),
+                    Bound::Closed(Time::from_integer(1))
+                ),
                 position: Position {
                     line: 1,
                     column: 11,
@@ -615,7 +772,7 @@ mod tests {
         assert_eq!(
             lexer.parse_next_token().unwrap(),
             Token {
-                kind: Kind::Identifier("p0".to_string()),
+                kind: Kind::Identifier("p0".into()),
                 position: Position {
                     line: 1,
                     column: 17,
@@ -635,7 +792,7 @@ mod tests {
         assert_eq!(
             lexer.parse_next_token().unwrap(),
             Token {
-                kind: Kind::Identifier("3".to_string()),
+                kind: Kind::Identifier("3".into()),
                 position: Position {
                     line: 1,
                     column: 20,
@@ -655,7 +812,7 @@ mod tests {
         assert_eq!(
             lexer.parse_next_token().unwrap(),
             Token {
-                kind: Kind::Identifier("p1".to_string()),
+                kind: Kind::Identifier("p1".into()),
                 position: Position {
                     line: 1,
                     column: 25,
@@ -664,21 +821,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_recover() {
+        let mut lexer = Lexer::new("tr -x p\npl p -> t\nnet");
+        assert_eq!(lexer.read().unwrap().kind, Kind::Transition);
+        assert!(lexer.peek().is_err());
+        assert_eq!(lexer.span(Position { line: 1, column: 5 }).1, 4..5);
+        lexer.recover().unwrap();
+        assert_eq!(lexer.read().unwrap().kind, Kind::Place);
+        assert_eq!(lexer.current_span, 8..10);
+        assert_eq!(lexer.read().unwrap().kind, Kind::Identifier("p".into()));
+        lexer.recover().unwrap();
+        assert_eq!(lexer.read().unwrap().kind, Kind::Net);
+    }
+
     #[test]
     fn test_invalid_time_interval() {
-        let mut lexer = Lexer::new("a".as_bytes());
+        let mut lexer = Lexer::new("a");
         assert!(lexer.parse_time_interval().is_err());
-        lexer = Lexer::new("[a,w]".as_bytes());
+        lexer = Lexer::new("[a,w]");
         assert!(lexer.parse_time_interval().is_err());
-        lexer = Lexer::new("[45w]".as_bytes());
+        lexer = Lexer::new("[45w]");
         assert!(lexer.parse_time_interval().is_err());
-        lexer = Lexer::new("[45,34a".as_bytes());
+        lexer = Lexer::new("[45,34a");
         assert!(lexer.parse_time_interval().is_err());
-        lexer = Lexer::new("[45,a]".as_bytes());
+        lexer = Lexer::new("[45,a]");
         assert!(lexer.parse_time_interval().is_err());
-        lexer = Lexer::new("[45,w]".as_bytes());
+        lexer = Lexer::new("[45,w]");
         assert!(lexer.parse_time_interval().is_err());
-        lexer = Lexer::new("[45,wa".as_bytes());
+        lexer = Lexer::new("[45,wa");
         assert!(lexer.parse_time_interval().is_err());
     }
 }
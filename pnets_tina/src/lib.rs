@@ -3,14 +3,21 @@
 //! [tina](http://projects.laas.fr/tina/manuals/formats.html#2) format for
 //! [pnets](https://crates.io/crates/pnets) framework.
 
+pub use diagnostic::{Diagnostic, Span};
 pub use errors::ParserError;
-pub use export::{Exporter, ExporterBuilder};
-pub use parser::Parser;
+pub use export::{Export, Exporter, ExporterBuilder, Order};
+pub use ndr::{Layout, NdrExporter, NdrParser, NoteId, Point};
+pub use parser::{Parser, Position};
+pub use visitor::{ArcKind, Visitor};
 
+mod builder;
+mod diagnostic;
 mod errors;
 mod export;
 mod lexer;
+mod ndr;
 mod parser;
 mod reader;
 
 mod token;
+mod visitor;
@@ -0,0 +1,533 @@
+//! Graphical format of the nd editor of tina
+//!
+//! A `.ndr` file describes a net as it is drawn: each node has a position and an anchor for its
+//! name. The net itself is stored in a [`timed::Net`] and the drawing in a [`Layout`].
+//!
+//! ```text
+//! p x y name marking anchor [label anchor]                place
+//! t x y name eft lft anchor [label anchor]                transition, lft is `w` for infinity
+//! t x y name interval anchor [label anchor]               transition with an open bound
+//! e from [angle radius] to [angle radius] weight anchor   arc
+//! n x y name justification text                           note
+//! pr names > names                                        priorities
+//! h name                                                  name of the net
+//! ```
+//!
+//! Arcs go from a place to a transition or from a transition to a place. The weight of an arc
+//! from a place is prefixed by `?`, `?-`, `!` or `!-` for test, inhibitor, stopwatch and
+//! stopwatch inhibitor arcs. The shape of arcs and the anchors of names are not kept.
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::io::{BufRead, ErrorKind, Write};
+
+use pnets::metadata::Note;
+use pnets::timed::{Bound, TimeRange};
+use pnets::{timed, NetError, NodeId};
+
+use crate::builder::NetBuilder;
+use crate::export::Export;
+use crate::parser::Position;
+use crate::visitor::{ArcKind, Visitor};
+use crate::ParserError;
+
+/// Position of an element in a drawing
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Point {
+    /// Horizontal position, from the left
+    pub x: f64,
+    /// Vertical position, from the top
+    pub y: f64,
+}
+
+/// Note of the [metadata][`pnets::metadata::Metadata`] of a net
+///
+/// Several notes can have the same name, so notes are identified by their index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NoteId {
+    /// Note about the whole net, by index in [`pnets::metadata::Metadata::notes`]
+    Net(usize),
+    /// Note attached to a node, by index in the notes of the node
+    Node(NodeId, usize),
+}
+
+/// Positions of the nodes and notes of a drawn net
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Layout {
+    /// Position of places and transitions
+    pub nodes: HashMap<NodeId, Point>,
+    /// Position of notes
+    pub notes: HashMap<NoteId, Point>,
+}
+
+/// Word of a line with its position
+struct Word {
+    text: String,
+    position: Position,
+}
+
+/// Split a line in words, a word between `{}` can contain spaces and escaped characters
+fn split(line: &str, number: usize) -> Result<Vec<Word>, Box<dyn Error>> {
+    let mut words = vec![];
+    let mut chars = line.chars().enumerate().peekable();
+    while let Some(&(column, c)) = chars.peek() {
+        let position = Position {
+            line: number,
+            column: column + 1,
+        };
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        let mut text = String::new();
+        if c == '{' {
+            chars.next();
+            loop {
+                match chars.next() {
+                    Some((_, '}')) => break,
+                    Some((_, '\\')) => match chars.next() {
+                        Some((_, c)) => text.push(c),
+                        None => break,
+                    },
+                    Some((_, c)) => text.push(c),
+                    None => {
+                        return Err(Box::new(ParserError::InvalidChar(
+                            position,
+                            "expected }".to_string(),
+                        )))
+                    }
+                }
+            }
+        } else {
+            while let Some(&(_, c)) = chars.peek() {
+                if c.is_whitespace() {
+                    break;
+                }
+                text.push(c);
+                chars.next();
+            }
+        }
+        words.push(Word { text, position });
+    }
+    Ok(words)
+}
+
+/// Parser for the `.ndr` format
+///
+/// ```
+/// use pnets_tina::NdrParser;
+///
+/// let (net, layout) = NdrParser::new("p 10.0 20.0 {p0} 1 n\nh {net}\n".as_bytes())
+///     .parse()
+///     .unwrap();
+/// assert_eq!(net.name, "net");
+/// assert_eq!(layout.nodes.len(), 1);
+/// ```
+pub struct NdrParser<R: BufRead> {
+    reader: R,
+}
+
+impl<R: BufRead> NdrParser<R> {
+    /// Create a new parser from a buffered reader
+    pub fn new(reader: R) -> Self {
+        Self { reader }
+    }
+
+    /// Parse a net and its layout
+    ///
+    /// Use [`pnets::standard::Net::from`] to get a standard net.
+    pub fn parse(self) -> Result<(timed::Net, Layout), Box<dyn Error>> {
+        let mut lines = vec![];
+        for (number, line) in self.reader.lines().enumerate() {
+            let line = match line {
+                Ok(line) => line,
+                Err(e) if e.kind() == ErrorKind::InvalidData => {
+                    return Err(Box::new(ParserError::Utf8Error(Position {
+                        line: number + 1,
+                        column: 0,
+                    })))
+                }
+                Err(e) => return Err(Box::new(e)),
+            };
+            let words = split(&line, number + 1)?;
+            if !words.is_empty() && !words[0].text.starts_with('#') {
+                lines.push(words);
+            }
+        }
+
+        let mut builder = NetBuilder::default();
+        let mut nodes = vec![];
+        let mut notes = vec![];
+        let mut transitions = HashSet::new();
+        // Nodes are declared first so that arcs can find the kind of their ends
+        for words in &lines {
+            match words[0].text.as_str() {
+                "p" => {
+                    let point = Self::point(words)?;
+                    let label = Self::label(words, 6)?;
+                    builder.place(Self::word(words, 3)?, label, Self::number(words, 4)?)?;
+                    nodes.push((words[3].text.as_str(), point));
+                }
+                "t" => {
+                    let point = Self::point(words)?;
+                    let (time, next) = Self::time(words)?;
+                    let label = Self::label(words, next + 1)?;
+                    builder.transition(Self::word(words, 3)?, label, Some(time))?;
+                    nodes.push((words[3].text.as_str(), point));
+                    transitions.insert(words[3].text.as_str());
+                }
+                "n" => {
+                    let point = Self::point(words)?;
+                    let name = Self::word(words, 3)?;
+                    builder.note(name, Self::number(words, 4)?, Self::word(words, 5)?)?;
+                    notes.push((name, point));
+                }
+                "h" => builder.net(Self::word(words, 1)?)?,
+                "e" | "pr" => {}
+                _ => {
+                    return Err(Box::new(ParserError::UnexpectedIdentifier(
+                        words[0].position,
+                        words[0].text.clone(),
+                    )))
+                }
+            }
+        }
+        for words in &lines {
+            match words[0].text.as_str() {
+                "e" => {
+                    let from = Self::word(words, 1)?;
+                    // The angle and the radius of a bent end follow its name
+                    let to = match words.len() {
+                        9 => 4,
+                        7 if !nodes.iter().any(|&(name, _)| name == words[2].text) => 4,
+                        _ => 2,
+                    };
+                    let weight = &words[words.len() - 2];
+                    let (kind, weight) = match (transitions.contains(from), &weight.text) {
+                        (true, weight) => (ArcKind::Produce, weight.as_str()),
+                        (false, w) if w.starts_with("?-") => (ArcKind::Inhibitor, &w[2..]),
+                        (false, w) if w.starts_with("!-") => (ArcKind::StopWatchInhibitor, &w[2..]),
+                        (false, w) if w.starts_with('?') => (ArcKind::Test, &w[1..]),
+                        (false, w) if w.starts_with('!') => (ArcKind::StopWatch, &w[1..]),
+                        (false, w) => (ArcKind::Consume, w.as_str()),
+                    };
+                    let weight = weight.parse().map_err(|_| {
+                        ParserError::InvalidChar(
+                            words[words.len() - 2].position,
+                            "expected a weight".to_string(),
+                        )
+                    })?;
+                    let to = Self::word(words, to)?;
+                    match kind {
+                        ArcKind::Produce => builder.arc(to, from, kind, weight)?,
+                        _ => builder.arc(from, to, kind, weight)?,
+                    }
+                }
+                "pr" => {
+                    let names: Vec<&str> = words[1..].iter().map(|w| w.text.as_str()).collect();
+                    match names.iter().position(|&name| name == ">") {
+                        Some(split) => builder.priority(&names[..split], &names[split + 1..])?,
+                        None => {
+                            return Err(Box::new(ParserError::InvalidChar(
+                                words[0].position,
+                                "expected >".to_string(),
+                            )))
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        builder.finish()?;
+
+        let mut layout = Layout::default();
+        for (name, point) in nodes {
+            layout
+                .nodes
+                .insert(builder.net.get_index_by_name(name).unwrap(), point);
+        }
+        // Notes are numbered in the order of the file, as they are added to the metadata
+        let mut net_notes = 0;
+        let mut node_notes: HashMap<NodeId, usize> = HashMap::new();
+        for (name, point) in notes {
+            let id = match builder.net.get_index_by_name(name) {
+                Some(node) => {
+                    let index = node_notes.entry(node).or_default();
+                    *index += 1;
+                    NoteId::Node(node, *index - 1)
+                }
+                None => {
+                    net_notes += 1;
+                    NoteId::Net(net_notes - 1)
+                }
+            };
+            layout.notes.insert(id, point);
+        }
+        Ok((builder.net, layout))
+    }
+
+    /// Word of a line
+    fn word(words: &[Word], index: usize) -> Result<&str, Box<dyn Error>> {
+        match words.get(index) {
+            Some(word) => Ok(&word.text),
+            None => Err(Box::new(ParserError::InvalidChar(
+                words[words.len() - 1].position,
+                "unexpected end of line".to_string(),
+            ))),
+        }
+    }
+
+    /// Integer of a line
+    fn number(words: &[Word], index: usize) -> Result<usize, Box<dyn Error>> {
+        Self::word(words, index)?.parse().map_err(|_| {
+            Box::new(ParserError::InvalidChar(
+                words[index].position,
+                "expected an integer".to_string(),
+            )) as Box<dyn Error>
+        })
+    }
+
+    /// Position of a node or a note, after the kind of the line
+    fn point(words: &[Word]) -> Result<Point, Box<dyn Error>> {
+        let mut coordinates = [0.; 2];
+        for (index, coordinate) in coordinates.iter_mut().enumerate() {
+            *coordinate = Self::word(words, index + 1)?.parse().map_err(|_| {
+                Box::new(ParserError::InvalidChar(
+                    words[index + 1].position,
+                    "expected a coordinate".to_string(),
+                )) as Box<dyn Error>
+            })?;
+        }
+        Ok(Point {
+            x: coordinates[0],
+            y: coordinates[1],
+        })
+    }
+
+    /// Optional label of a node, followed by its anchor
+    fn label(words: &[Word], index: usize) -> Result<Option<&str>, Box<dyn Error>> {
+        match words.get(index) {
+            Some(word) => {
+                Self::word(words, index + 1)?;
+                Ok(Some(&word.text))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Time range of a transition and the index of the anchor of its name
+    fn time(words: &[Word]) -> Result<(TimeRange, usize), Box<dyn Error>> {
+        let eft = Self::word(words, 4)?;
+        let (range, next) = if eft.starts_with(&['[', ']'][..]) {
+            (eft.to_string(), 5)
+        } else {
+            match Self::word(words, 5)? {
+                "w" => (format!("[{},w[", eft), 6),
+                lft => (format!("[{},{}]", eft, lft), 6),
+            }
+        };
+        match range.parse() {
+            Ok(time) => Ok((time, next)),
+            Err(_) => Err(Box::new(ParserError::InvalidChar(
+                words[4].position,
+                "expected a time interval".to_string(),
+            ))),
+        }
+    }
+}
+
+/// Exporter for the `.ndr` format
+///
+/// Nodes and notes without position in the layout are placed on a grid below the drawing.
+/// Deleted nodes are not written.
+pub struct NdrExporter<'w> {
+    writer: &'w mut dyn Write,
+}
+
+impl<'w> NdrExporter<'w> {
+    /// Create a new exporter
+    pub fn new(writer: &'w mut dyn Write) -> Self {
+        Self { writer }
+    }
+
+    fn escape(s: &str) -> String {
+        format!(
+            "{{{}}}",
+            s.replace("\\", "\\\\")
+                .replace("{", "\\{")
+                .replace("}", "\\}")
+        )
+    }
+
+    /// Export a net with the positions of its elements
+    ///
+    /// # Errors
+    /// Return [`NetError::UnsupportedArc`] if the net has reset arcs
+    pub fn export(&mut self, net: &dyn Export, layout: &Layout) -> Result<(), Box<dyn Error>> {
+        if let Some(arc) = net.unsupported_arc() {
+            return Err(Box::new(NetError::UnsupportedArc(arc)));
+        }
+        let bottom = layout
+            .nodes
+            .values()
+            .chain(layout.notes.values())
+            .map(|point| point.y)
+            .fold(0., f64::max);
+        let mut placed = 0;
+        let mut place = |point: Option<&Point>| match point {
+            Some(&point) => point,
+            None => {
+                placed += 1;
+                Point {
+                    x: (100 * ((placed - 1) % 10 + 1)) as f64,
+                    y: bottom + (100 * ((placed - 1) / 10 + 1)) as f64,
+                }
+            }
+        };
+
+        let places: Vec<_> = net
+            .place_ids()
+            .into_iter()
+            .filter(|&pl| !net.is_node_deleted(NodeId::Place(pl)))
+            .collect();
+        let transitions: Vec<_> = net
+            .transition_ids()
+            .into_iter()
+            .filter(|&tr| !net.is_node_deleted(NodeId::Transition(tr)))
+            .collect();
+
+        for &pl in &places {
+            let node = NodeId::Place(pl);
+            let point = place(layout.nodes.get(&node));
+            self.writer.write_all(
+                format!(
+                    "p {:?} {:?} {} {} n",
+                    point.x,
+                    point.y,
+                    Self::escape(&net.node_name(node)),
+                    net.place_initial(pl)
+                )
+                .as_ref(),
+            )?;
+            self.label(net, node)?;
+        }
+        for &tr in &transitions {
+            let node = NodeId::Transition(tr);
+            let point = place(layout.nodes.get(&node));
+            let time = net.transition_time(tr);
+            let time = match (time.start, time.end) {
+                (Bound::Closed(eft), Bound::Infinity) => format!("{} w", eft),
+                (Bound::Closed(eft), Bound::Closed(lft)) => format!("{} {}", eft, lft),
+                _ => time.to_string(),
+            };
+            self.writer.write_all(
+                format!(
+                    "t {:?} {:?} {} {} n",
+                    point.x,
+                    point.y,
+                    Self::escape(&net.node_name(node)),
+                    time
+                )
+                .as_ref(),
+            )?;
+            self.label(net, node)?;
+        }
+
+        let metadata = net.net_metadata();
+        let mut notes: Vec<(String, NoteId, &Note)> = metadata
+            .notes
+            .iter()
+            .enumerate()
+            .map(|(index, note)| (note.name.clone(), NoteId::Net(index), note))
+            .collect();
+        for node in places
+            .iter()
+            .map(|&pl| NodeId::Place(pl))
+            .chain(transitions.iter().map(|&tr| NodeId::Transition(tr)))
+        {
+            for (index, note) in metadata.node_notes(node).iter().enumerate() {
+                notes.push((net.node_name(node), NoteId::Node(node, index), note));
+            }
+        }
+        for (name, id, note) in notes {
+            let point = place(layout.notes.get(&id));
+            self.writer.write_all(
+                format!(
+                    "n {:?} {:?} {} {} {}\n",
+                    point.x,
+                    point.y,
+                    Self::escape(&name),
+                    note.justification,
+                    Self::escape(&note.text)
+                )
+                .as_ref(),
+            )?;
+        }
+
+        for &tr in &transitions {
+            let transition = Self::escape(&net.node_name(NodeId::Transition(tr)));
+            for &(kind, prefix) in &[
+                (ArcKind::Consume, ""),
+                (ArcKind::Test, "?"),
+                (ArcKind::Inhibitor, "?-"),
+                (ArcKind::StopWatch, "!"),
+                (ArcKind::StopWatchInhibitor, "!-"),
+                (ArcKind::Produce, ""),
+            ] {
+                for &(pl, weight) in net.transition_arcs(tr, kind) {
+                    let place = Self::escape(&net.node_name(NodeId::Place(pl)));
+                    let (from, to) = match kind {
+                        ArcKind::Produce => (&transition, &place),
+                        _ => (&place, &transition),
+                    };
+                    self.writer.write_all(
+                        format!("e {} {} {}{} n\n", from, to, prefix, weight).as_ref(),
+                    )?;
+                }
+            }
+        }
+
+        // Only the priorities which are not implied by transitivity are written
+        if let Some(priorities) = net.priorities() {
+            let priorities = priorities.transitive_reduction()?;
+            for &tr in &transitions {
+                let lower = priorities.priorities(tr);
+                if !lower.is_empty() {
+                    self.writer.write_all(
+                        format!(
+                            "pr {} >",
+                            Self::escape(&net.node_name(NodeId::Transition(tr)))
+                        )
+                        .as_ref(),
+                    )?;
+                    for &lower in lower {
+                        self.writer.write_all(
+                            format!(
+                                " {}",
+                                Self::escape(&net.node_name(NodeId::Transition(lower)))
+                            )
+                            .as_ref(),
+                        )?;
+                    }
+                    self.writer.write_all("\n".as_ref())?;
+                }
+            }
+        }
+
+        if !net.net_name().is_empty() {
+            self.writer
+                .write_all(format!("h {}\n", Self::escape(net.net_name())).as_ref())?;
+        }
+        Ok(())
+    }
+
+    /// End the line of a node with its label
+    fn label(&mut self, net: &dyn Export, node: NodeId) -> Result<(), Box<dyn Error>> {
+        match net.node_label(node) {
+            Some(label) => self
+                .writer
+                .write_all(format!(" {} s\n", Self::escape(label)).as_ref())?,
+            None => self.writer.write_all("\n".as_ref())?,
+        }
+        Ok(())
+    }
+}
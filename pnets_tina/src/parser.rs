@@ -1,12 +1,13 @@
+use std::borrow::Cow;
 use std::error::Error;
 
 use pnets::timed::{Net, TimeRange};
-use pnets::{arc, NetError, NodeId};
-use pnets::{PlaceId, TransitionId};
 
+use crate::builder::NetBuilder;
+use crate::diagnostic::{Diagnostic, Span};
 use crate::lexer::Lexer;
 use crate::token;
-use crate::token::Kind;
+use crate::visitor::{ArcKind, Visitor};
 use crate::ParserError;
 use std::io::BufRead;
 
@@ -21,193 +22,267 @@ pub struct Position {
 
 /// Exporter for [tina]() format.
 ///
-/// It consume a reader and creates a [`pnets::timed::Net`]
+/// It consume a reader and creates a [`pnets::timed::Net`], or emits the elements of the net to
+/// a [`Visitor`]. The whole reader is buffered before parsing, then the net is lexed from this
+/// buffer without copying identifiers.
 pub struct Parser<R: BufRead> {
-    lexer: Lexer<R>,
-    net: Net,
+    reader: R,
 }
 
-impl<R: BufRead> Parser<R> {
-    /// Parse a timed net from a reader
-    pub fn parse(mut self) -> Result<Net, Box<dyn Error>> {
-        loop {
-            let token = self.lexer.peek()?;
-
-            match token.kind {
-                token::Kind::NewLine | token::Kind::Comment(_) => {
-                    self.lexer.read()?;
-                }
-                token::Kind::Net => self.parse_net()?,
-                token::Kind::Transition => self.parse_transition()?,
-                token::Kind::Place => self.parse_place()?,
-                token::Kind::Note => self.parse_note()?,
-                token::Kind::Label => self.parse_label()?,
-                token::Kind::Priority => self.parse_priority()?,
-                token::Kind::EndOfFile => break,
-                _ => {
-                    return Err(Box::new(ParserError::UnexpectedToken(
-                        token,
-                        "This token can not start a line".to_string(),
-                    )));
-                }
-            }
-        }
-        Ok(self.net)
-    }
+/// State of the parser while it reads a source
+struct SourceParser<'s, 'v> {
+    lexer: Lexer<'s>,
+    visitor: &'v mut dyn Visitor,
+    /// Set when the current line declares an element, a comment at its end is inline
+    declared: bool,
+}
 
+impl<R: BufRead> Parser<R> {
     /// Create a new parser from reader
     ///
     /// ```ignore
     /// let parser = Parser::new(&"net Réseau\ntr t0 p0 -> p1\ntr t1 p1 -> p0".as_bytes());
     /// ```
     pub fn new(reader: R) -> Self {
-        Self {
-            lexer: Lexer::new(reader),
-            net: Net::default(),
-        }
+        Self { reader }
     }
 
-    /// Get transition from net or create one
-    fn get_or_create_transition(&mut self, name: &str) -> Result<TransitionId, Box<dyn Error>> {
-        match self.net.get_index_by_name(name) {
-            Some(NodeId::Transition(id)) => Ok(id),
-            Some(NodeId::Place(_)) => Err(Box::new(NetError::DuplicatedName(name.to_string()))),
-            None => {
-                let tr = self.net.create_transition();
-                self.net.rename_node(NodeId::Transition(tr), name)?;
-                Ok(tr)
-            }
+    /// Parse a timed net from a reader
+    ///
+    /// Returns the first error of the net, use [`Parser::parse_with_diagnostics`] to get all of
+    /// them.
+    pub fn parse(self) -> Result<Net, Box<dyn Error>> {
+        self.parse_with_diagnostics()
+            .map_err(|mut diagnostics| diagnostics.remove(0).error)
+    }
+
+    /// Parse a timed net from a reader and report all errors
+    ///
+    /// After an error, the parser skips the rest of the line and continues with the next one.
+    /// Errors of the reader and invalid utf-8 stop the parsing.
+    pub fn parse_with_diagnostics(self) -> Result<Net, Vec<Diagnostic>> {
+        let mut builder = NetBuilder::default();
+        let mut diagnostics = match self.visit_with_diagnostics(&mut builder) {
+            Ok(()) => vec![],
+            Err(diagnostics) => diagnostics,
+        };
+        if let Err(error) = builder.finish() {
+            diagnostics.push(Diagnostic { error, span: None });
+        }
+        if diagnostics.is_empty() {
+            Ok(builder.net)
+        } else {
+            Err(diagnostics)
         }
     }
 
-    /// Get place from net or create one
-    fn get_or_create_place(&mut self, name: &str) -> Result<PlaceId, Box<dyn Error>> {
-        match self.net.get_index_by_name(name) {
-            Some(NodeId::Place(id)) => Ok(id),
-            Some(NodeId::Transition(_)) => {
-                Err(Box::new(NetError::DuplicatedName(name.to_string())))
-            }
-            None => {
-                let pl = self.net.create_place();
-                self.net.rename_node(NodeId::Place(pl), name)?;
-                Ok(pl)
+    /// Emit the elements of the net to a visitor
+    ///
+    /// Returns the first error of the net, use [`Parser::visit_with_diagnostics`] to get all of
+    /// them.
+    pub fn visit(self, visitor: &mut dyn Visitor) -> Result<(), Box<dyn Error>> {
+        self.visit_with_diagnostics(visitor)
+            .map_err(|mut diagnostics| diagnostics.remove(0).error)
+    }
+
+    /// Emit the elements of the net to a visitor and report all errors
+    ///
+    /// Errors are recovered as in [`Parser::parse_with_diagnostics`], the visitor receives the
+    /// elements of the lines without errors.
+    pub fn visit_with_diagnostics(
+        mut self,
+        visitor: &mut dyn Visitor,
+    ) -> Result<(), Vec<Diagnostic>> {
+        let mut buffer = vec![];
+        if let Err(error) = self.reader.read_to_end(&mut buffer) {
+            return Err(vec![Diagnostic {
+                error: Box::new(error),
+                span: None,
+            }]);
+        }
+        let source = match std::str::from_utf8(&buffer) {
+            Ok(source) => source,
+            Err(error) => {
+                let offset = error.valid_up_to();
+                // The valid part of the buffer gives the position of the invalid character
+                let valid = std::str::from_utf8(&buffer[..offset]).unwrap();
+                let line_start = valid.rfind('\n').map_or(0, |i| i + 1);
+                let position = Position {
+                    line: valid.matches('\n').count() + 1,
+                    column: valid[line_start..].chars().count() + 1,
+                };
+                return Err(vec![Diagnostic {
+                    error: Box::new(ParserError::Utf8Error(position)),
+                    span: Some(Span {
+                        position,
+                        bytes: offset..offset + error.error_len().unwrap_or(1),
+                    }),
+                }]);
             }
+        };
+        SourceParser::new(source, visitor).parse_with_diagnostics()
+    }
+}
+
+impl<'s, 'v> SourceParser<'s, 'v> {
+    /// Create a new parser for a source
+    fn new(source: &'s str, visitor: &'v mut dyn Visitor) -> Self {
+        Self {
+            lexer: Lexer::new(source),
+            visitor,
+            declared: false,
         }
     }
 
-    /// Parse the label token
-    fn parse_label(&mut self) -> Result<(), Box<dyn Error>> {
-        self.lexer.read()?;
-        let token = self.lexer.read()?;
-        match token.kind {
-            Kind::Identifier(identifier) => {
-                let index = match self.net.get_index_by_name(&identifier) {
-                    None => return Err(Box::new(NetError::UnknownIdentifier(identifier))),
-                    Some(index) => index,
-                };
-                let token = self.lexer.read()?;
-                match token.kind {
-                    Kind::Identifier(identifier) => {
-                        match index {
-                            NodeId::Place(pl) => self.net[pl].label = Some(identifier),
-                            NodeId::Transition(tr) => self.net[tr].label = Some(identifier),
-                        }
-                        Ok(())
+    /// Parse all lines of the source and report all errors
+    fn parse_with_diagnostics(mut self) -> Result<(), Vec<Diagnostic>> {
+        let mut diagnostics = vec![];
+        loop {
+            match self.parse_next() {
+                Ok(true) => {}
+                Ok(false) => break,
+                Err(error) => {
+                    diagnostics.push(self.diagnostic(error));
+                    self.declared = false;
+                    if let Err(error) = self.lexer.recover() {
+                        // The reader can not be read anymore
+                        diagnostics.push(self.diagnostic(error));
+                        break;
                     }
-
-                    _ => Err(Box::new(ParserError::UnexpectedToken(
-                        token,
-                        "Expected TokenKind::Identifier(_)".to_string(),
-                    ))),
                 }
             }
-            _ => Err(Box::new(ParserError::UnexpectedToken(
-                token,
-                "Expected TokenKind::Identifier(_)".to_string(),
-            ))),
+        }
+        if diagnostics.is_empty() {
+            Ok(())
+        } else {
+            Err(diagnostics)
         }
     }
 
-    /// Parse the net token
-    fn parse_net(&mut self) -> Result<(), Box<dyn Error>> {
-        self.lexer.read()?;
-        let identifier = self.lexer.read()?;
-        match identifier.kind {
-            token::Kind::Identifier(id) => {
-                self.net.name = id;
-                Ok(())
-            }
-            _ => Err(Box::new(ParserError::UnexpectedToken(
-                identifier,
-                "Expected TokenKind::Identifier(_)".to_string(),
-            ))),
+    /// Locate an error at the token where it was found
+    fn diagnostic(&self, error: Box<dyn Error>) -> Diagnostic {
+        let position = match error.downcast_ref::<ParserError>() {
+            Some(ParserError::Utf8Error(position))
+            | Some(ParserError::InvalidChar(position, _))
+            | Some(ParserError::UnexpectedIdentifier(position, _))
+            | Some(ParserError::UnexpectedArc(position, _))
+            | Some(ParserError::UnsupportedArc(position, _)) => *position,
+            Some(ParserError::UnexpectedToken(token, _)) => token.position,
+            None => self.lexer.current_token.position,
+        };
+        let (position, bytes) = self.lexer.span(position);
+        Diagnostic {
+            error,
+            span: Some(Span { position, bytes }),
         }
     }
 
-    /// Parse a transition line
-    fn parse_transition(&mut self) -> Result<(), Box<dyn Error>> {
-        self.lexer.read()?;
-        let transition_name = match self.lexer.read()?.kind {
-            token::Kind::Identifier(name) => name,
+    /// Parse the next line, comment or end of line
+    ///
+    /// Returns `false` at the end of the file
+    fn parse_next(&mut self) -> Result<bool, Box<dyn Error>> {
+        let token = self.lexer.peek()?;
+
+        match token.kind {
+            token::Kind::NewLine => {
+                self.lexer.read()?;
+                self.declared = false;
+                return Ok(true);
+            }
+            token::Kind::Comment(comment) => {
+                self.lexer.read()?;
+                self.visitor.comment(&comment, self.declared)?;
+                return Ok(true);
+            }
+            token::Kind::Net => self.parse_net()?,
+            token::Kind::Transition => self.parse_transition()?,
+            token::Kind::Place => self.parse_place()?,
+            token::Kind::Note => self.parse_note()?,
+            token::Kind::Label => self.parse_label()?,
+            token::Kind::Priority => self.parse_priority()?,
+            token::Kind::EndOfFile => return Ok(false),
             _ => {
                 return Err(Box::new(ParserError::UnexpectedToken(
-                    self.lexer.current_token.clone(),
-                    "Expected TokenKind::Identifier(_)".to_string(),
+                    token.into_owned(),
+                    "This token can not start a line".to_string(),
                 )));
             }
-        };
+        }
+        self.declared = true;
+        Ok(true)
+    }
 
-        let tr = self.get_or_create_transition(&transition_name)?;
+    /// Read an identifier
+    fn parse_identifier(&mut self) -> Result<Cow<'s, str>, Box<dyn Error>> {
+        match self.lexer.read()?.kind {
+            token::Kind::Identifier(name) => Ok(name),
+            _ => Err(Box::new(ParserError::UnexpectedToken(
+                self.lexer.current_token.clone().into_owned(),
+                "Expected TokenKind::Identifier(_)".to_string(),
+            ))),
+        }
+    }
 
-        // Try to read label
+    /// Try to read an inline label
+    fn parse_inline_label(&mut self) -> Result<Option<Cow<'s, str>>, Box<dyn Error>> {
         if self.lexer.peek()?.kind == token::Kind::InlineLabel {
             self.lexer.read()?;
-            match self.lexer.read()?.kind {
-                token::Kind::Identifier(label) => self.net[tr].label = Some(label),
-                _ => {
-                    return Err(Box::new(ParserError::UnexpectedToken(
-                        self.lexer.current_token.clone(),
-                        "Expected TokenKind::Identifier(_)".to_string(),
-                    )));
-                }
-            }
+            Ok(Some(self.parse_identifier()?))
+        } else {
+            Ok(None)
         }
+    }
+
+    /// Parse the label token
+    fn parse_label(&mut self) -> Result<(), Box<dyn Error>> {
+        self.lexer.read()?;
+        let node = self.parse_identifier()?;
+        let label = self.parse_identifier()?;
+        self.visitor.label(&node, &label)
+    }
+
+    /// Parse the net token
+    fn parse_net(&mut self) -> Result<(), Box<dyn Error>> {
+        self.lexer.read()?;
+        let name = self.parse_identifier()?;
+        self.visitor.net(&name)
+    }
+
+    /// Parse a transition line
+    fn parse_transition(&mut self) -> Result<(), Box<dyn Error>> {
+        self.lexer.read()?;
+        let transition = self.parse_identifier()?;
+        let label = self.parse_inline_label()?;
 
         // Try to read interval
-        if let token::Kind::TimeInterval(start, end) = self.lexer.peek()?.kind {
-            self.lexer.read()?;
-            self.net[tr].time = self.net[tr].time.intersect(TimeRange { start, end })
-        }
+        let time = match self.lexer.peek()?.kind {
+            token::Kind::TimeInterval(start, end) => {
+                self.lexer.read()?;
+                Some(TimeRange { start, end })
+            }
+            _ => None,
+        };
+        self.visitor
+            .transition(&transition, label.as_deref(), time)?;
 
         if matches!(
             self.lexer.peek()?.kind,
             token::Kind::Identifier(_) | token::Kind::Arrow
         ) {
             // Try to read input places
-            loop {
-                let identifier = &match self.lexer.peek()?.kind {
-                    token::Kind::Identifier(name) => name,
-                    _ => break,
-                };
-                let pl = self.get_or_create_place(identifier)?;
+            while let token::Kind::Identifier(place) = self.lexer.peek()?.kind {
                 self.lexer.read()?;
-
-                let new_arc = self.parse_transition_input_arc(pl, tr)?;
-                self.net.add_arc(new_arc)?;
+                let (kind, weight) = self.parse_transition_input_arc()?;
+                self.visitor.arc(&place, &transition, kind, weight)?;
             }
 
             self.parse_arrow()?;
 
             // Try to read output
-            loop {
-                let identifier = &match self.lexer.peek()?.kind {
-                    token::Kind::Identifier(name) => name,
-                    _ => break,
-                };
-                let pl = self.get_or_create_place(identifier)?;
+            while let token::Kind::Identifier(place) = self.lexer.peek()?.kind {
                 self.lexer.read()?;
-                let new_arc = self.parse_transition_output_arc(pl, tr)?;
-                self.net.add_arc(new_arc)?;
+                let (kind, weight) = self.parse_transition_output_arc()?;
+                self.visitor.arc(&place, &transition, kind, weight)?;
             }
         }
 
@@ -223,7 +298,7 @@ impl<R: BufRead> Parser<R> {
             }
             _ => {
                 return Err(Box::new(ParserError::UnexpectedToken(
-                    self.lexer.current_token.clone(),
+                    self.lexer.current_token.clone().into_owned(),
                     "Expected TokenKind::Int(_)".to_string(),
                 )));
             }
@@ -236,74 +311,56 @@ impl<R: BufRead> Parser<R> {
             Ok(())
         } else {
             Err(Box::new(ParserError::UnexpectedToken(
-                self.lexer.current_token.clone(),
+                self.lexer.current_token.clone().into_owned(),
                 "Expected TokenKind::Arrow".to_string(),
             )))
         }
     }
-    fn parse_transition_input_arc(
-        &mut self,
-        place: PlaceId,
-        transition: TransitionId,
-    ) -> Result<arc::Kind, Box<dyn Error>> {
-        match self.lexer.peek()?.kind {
-            token::Kind::NormalArc => {
-                self.lexer.read()?;
-                Ok(arc::Kind::Consume(place, transition, self.parse_int()?))
-            }
-            token::Kind::InhibitorArc => {
-                self.lexer.read()?;
-                Ok(arc::Kind::Inhibitor(place, transition, self.parse_int()?))
-            }
-            token::Kind::TestArc => {
-                self.lexer.read()?;
-                Ok(arc::Kind::Test(place, transition, self.parse_int()?))
-            }
-            token::Kind::StopWatchArc => {
-                self.lexer.read()?;
-                Ok(arc::Kind::StopWatch(place, transition, self.parse_int()?))
-            }
-            token::Kind::StopWatchInhibitorArc => {
-                self.lexer.read()?;
-                Ok(arc::Kind::StopWatchInhibitor(
-                    place,
-                    transition,
-                    self.parse_int()?,
-                ))
-            }
+
+    /// Parse the kind and the weight of an arc from a place to a transition
+    fn parse_transition_input_arc(&mut self) -> Result<(ArcKind, usize), Box<dyn Error>> {
+        let kind = match self.lexer.peek()?.kind {
+            token::Kind::NormalArc => ArcKind::Consume,
+            token::Kind::InhibitorArc => ArcKind::Inhibitor,
+            token::Kind::TestArc => ArcKind::Test,
+            token::Kind::StopWatchArc => ArcKind::StopWatch,
+            token::Kind::StopWatchInhibitorArc => ArcKind::StopWatchInhibitor,
             token::Kind::Arrow
             | token::Kind::EndOfFile
             | token::Kind::Identifier(_)
-            | token::Kind::NewLine => Ok(arc::Kind::Consume(place, transition, 1)),
-            _ => Err(Box::new(ParserError::UnexpectedToken(
-                self.lexer.current_token.clone(),
-                "Expected TokenKind::Arc*".to_string(),
-            ))),
-        }
+            | token::Kind::Comment(_)
+            | token::Kind::NewLine => return Ok((ArcKind::Consume, 1)),
+            _ => {
+                return Err(Box::new(ParserError::UnexpectedToken(
+                    self.lexer.current_token.clone().into_owned(),
+                    "Expected TokenKind::Arc*".to_string(),
+                )))
+            }
+        };
+        self.lexer.read()?;
+        Ok((kind, self.parse_int()?))
     }
 
-    fn parse_transition_output_arc(
-        &mut self,
-        place: PlaceId,
-        transition: TransitionId,
-    ) -> Result<arc::Kind, Box<dyn Error>> {
+    /// Parse the kind and the weight of an arc from a transition to a place
+    fn parse_transition_output_arc(&mut self) -> Result<(ArcKind, usize), Box<dyn Error>> {
         match self.lexer.peek()?.kind {
             token::Kind::NormalArc => {
                 self.lexer.read()?;
-                Ok(arc::Kind::Produce(place, transition, self.parse_int()?))
+                Ok((ArcKind::Produce, self.parse_int()?))
             }
             arc @ token::Kind::InhibitorArc
             | arc @ token::Kind::TestArc
             | arc @ token::Kind::StopWatchArc
             | arc @ token::Kind::StopWatchInhibitorArc => Err(Box::new(
-                ParserError::UnexpectedArc(self.lexer.current_token.position, arc),
+                ParserError::UnexpectedArc(self.lexer.current_token.position, arc.into_owned()),
             )),
             token::Kind::Arrow
             | token::Kind::EndOfFile
             | token::Kind::Identifier(_)
-            | token::Kind::NewLine => Ok(arc::Kind::Produce(place, transition, 1)),
+            | token::Kind::Comment(_)
+            | token::Kind::NewLine => Ok((ArcKind::Produce, 1)),
             _ => Err(Box::new(ParserError::UnexpectedToken(
-                self.lexer.current_token.clone(),
+                self.lexer.current_token.clone().into_owned(),
                 "Expected TokenKind::Arc*".to_string(),
             ))),
         }
@@ -312,66 +369,37 @@ impl<R: BufRead> Parser<R> {
     /// Parse a place line
     fn parse_place(&mut self) -> Result<(), Box<dyn Error>> {
         self.lexer.read()?;
-        let pl = match self.lexer.read()?.kind {
-            token::Kind::Identifier(name) => self.get_or_create_place(&name)?,
-            _ => {
-                return Err(Box::new(ParserError::UnexpectedToken(
-                    self.lexer.current_token.clone(),
-                    "Expected TokenKind::Identifier(_)".to_string(),
-                )));
-            }
-        };
-
-        // Parse Label
-        if self.lexer.peek()?.kind == token::Kind::InlineLabel {
-            self.lexer.read()?;
-            match self.lexer.read()?.kind {
-                token::Kind::Identifier(label) => {
-                    self.net[pl].label = Some(label);
-                }
-                _ => {
-                    return Err(Box::new(ParserError::UnexpectedToken(
-                        self.lexer.current_token.clone(),
-                        "Expected TokenKind::Identifier(_)".to_string(),
-                    )));
-                }
-            }
-        }
+        let place = self.parse_identifier()?;
+        let label = self.parse_inline_label()?;
 
         // Parse marking
-        if let token::Kind::Int(v) = self.lexer.peek()?.kind {
-            self.lexer.read()?;
-            self.net[pl].initial += v;
-        }
+        let marking = match self.lexer.peek()?.kind {
+            token::Kind::Int(v) => {
+                self.lexer.read()?;
+                v
+            }
+            _ => 0,
+        };
+        self.visitor.place(&place, label.as_deref(), marking)?;
 
         if matches!(
             self.lexer.peek()?.kind,
             token::Kind::Identifier(_) | token::Kind::Arrow
         ) {
             // Parse inputs
-            loop {
-                let identifier = &match self.lexer.peek()?.kind {
-                    token::Kind::Identifier(name) => name,
-                    _ => break,
-                };
-                let tr = self.get_or_create_transition(identifier)?;
+            while let token::Kind::Identifier(transition) = self.lexer.peek()?.kind {
                 self.lexer.read()?;
-                let new_arc = self.parse_transition_output_arc(pl, tr)?;
-                self.net.add_arc(new_arc)?;
+                let (kind, weight) = self.parse_transition_output_arc()?;
+                self.visitor.arc(&place, &transition, kind, weight)?;
             }
 
             self.parse_arrow()?;
 
             // Parse output
-            loop {
-                let identifier = &match self.lexer.peek()?.kind {
-                    token::Kind::Identifier(name) => name,
-                    _ => break,
-                };
-                let tr = self.get_or_create_transition(identifier)?;
+            while let token::Kind::Identifier(transition) = self.lexer.peek()?.kind {
                 self.lexer.read()?;
-                let new_arc = self.parse_transition_input_arc(pl, tr)?;
-                self.net.add_arc(new_arc)?;
+                let (kind, weight) = self.parse_transition_input_arc()?;
+                self.visitor.arc(&place, &transition, kind, weight)?;
             }
         }
 
@@ -381,26 +409,10 @@ impl<R: BufRead> Parser<R> {
     /// Parse a note line
     fn parse_note(&mut self) -> Result<(), Box<dyn Error>> {
         self.lexer.read()?;
-        match self.lexer.read()?.kind {
-            token::Kind::Identifier(_) => {}
-            _ => {
-                return Err(Box::new(ParserError::UnexpectedToken(
-                    self.lexer.current_token.clone(),
-                    "Expected TokenKind::Identifier(_)".to_string(),
-                )));
-            }
-        }
-        self.parse_int()?;
-        match self.lexer.read()?.kind {
-            token::Kind::Identifier(_) => {}
-            _ => {
-                return Err(Box::new(ParserError::UnexpectedToken(
-                    self.lexer.current_token.clone(),
-                    "Expected TokenKind::Identifier(_)".to_string(),
-                )));
-            }
-        }
-        Ok(())
+        let name = self.parse_identifier()?;
+        let justification = self.parse_int()?;
+        let text = self.parse_identifier()?;
+        self.visitor.note(&name, justification, &text)
     }
 
     /// Parse a priority line
@@ -410,29 +422,29 @@ impl<R: BufRead> Parser<R> {
         let mut post = vec![];
         while let token::Kind::Identifier(id) = self.lexer.peek()?.kind {
             self.lexer.read()?;
-            pre.push(self.get_or_create_transition(&id)?);
+            pre.push(id);
         }
         let order = match self.lexer.read()?.kind {
             token::Kind::GreaterThan => false,
             token::Kind::LessThan => true,
             _ => {
                 return Err(Box::new(ParserError::UnexpectedToken(
-                    self.lexer.current_token.clone(),
+                    self.lexer.current_token.clone().into_owned(),
                     "Expected TokenKind::GreaterThan or TokenKind::LessThan".to_string(),
                 )));
             }
         };
         while let token::Kind::Identifier(id) = self.lexer.peek()?.kind {
             self.lexer.read()?;
-            post.push(self.get_or_create_transition(&id)?);
+            post.push(id);
         }
 
-        let (pre, post) = if order { (post, pre) } else { (pre, post) };
-        for id in &pre {
-            for id_post in &post {
-                self.net.add_priority(*id, *id_post);
-            }
+        let pre: Vec<&str> = pre.iter().map(|id| id.as_ref()).collect();
+        let post: Vec<&str> = post.iter().map(|id| id.as_ref()).collect();
+        if order {
+            self.visitor.priority(&post, &pre)
+        } else {
+            self.visitor.priority(&pre, &post)
         }
-        Ok(())
     }
 }
@@ -1,84 +1,39 @@
-use std::error::Error;
-use std::io::BufRead;
-
 use crate::parser::Position;
-use crate::ParserError;
 
-/// Reader is an utf-8 reader.
-pub struct Reader<R: BufRead> {
-    reader: R,
+/// Reader is a cursor over an utf-8 source.
+///
+/// The source is decoded beforehand, characters are read from it without copies and the end of
+/// the source is read as `\0`.
+pub struct Reader<'s> {
+    source: &'s str,
     pub current_position: Position,
     pub next_position: Position,
-    next_char: Option<char>,
+    /// Byte offset of the last read character
+    pub current_offset: usize,
+    /// Byte offset of the next character
+    pub next_offset: usize,
 }
 
-impl<R: BufRead> Reader<R> {
-    /// Create a new Reader for a Read object.
-    pub fn new(reader: R) -> Self {
+impl<'s> Reader<'s> {
+    /// Create a new Reader for a source.
+    pub fn new(source: &'s str) -> Self {
         Self {
-            reader,
+            source,
             current_position: Position { line: 1, column: 0 },
             next_position: Position { line: 1, column: 1 },
-            next_char: None,
-        }
-    }
-
-    /// Read next u8 from reader
-    ///
-    /// Can return `Error::IoError` if the reader return an error
-    fn read_u8(&mut self) -> Result<u8, Box<dyn Error>> {
-        let mut c = [0_u8];
-        if self.reader.read(&mut c)? == 0 {
-            Ok(0)
-        } else {
-            Ok(c[0])
-        }
-    }
-
-    /// Read next utf8 char
-    ///
-    /// Can return [`Error::IoError`] if the reader return an error
-    fn read_utf8(&mut self) -> Result<char, Box<dyn Error>> {
-        // Convert u8 to char
-        match match self.read_u8()? {
-            c if c <= 0b1000_0000 => {
-                // ASCII char
-                Some(c as char)
-            }
-            c if (c & 0b1110_0000) == 0b1100_0000 => {
-                // 2 bytes
-                char::from_u32(
-                    ((u32::from(c) & 0b0001_1111) << 6)
-                        | (u32::from(self.read_u8()?) & 0b0011_1111),
-                )
-            }
-            c if (c & 0b1111_0000) == 0b1110_0000 => {
-                // 3 bytes
-                char::from_u32(
-                    ((u32::from(c) & 0b0000_1111) << 12)
-                        | (u32::from(self.read_u8()?) & 0b0011_1111) << 6
-                        | (u32::from(self.read_u8()?) & 0b0011_1111),
-                )
-            }
-            c if (c & 0b1111_1000) == 0b1111_0000 => {
-                // 4 bytes
-                char::from_u32(
-                    ((u32::from(c) & 0b0000_0111) << 18)
-                        | (u32::from(self.read_u8()?) & 0b0011_1111) << 12
-                        | (u32::from(self.read_u8()?) & 0b0011_1111) << 6
-                        | (u32::from(self.read_u8()?) & 0b0011_1111),
-                )
-            }
-            _ => return Err(Box::new(ParserError::Utf8Error(self.current_position))),
-        } {
-            None => Err(Box::new(ParserError::Utf8Error(self.current_position))),
-            Some(c) => Ok(c),
+            current_offset: 0,
+            next_offset: 0,
         }
     }
 
     /// Increment position according to character pased in parameters
     fn increment_position(&mut self, ch: char) {
         self.current_position = self.next_position;
+        self.current_offset = self.next_offset;
+        // The end of file is read as `\0` but is not in the source
+        if ch != '\u{0}' {
+            self.next_offset += ch.len_utf8();
+        }
         if ch == '\n' {
             self.next_position.line += 1;
             self.next_position.column = 0;
@@ -86,25 +41,35 @@ impl<R: BufRead> Reader<R> {
         self.next_position.column += 1;
     }
 
-    /// Read next char from reader
-    ///
-    /// If this char was read with `peek` it will be used.
-    pub fn read(&mut self) -> Result<char, Box<dyn Error>> {
-        let ch = self.peek()?;
-        self.next_char = None;
+    /// Read next char from source
+    pub fn read(&mut self) -> char {
+        let ch = self.peek();
         self.increment_position(ch);
-        Ok(ch)
+        ch
+    }
+
+    /// Peek next character from source
+    pub fn peek(&self) -> char {
+        match self.source.as_bytes().get(self.next_offset) {
+            // Most characters of net files are ascii
+            Some(&b) if b.is_ascii() => char::from(b),
+            Some(_) => self.source[self.next_offset..].chars().next().unwrap(),
+            None => '\u{0}',
+        }
     }
 
-    /// Peek next character from reader
-    ///
-    /// I this char exist in buffer, it will be used
-    pub fn peek(&mut self) -> Result<char, Box<dyn Error>> {
-        match self.next_char {
-            None => self.next_char = Some(self.read_utf8()?),
-            Some(_) => (),
+    /// Read characters while they match `predicate` and return them
+    pub fn read_while(&mut self, predicate: impl Fn(char) -> bool) -> &'s str {
+        let start = self.next_offset;
+        while self.next_offset < self.source.len() && predicate(self.peek()) {
+            self.read();
         }
-        Ok(self.next_char.unwrap())
+        &self.source[start..self.next_offset]
+    }
+
+    /// Part of the source from `start` to the next character
+    pub fn slice(&self, start: usize) -> &'s str {
+        &self.source[start..self.next_offset]
     }
 }
 
@@ -119,10 +84,8 @@ mod tests {
                 None => (),
                 Some(c) => {
                     let tmp = c.to_string();
-                    let mut reader: Reader<&[u8]> = Reader::new(tmp.as_ref());
-                    let result = reader.read();
-                    assert_eq!(result.is_ok(), true);
-                    assert_eq!(result.unwrap(), c);
+                    let mut reader = Reader::new(&tmp);
+                    assert_eq!(reader.read(), c);
                 }
             }
         }
@@ -135,10 +98,8 @@ mod tests {
                 None => (),
                 Some(c) => {
                     let tmp = c.to_string();
-                    let mut reader: Reader<&[u8]> = Reader::new(tmp.as_ref());
-                    let result = reader.read();
-                    assert_eq!(result.is_ok(), true);
-                    assert_eq!(result.unwrap(), c);
+                    let mut reader = Reader::new(&tmp);
+                    assert_eq!(reader.read(), c);
                 }
             }
         }
@@ -151,10 +112,8 @@ mod tests {
                 None => (),
                 Some(c) => {
                     let tmp = c.to_string();
-                    let mut reader: Reader<&[u8]> = Reader::new(tmp.as_ref());
-                    let result = reader.read();
-                    assert_eq!(result.is_ok(), true);
-                    assert_eq!(result.unwrap(), c);
+                    let mut reader = Reader::new(&tmp);
+                    assert_eq!(reader.read(), c);
                 }
             }
         }
@@ -167,10 +126,8 @@ mod tests {
                 None => (),
                 Some(c) => {
                     let tmp = c.to_string();
-                    let mut reader: Reader<&[u8]> = Reader::new(tmp.as_ref());
-                    let result = reader.read();
-                    assert_eq!(result.is_ok(), true);
-                    assert_eq!(result.unwrap(), c);
+                    let mut reader = Reader::new(&tmp);
+                    assert_eq!(reader.read(), c);
                 }
             }
         }
@@ -1,5 +1,6 @@
 //! All tokens present in a net file
 
+use std::borrow::Cow;
 use std::fmt;
 use std::fmt::Formatter;
 
@@ -8,17 +9,19 @@ use pnets::timed::Bound;
 use crate::parser::Position;
 
 /// Token is a tokenkind linked with its position in file
+///
+/// Identifiers and comments borrow their text from the source when possible.
 #[derive(PartialEq, Debug, Clone)]
-pub struct Token {
+pub struct Token<'s> {
     /// Kind of token
-    pub kind: Kind,
+    pub kind: Kind<'s>,
     /// Position of token
     pub position: Position,
 }
 
 /// All tokens kind
 #[derive(PartialEq, Debug, Clone)]
-pub enum Kind {
+pub enum Kind<'s> {
     /// New line in original file
     NewLine,
     /// Net `net`
@@ -46,7 +49,7 @@ pub enum Kind {
     /// StopWatchInhibitorArc `!-`
     StopWatchInhibitorArc,
     /// Identifier ANAME or QNAME
-    Identifier(String),
+    Identifier(Cow<'s, str>),
     /// Inline label `:`
     InlineLabel,
     /// Arrow `->`
@@ -54,7 +57,7 @@ pub enum Kind {
     /// Marking, weight, int
     Int(usize),
     /// Comment `#`
-    Comment(String),
+    Comment(Cow<'s, str>),
     /// >
     GreaterThan,
     /// <
@@ -63,7 +66,46 @@ pub enum Kind {
     EndOfFile,
 }
 
-impl fmt::Display for Kind {
+impl Token<'_> {
+    /// Copy the borrowed text of the token so that it outlives the source
+    pub fn into_owned(self) -> Token<'static> {
+        Token {
+            kind: self.kind.into_owned(),
+            position: self.position,
+        }
+    }
+}
+
+impl Kind<'_> {
+    /// Copy the borrowed text of the kind so that it outlives the source
+    pub fn into_owned(self) -> Kind<'static> {
+        match self {
+            Kind::NewLine => Kind::NewLine,
+            Kind::Net => Kind::Net,
+            Kind::Transition => Kind::Transition,
+            Kind::Place => Kind::Place,
+            Kind::Note => Kind::Note,
+            Kind::Label => Kind::Label,
+            Kind::Priority => Kind::Priority,
+            Kind::TimeInterval(a, b) => Kind::TimeInterval(a, b),
+            Kind::NormalArc => Kind::NormalArc,
+            Kind::TestArc => Kind::TestArc,
+            Kind::InhibitorArc => Kind::InhibitorArc,
+            Kind::StopWatchArc => Kind::StopWatchArc,
+            Kind::StopWatchInhibitorArc => Kind::StopWatchInhibitorArc,
+            Kind::Identifier(s) => Kind::Identifier(Cow::Owned(s.into_owned())),
+            Kind::InlineLabel => Kind::InlineLabel,
+            Kind::Arrow => Kind::Arrow,
+            Kind::Int(v) => Kind::Int(v),
+            Kind::Comment(c) => Kind::Comment(Cow::Owned(c.into_owned())),
+            Kind::GreaterThan => Kind::GreaterThan,
+            Kind::LessThan => Kind::LessThan,
+            Kind::EndOfFile => Kind::EndOfFile,
+        }
+    }
+}
+
+impl fmt::Display for Kind<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
             Kind::NewLine => write!(f, "NewLine"),
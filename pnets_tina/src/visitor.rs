@@ -0,0 +1,125 @@
+//! Events emitted while a net file is parsed
+use std::error::Error;
+
+use pnets::timed::TimeRange;
+
+/// Kind of an arc between a place and a transition
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ArcKind {
+    /// The transition consumes tokens of the place `*`
+    Consume,
+    /// The transition produces tokens in the place `*`
+    Produce,
+    /// The transition needs tokens in the place `?`
+    Test,
+    /// The transition can not fire with tokens in the place `?-`
+    Inhibitor,
+    /// The clock of the transition runs with tokens in the place `!`
+    StopWatch,
+    /// The clock of the transition stops with tokens in the place `!-`
+    StopWatchInhibitor,
+}
+
+/// Receives the elements of a net file while it is parsed
+///
+/// [`Parser::visit`](crate::Parser::visit) calls these methods in the order of the file, nodes
+/// are designated by their name and names are borrowed from the source. In the tina format a
+/// node exists as soon as it is named: an arc can reference a place which has no `pl` line.
+///
+/// All methods do nothing by default. An error returned by a method is reported at the current
+/// line and the parser continues with the next line.
+///
+/// ```
+/// use std::error::Error;
+///
+/// use pnets::timed::TimeRange;
+/// use pnets_tina::{Parser, Visitor};
+///
+/// #[derive(Default)]
+/// struct Counter {
+///     transitions: usize,
+/// }
+///
+/// impl Visitor for Counter {
+///     fn transition(
+///         &mut self,
+///         _name: &str,
+///         _label: Option<&str>,
+///         _time: Option<TimeRange>,
+///     ) -> Result<(), Box<dyn Error>> {
+///         self.transitions += 1;
+///         Ok(())
+///     }
+/// }
+///
+/// let mut counter = Counter::default();
+/// Parser::new("tr t0 p0 -> p1\ntr t1 p1 -> p0".as_bytes())
+///     .visit(&mut counter)
+///     .unwrap();
+/// assert_eq!(counter.transitions, 2);
+/// ```
+#[allow(unused_variables)]
+pub trait Visitor {
+    /// Name of the net, from a `net` line
+    fn net(&mut self, name: &str) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+
+    /// Declaration of a place, from a `pl` line
+    ///
+    /// The arcs of the line are emitted after the place.
+    fn place(
+        &mut self,
+        name: &str,
+        label: Option<&str>,
+        marking: usize,
+    ) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+
+    /// Declaration of a transition, from a `tr` line
+    ///
+    /// The arcs of the line are emitted after the transition.
+    fn transition(
+        &mut self,
+        name: &str,
+        label: Option<&str>,
+        time: Option<TimeRange>,
+    ) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+
+    /// Arc between a place and a transition
+    fn arc(
+        &mut self,
+        place: &str,
+        transition: &str,
+        kind: ArcKind,
+        weight: usize,
+    ) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+
+    /// Priorities of a `pr` line, all transitions of `higher` have priority over all
+    /// transitions of `lower`
+    fn priority(&mut self, higher: &[&str], lower: &[&str]) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+
+    /// Label of a node, from a `lb` line
+    fn label(&mut self, node: &str, label: &str) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+
+    /// Note, from a `nt` line
+    fn note(&mut self, name: &str, justification: usize, text: &str) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+
+    /// Comment line, without its `#`
+    ///
+    /// `inline` is [`true`] if the comment ends a line which declares an element.
+    fn comment(&mut self, comment: &str, inline: bool) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+}
@@ -0,0 +1,413 @@
+use std::collections::BTreeSet;
+
+use pnets::metadata::{Anchor, Note};
+use pnets::timed::{Bound, Net, Time, TimeRange};
+use pnets::{arc, standard, NetError, NodeId};
+use pnets_tina::{Export, ExporterBuilder, Order, Parser};
+use proptest::prelude::*;
+
+/// Options of the exporter
+#[derive(Debug, Clone, Copy)]
+struct Options {
+    order: Order,
+    minimal_quoting: bool,
+    label_lines: bool,
+    arc_lines: bool,
+    with_labels: bool,
+    with_metadata: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            order: Order::Index,
+            minimal_quoting: false,
+            label_lines: false,
+            arc_lines: false,
+            with_labels: true,
+            with_metadata: true,
+        }
+    }
+}
+
+fn export(net: &dyn Export, options: Options) -> String {
+    let mut out = vec![];
+    ExporterBuilder::new(&mut out)
+        .with_all_places(true)
+        .with_order(options.order)
+        .with_minimal_quoting(options.minimal_quoting)
+        .with_label_lines(options.label_lines)
+        .with_arc_lines(options.arc_lines)
+        .with_labels(options.with_labels)
+        .with_metadata(options.with_metadata)
+        .build()
+        .export(net)
+        .unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+/// Description of a net which does not depend on the index of its nodes
+fn describe(net: &Net, labels: bool, metadata: bool) -> BTreeSet<String> {
+    let name = |node: NodeId| net.get_name_by_index(&node).unwrap();
+    let arcs = |arcs: &mut dyn Iterator<Item = &(pnets::PlaceId, usize)>| {
+        arcs.map(|&(pl, w)| format!("{}*{}", name(NodeId::Place(pl)), w))
+            .collect::<BTreeSet<_>>()
+    };
+    let mut lines = BTreeSet::new();
+    lines.insert(format!("net {}", net.name));
+    for (pl, place) in net.places.iter_enumerated() {
+        lines.insert(format!(
+            "pl {} {:?} {}",
+            name(NodeId::Place(pl)),
+            place.label.as_ref().filter(|_| labels),
+            place.initial
+        ));
+    }
+    for (tr, transition) in net.transitions.iter_enumerated() {
+        let priorities: BTreeSet<_> = transition
+            .priorities
+            .iter()
+            .map(|&t| name(NodeId::Transition(t)))
+            .collect();
+        lines.insert(format!(
+            "tr {} {:?} {} {:?} {:?} {:?} {:?} {:?} {:?} > {:?}",
+            name(NodeId::Transition(tr)),
+            transition.label.as_ref().filter(|_| labels),
+            transition.time,
+            arcs(&mut transition.consume.iter()),
+            arcs(&mut transition.produce.iter()),
+            arcs(&mut transition.conditions.iter()),
+            arcs(&mut transition.inhibitors.iter()),
+            arcs(&mut transition.stopwatches.iter()),
+            arcs(&mut transition.stopwatch_inhibitors.iter()),
+            priorities
+        ));
+    }
+    if metadata {
+        for (anchor, comments) in &net.metadata.comments {
+            let anchor = match anchor {
+                Anchor::Node(node) => name(*node),
+                anchor => format!("{:?}", anchor),
+            };
+            lines.insert(format!("# {} {:?}", anchor, comments));
+        }
+        for (node, notes) in &net.metadata.node_notes {
+            lines.insert(format!("nt {} {:?}", name(*node), notes));
+        }
+        lines.insert(format!("nt {:?}", net.metadata.notes));
+    }
+    lines
+}
+
+/// Generated net, nodes are referenced by their index in the name lists
+#[derive(Debug, Clone)]
+struct Model {
+    places: Vec<(String, Option<String>, usize)>,
+    transitions: Vec<(String, Option<String>, TimeRange)>,
+    arcs: Vec<(usize, usize, usize, usize)>,
+    priorities: Vec<(usize, usize)>,
+    comments: Vec<(usize, String)>,
+    notes: Vec<(usize, String, usize, String)>,
+}
+
+impl Model {
+    fn build(&self) -> Net {
+        let mut net = Net::default();
+        net.name = "model".to_string();
+        let mut nodes = vec![];
+        for (name, label, initial) in &self.places {
+            let pl = net.create_place();
+            net.rename_node(NodeId::Place(pl), name).unwrap();
+            net[pl].label = label.clone();
+            net[pl].initial = *initial;
+            nodes.push(NodeId::Place(pl));
+        }
+        let mut transitions = vec![];
+        for (name, label, time) in &self.transitions {
+            let tr = net.create_transition();
+            net.rename_node(NodeId::Transition(tr), name).unwrap();
+            net[tr].label = label.clone();
+            net[tr].time = *time;
+            nodes.push(NodeId::Transition(tr));
+            transitions.push(tr);
+        }
+        let places: Vec<_> = net.places.iter_enumerated().map(|(pl, _)| pl).collect();
+        for &(pl, tr, kind, w) in &self.arcs {
+            let (pl, tr) = (
+                places[pl % places.len()],
+                transitions[tr % transitions.len()],
+            );
+            // Some kinds of arcs can not be combined, these arcs are not added
+            let _ = net.add_arc(match kind {
+                0 => arc::Kind::Consume(pl, tr, w),
+                1 => arc::Kind::Produce(pl, tr, w),
+                2 => arc::Kind::Test(pl, tr, w),
+                3 => arc::Kind::Inhibitor(pl, tr, w),
+                4 => arc::Kind::StopWatch(pl, tr, w),
+                _ => arc::Kind::StopWatchInhibitor(pl, tr, w),
+            });
+        }
+        // Priorities from a transition to a transition with a greater index are not cyclic
+        for &(a, b) in &self.priorities {
+            let (a, b) = (a % transitions.len(), b % transitions.len());
+            if a < b {
+                net.add_priority(transitions[a], transitions[b]);
+            }
+        }
+        net.update_priorities().unwrap();
+        for (node, comment) in &self.comments {
+            let anchor = match node % (nodes.len() + 2) {
+                0 => Anchor::Net,
+                1 => Anchor::End,
+                n => Anchor::Node(nodes[n - 2]),
+            };
+            net.metadata.add_comment(anchor, comment.clone());
+        }
+        for (node, name, justification, text) in &self.notes {
+            let note = Note {
+                name: name.clone(),
+                justification: *justification,
+                text: text.clone(),
+                comments: vec![],
+            };
+            match node % (nodes.len() + 1) {
+                0 => net.metadata.notes.push(note),
+                n => {
+                    let node = nodes[n - 1];
+                    let note = Note {
+                        name: net.get_name_by_index(&node).unwrap(),
+                        ..note
+                    };
+                    net.metadata.add_node_note(node, note);
+                }
+            }
+        }
+        net
+    }
+}
+
+/// Names with characters to escape and keywords, notes use longer names
+fn name() -> impl Strategy<Value = String> {
+    prop_oneof![
+        4 => "[a-zA-Z0-9_'{}\\\\ é]{1,5}",
+        1 => prop_oneof![Just("tr"), Just("Pl"), Just("NET"), Just("lb"), Just("nA"), Just("pr")]
+            .prop_map(String::from),
+    ]
+}
+
+fn time_range() -> impl Strategy<Value = TimeRange> {
+    (0_u64..20, 1_u64..4, any::<bool>(), 0_u64..20, 0..4).prop_map(
+        |(start, denominator, open, length, end)| {
+            let start_time = Time::new(start, denominator);
+            TimeRange {
+                start: if open {
+                    Bound::Open(start_time)
+                } else {
+                    Bound::Closed(start_time)
+                },
+                end: match end {
+                    0 => Bound::Infinity,
+                    1 => Bound::Open(start_time + Time::from_integer(length + 1)),
+                    _ => Bound::Closed(start_time + Time::from_integer(length + 1)),
+                },
+            }
+        },
+    )
+}
+
+fn model() -> impl Strategy<Value = Model> {
+    (
+        prop::collection::hash_set(name(), 2..12),
+        prop::collection::vec(prop::option::of(name()), 12),
+        prop::collection::vec((0_usize..3, time_range()), 12),
+        prop::collection::vec((0_usize..12, 0_usize..12, 0_usize..6, 1_usize..4), 0..20),
+        prop::collection::vec((0_usize..12, 0_usize..12), 0..6),
+        prop::collection::vec((0_usize..14, "[ a-z#{}]{0,8}"), 0..6),
+        prop::collection::vec(
+            (0_usize..13, "note:[0-9]", 0_usize..2, "[ a-z{}]{0,8}"),
+            0..3,
+        ),
+    )
+        .prop_map(
+            |(names, labels, nodes, arcs, priorities, comments, notes)| {
+                let names: Vec<String> = names.into_iter().collect();
+                // At least one place and one transition
+                let places = 1 + names.len() / 3;
+                Model {
+                    places: names[..places]
+                        .iter()
+                        .zip(labels.iter().zip(nodes.iter()))
+                        .map(|(name, (label, (initial, _)))| {
+                            (name.clone(), label.clone(), *initial)
+                        })
+                        .collect(),
+                    transitions: names[places..]
+                        .iter()
+                        .zip(labels.iter().rev().zip(nodes.iter()))
+                        .map(|(name, (label, (_, time)))| (name.clone(), label.clone(), *time))
+                        .collect(),
+                    arcs,
+                    priorities,
+                    comments,
+                    notes,
+                }
+            },
+        )
+}
+
+fn options() -> impl Strategy<Value = Options> {
+    (
+        any::<bool>(),
+        any::<bool>(),
+        any::<bool>(),
+        any::<bool>(),
+        any::<bool>(),
+        any::<bool>(),
+    )
+        .prop_map(
+            |(by_name, minimal_quoting, label_lines, arc_lines, with_labels, with_metadata)| {
+                Options {
+                    order: if by_name { Order::Name } else { Order::Index },
+                    minimal_quoting,
+                    label_lines,
+                    arc_lines,
+                    with_labels,
+                    with_metadata,
+                }
+            },
+        )
+}
+
+fn example() -> Net {
+    Parser::new(
+        "net demo\npl p1 : {first place} (2)\ntr b [1,2] p1 ?1 p0 ?-2 -> {p 2}*3\n\
+         tr a : go p0 -> p1\npr a > b\n"
+            .as_bytes(),
+    )
+    .parse()
+    .unwrap()
+}
+
+#[test]
+fn default_test() {
+    assert_eq!(
+        export(&example(), Options::default()),
+        "net {demo}\npl {p1} : {first place} (2)\npl {p0} \npl {p 2} \n\
+         tr {b} [1,2] {p0}?-2 {p1}?1 -> {p 2}*3 \ntr {a} : {go} {p0} -> {p1} \npr {a} > {b} \n"
+    );
+}
+
+#[test]
+fn options_test() {
+    let options = Options {
+        order: Order::Name,
+        minimal_quoting: true,
+        label_lines: true,
+        arc_lines: true,
+        ..Options::default()
+    };
+    assert_eq!(
+        export(&example(), options),
+        "net demo\npl {p 2} \npl p0 \npl p1 (2)\nlb p1 {first place}\n\
+         tr a \ntr a p0 -> \ntr a -> p1 \nlb a {go}\npr a > b \n\
+         tr b [1,2] \ntr b p0?-2 -> \ntr b p1?1 -> \ntr b -> {p 2}*3 \n"
+    );
+
+    let options = Options {
+        with_labels: false,
+        ..Options::default()
+    };
+    assert!(!export(&example(), options).contains("go"));
+}
+
+#[test]
+fn quoting_test() {
+    let mut net = Net::default();
+    for name in &["a_b'é", "tr", "Net", "1a", "a b", "a{"] {
+        let pl = net.create_place();
+        net.rename_node(NodeId::Place(pl), name).unwrap();
+    }
+    let options = Options {
+        minimal_quoting: true,
+        ..Options::default()
+    };
+    assert_eq!(
+        export(&net, options),
+        "pl a_b'é \npl {tr} \npl {Net} \npl {1a} \npl {a b} \npl {a\\{} \n"
+    );
+}
+
+#[test]
+fn standard_test() {
+    let mut net = standard::Net::default();
+    net.name = "standard".to_string();
+    let (p0, p1, p2) = (net.create_place(), net.create_place(), net.create_place());
+    let (t0, t1) = (net.create_transition(), net.create_transition());
+    net.rename_node(NodeId::Place(p1), "b").unwrap();
+    net[p0].initial = 2;
+    net[t0].label = Some("fire".to_string());
+    net.add_arc(arc::Kind::Consume(p0, t0, 1)).unwrap();
+    net.add_arc(arc::Kind::Produce(p1, t0, 3)).unwrap();
+    net.add_arc(arc::Kind::Consume(p2, t1, 1)).unwrap();
+    net.delete_place(p2);
+    net.metadata
+        .add_comment(Anchor::Node(NodeId::Place(p1)), "output".to_string());
+
+    let timed: Net = (&net).into();
+    for &order in &[Order::Index, Order::Name] {
+        let options = Options {
+            order,
+            ..Options::default()
+        };
+        assert_eq!(export(&net, options), export(&timed, options));
+    }
+}
+
+#[test]
+fn reset_test() {
+    // Tina has no reset arcs
+    let mut net = example();
+    let (p0, a) = match (net.get_index_by_name("p0"), net.get_index_by_name("a")) {
+        (Some(NodeId::Place(p0)), Some(NodeId::Transition(a))) => (p0, a),
+        _ => panic!("p0 and a must be nodes of the net"),
+    };
+    net.add_arc(arc::Kind::Reset(p0, a, 1)).unwrap();
+    let mut out = Vec::new();
+    let error = ExporterBuilder::new(&mut out)
+        .build()
+        .export(&net)
+        .unwrap_err();
+    assert_eq!(
+        error.downcast_ref::<NetError>(),
+        Some(&NetError::UnsupportedArc(arc::Kind::Reset(p0, a, 1)))
+    );
+    assert!(out.is_empty());
+}
+
+proptest! {
+    #[test]
+    fn round_trip(model in model(), options in options()) {
+        let net = model.build();
+        let exported = export(&net, options);
+        let parsed = Parser::new(exported.as_bytes()).parse().unwrap();
+        prop_assert_eq!(
+            describe(&parsed, options.with_labels, options.with_metadata),
+            describe(&net, options.with_labels, options.with_metadata),
+            "{}",
+            exported
+        );
+
+        // Parsing and exporting again gives the same net
+        let reexported = export(&parsed, options);
+        let reparsed = Parser::new(reexported.as_bytes()).parse().unwrap();
+        prop_assert_eq!(describe(&reparsed, true, true), describe(&parsed, true, true));
+    }
+
+    #[test]
+    fn deterministic(model in model(), options in options()) {
+        let options = Options { order: Order::Name, ..options };
+        let exported = export(&model.build(), options);
+        let parsed = Parser::new(exported.as_bytes()).parse().unwrap();
+        prop_assert_eq!(export(&parsed, options), exported);
+    }
+}
@@ -0,0 +1,164 @@
+use std::io::{self, BufReader, ErrorKind, Read};
+
+use pnets::timed::{Bound, Net, Time, TimeRange};
+use pnets::{arc, standard, NodeId};
+use pnets_tina::{Layout, NdrExporter, NdrParser, NoteId, ParserError, Point};
+
+const DRAWING: &str = "p 100.0 50.0 {p0} 2 n
+p 300.5 50.0 {p 1} 0 n {first place} s
+# Comment line
+t 200.0 50.0 {t0} 0 w n
+t 200.0 150.0 {t1} ]1,2] n {go} e
+t 200.0 250.0 {t2} 1 3 n
+n 400.0 300.0 {note} 1 {A note}
+n 500.0 300.0 {note} 0 {Another note}
+n 200.0 0.0 {t0} 0 {Start}
+e {p0} {t0} 1 n
+e {t0} 0.3 40.0 {p 1} 2 n
+e {p 1} 0.5 20.0 {t1} -0.5 20.0 ?1 n
+e {p0} {t1} ?-3 n
+e {t1} {p0} 1 n
+e {p0} {t2} !1 n
+pr {t0} > {t1} {t2}
+h {demo}
+";
+
+fn export(net: &Net, layout: &Layout) -> String {
+    let mut out = vec![];
+    NdrExporter::new(&mut out).export(net, layout).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn parse_test() {
+    let (net, layout) = NdrParser::new(DRAWING.as_bytes()).parse().unwrap();
+    assert_eq!(net.name, "demo");
+    let node = |name: &str| net.get_index_by_name(name).unwrap();
+    let (p0, p1) = match (node("p0"), node("p 1")) {
+        (NodeId::Place(p0), NodeId::Place(p1)) => (p0, p1),
+        _ => panic!("p0 and p 1 must be places"),
+    };
+    let (t0, t1, t2) = match (node("t0"), node("t1"), node("t2")) {
+        (NodeId::Transition(t0), NodeId::Transition(t1), NodeId::Transition(t2)) => (t0, t1, t2),
+        _ => panic!("t0, t1 and t2 must be transitions"),
+    };
+    assert_eq!(net[p0].initial, 2);
+    assert_eq!(net[p1].label.as_deref(), Some("first place"));
+    assert_eq!(net[t1].label.as_deref(), Some("go"));
+    assert_eq!(net[t0].time, TimeRange::default());
+    assert_eq!(
+        net[t1].time,
+        TimeRange {
+            start: Bound::Open(Time::from_integer(1)),
+            end: Bound::Closed(Time::from_integer(2)),
+        }
+    );
+    assert_eq!(
+        net[t2].time,
+        TimeRange {
+            start: Bound::Closed(Time::from_integer(1)),
+            end: Bound::Closed(Time::from_integer(3)),
+        }
+    );
+    assert_eq!(net[t0].consume[p0], 1);
+    assert_eq!(net[t0].produce[p1], 2);
+    assert_eq!(net[t1].conditions[p1], 1);
+    assert_eq!(net[t1].inhibitors[p0], 3);
+    assert_eq!(net[t1].produce[p0], 1);
+    assert_eq!(net[t2].stopwatches[p0], 1);
+    assert_eq!(net[t0].priorities.len(), 2);
+    assert_eq!(net.metadata.notes[0].text, "A note");
+
+    assert_eq!(
+        layout.nodes[&NodeId::Place(p1)],
+        Point { x: 300.5, y: 50.0 }
+    );
+    assert_eq!(
+        layout.nodes[&NodeId::Transition(t2)],
+        Point { x: 200.0, y: 250.0 }
+    );
+    // Notes with the same name keep their own position
+    assert_eq!(layout.notes[&NoteId::Net(0)], Point { x: 400.0, y: 300.0 });
+    assert_eq!(layout.notes[&NoteId::Net(1)], Point { x: 500.0, y: 300.0 });
+    assert_eq!(
+        layout.notes[&NoteId::Node(NodeId::Transition(t0), 0)],
+        Point { x: 200.0, y: 0.0 }
+    );
+}
+
+#[test]
+fn round_trip_test() {
+    let (net, layout) = NdrParser::new(DRAWING.as_bytes()).parse().unwrap();
+    let exported = export(&net, &layout);
+    let (parsed, parsed_layout) = NdrParser::new(exported.as_bytes()).parse().unwrap();
+    assert_eq!(parsed_layout, layout);
+    assert_eq!(export(&parsed, &parsed_layout), exported);
+}
+
+#[test]
+fn invalid_test() {
+    for drawing in &[
+        "p 10.0 {p0} 1 n\n",
+        "p 10.0 20.0 {p0 1 n\n",
+        "t 10.0 20.0 {t0} 1 n\n",
+        "e {p0} {t0} a n\n",
+        "x 10.0 20.0\n",
+    ] {
+        assert!(
+            NdrParser::new(drawing.as_bytes()).parse().is_err(),
+            "{}",
+            drawing
+        );
+    }
+}
+
+/// Reader which always fails
+struct Failing;
+
+impl Read for Failing {
+    fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+        Err(io::Error::new(ErrorKind::BrokenPipe, "broken"))
+    }
+}
+
+#[test]
+fn read_error_test() {
+    let error = NdrParser::new(BufReader::new(Failing)).parse().unwrap_err();
+    assert_eq!(
+        error.downcast_ref::<io::Error>().map(io::Error::kind),
+        Some(ErrorKind::BrokenPipe)
+    );
+
+    let error = NdrParser::new(&b"h {net}\np 1.0 2.0 {p\xff} 0 n\n"[..])
+        .parse()
+        .unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<ParserError>(),
+        Some(ParserError::Utf8Error(position)) if position.line == 2
+    ));
+}
+
+#[test]
+fn standard_test() {
+    let mut net = standard::Net::default();
+    let (p0, p1) = (net.create_place(), net.create_place());
+    let tr = net.create_transition();
+    net.rename_node(NodeId::Place(p0), "p0").unwrap();
+    net.rename_node(NodeId::Transition(tr), "t0").unwrap();
+    net.add_arc(arc::Kind::Consume(p0, tr, 1)).unwrap();
+    net.add_arc(arc::Kind::Produce(p1, tr, 1)).unwrap();
+    net.delete_place(p1);
+    let mut layout = Layout::default();
+    layout
+        .nodes
+        .insert(NodeId::Place(p0), Point { x: 10.0, y: 20.0 });
+
+    let mut out = vec![];
+    NdrExporter::new(&mut out).export(&net, &layout).unwrap();
+    let exported = String::from_utf8(out).unwrap();
+    // Nodes without position are placed below the drawing, deleted nodes are not written
+    assert_eq!(
+        exported,
+        "p 10.0 20.0 {p0} 0 n\nt 100.0 120.0 {t0} 0 w n\ne {p0} {t0} 1 n\n"
+    );
+}
@@ -1,4 +1,5 @@
-use pnets::timed::{Bound, TimeRange};
+use pnets::metadata::Anchor;
+use pnets::timed::{Bound, Time, TimeRange};
 use pnets::{NodeId, PlaceId, TransitionId};
 
 #[test]
@@ -86,8 +87,8 @@ fn transition_test() {
     assert_eq!(
         net[t0].time,
         TimeRange {
-            start: Bound::Closed(1),
-            end: Bound::Closed(5),
+            start: Bound::Closed(Time::from_integer(1)),
+            end: Bound::Closed(Time::from_integer(5)),
         }
     );
     assert_eq!(net[t0].label, Some("transition_label".to_string()));
@@ -223,13 +224,88 @@ fn place_test() {
 #[test]
 fn note_test() {
     let parser = pnets_tina::Parser::new("nt note 0 {This is a note}".as_bytes());
-    parser.parse().unwrap();
+    let net = parser.parse().unwrap();
+    assert_eq!(net.metadata.notes.len(), 1);
+    assert_eq!(net.metadata.notes[0].name, "note");
+    assert_eq!(net.metadata.notes[0].justification, 0);
+    assert_eq!(net.metadata.notes[0].text, "This is a note");
+
+    let parser = pnets_tina::Parser::new("tr t0 p0 -> p1\nnt t0 1 {Fires once}".as_bytes());
+    let net = parser.parse().unwrap();
+    let t0 = net.get_index_by_name("t0").unwrap();
+    assert!(net.metadata.notes.is_empty());
+    assert_eq!(net.metadata.node_notes(t0)[0].text, "Fires once");
+}
+
+#[test]
+fn metadata_test() {
+    let source = "# Header\nnet demo\n# Source of tokens\npl p0 (1) # Initially marked\n\
+                  tr t0 p0 -> p1\n# About t0\nnt t0 1 {Fires once}\n# About the net\n\
+                  nt summary 0 {Two places}\n# Footer\n";
+    let net = pnets_tina::Parser::new(source.as_bytes()).parse().unwrap();
+    let p0 = net.get_index_by_name("p0").unwrap();
+    let t0 = net.get_index_by_name("t0").unwrap();
+    assert_eq!(net.metadata.comments(Anchor::Net), &[" Header".to_string()]);
+    assert_eq!(
+        net.metadata.comments(Anchor::Node(p0)),
+        &[
+            " Source of tokens".to_string(),
+            " Initially marked".to_string()
+        ]
+    );
+    assert!(net.metadata.comments(Anchor::Node(t0)).is_empty());
+    assert_eq!(
+        net.metadata.node_notes(t0)[0].comments,
+        vec![" About t0".to_string()]
+    );
+    assert_eq!(
+        net.metadata.notes[0].comments,
+        vec![" About the net".to_string()]
+    );
+    assert_eq!(net.metadata.comments(Anchor::End), &[" Footer".to_string()]);
+
+    // Notes and comments survive a round-trip
+    let mut out = vec![];
+    pnets_tina::ExporterBuilder::new(&mut out)
+        .build()
+        .export(&net)
+        .unwrap();
+    let exported = String::from_utf8(out).unwrap();
+    assert!(exported.starts_with("# Header\nnet {demo}\n"));
+    assert!(exported.contains("nt {t0} 1 {Fires once}\n"));
+    let reparsed = pnets_tina::Parser::new(exported.as_bytes())
+        .parse()
+        .unwrap();
+    assert_eq!(reparsed.metadata, net.metadata);
 }
 
 #[test]
 fn priority_test() {
     let parser = pnets_tina::Parser::new("".as_bytes());
     parser.parse().unwrap();
+
+    let parser = pnets_tina::Parser::new("pr a > b c\npr b > c\npr d < c".as_bytes());
+    let net = parser.parse().unwrap();
+    let a = net.get_index_by_name("a").unwrap().as_transition().unwrap();
+    let d = net.get_index_by_name("d").unwrap().as_transition().unwrap();
+    assert!(net[a].priorities.contains(&d));
+
+    let mut out = vec![];
+    pnets_tina::ExporterBuilder::new(&mut out)
+        .build()
+        .export(&net)
+        .unwrap();
+    let exported = String::from_utf8(out).unwrap();
+    assert!(exported.contains("pr {a} > {b} \n"));
+    assert!(exported.contains("pr {b} > {c} \n"));
+    assert!(exported.contains("pr {c} > {d} \n"));
+
+    let parser = pnets_tina::Parser::new("pr a > b\npr b > c\npr c > a".as_bytes());
+    let error = parser.parse().unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Cyclic priorities in Petri net: a > b > c > a"
+    );
 }
 
 #[test]
@@ -255,3 +331,151 @@ fn sokoban_3_test() {
     let parser = pnets_tina::Parser::new(include_str!("sokoban_3.net").as_bytes());
     parser.parse().unwrap();
 }
+
+#[test]
+fn stopwatch_test() {
+    let parser = pnets_tina::Parser::new("tr t0 [1,2] p0 p1!2 p2!-1 -> p3".as_bytes());
+    let net = parser.parse().unwrap();
+    let t0 = net
+        .get_index_by_name("t0")
+        .unwrap()
+        .as_transition()
+        .unwrap();
+    let p1 = net.get_index_by_name("p1").unwrap().as_place().unwrap();
+    let p2 = net.get_index_by_name("p2").unwrap().as_place().unwrap();
+    assert_eq!(
+        net[t0].stopwatches.iter().collect::<Vec<_>>(),
+        vec![&(p1, 2)]
+    );
+    assert_eq!(
+        net[t0].stopwatch_inhibitors.iter().collect::<Vec<_>>(),
+        vec![&(p2, 1)]
+    );
+    assert_eq!(net[p1].stopwatch_for[t0], 2);
+
+    let mut out = vec![];
+    pnets_tina::ExporterBuilder::new(&mut out)
+        .build()
+        .export(&net)
+        .unwrap();
+    let exported = String::from_utf8(out).unwrap();
+    assert!(exported.contains("{p1}!2 {p2}!-1 -> {p3}"));
+}
+
+#[test]
+fn rational_time_test() {
+    let parser =
+        pnets_tina::Parser::new("tr t0 [0.5, 3/2] p0 -> p1\ntr t0 ]1/4,1.25] p0 -> p1".as_bytes());
+    let net = parser.parse().unwrap();
+    let t0 = net
+        .get_index_by_name("t0")
+        .unwrap()
+        .as_transition()
+        .unwrap();
+    assert_eq!(
+        net[t0].time,
+        TimeRange {
+            start: Bound::Closed(Time::new(1, 2)),
+            end: Bound::Closed(Time::new(5, 4)),
+        }
+    );
+
+    let mut out = vec![];
+    pnets_tina::ExporterBuilder::new(&mut out)
+        .build()
+        .export(&net)
+        .unwrap();
+    let exported = String::from_utf8(out).unwrap();
+    assert!(exported.contains("[1/2,5/4]"));
+    let reparsed = pnets_tina::Parser::new(exported.as_bytes())
+        .parse()
+        .unwrap();
+    assert_eq!(reparsed[t0].time, net[t0].time);
+}
+
+#[test]
+fn diagnostics_test() {
+    let source =
+        "pl p0 (1)\ntr t0 p0 -x p1\ntr t1 [2,x] p0 -> p1\nfoo bar\ntr t2 p1 -> p0\npr t2 > t2\n";
+    let diagnostics = pnets_tina::Parser::new(source.as_bytes())
+        .parse_with_diagnostics()
+        .unwrap_err();
+    assert_eq!(diagnostics.len(), 4);
+
+    let span = diagnostics[0].span.as_ref().unwrap();
+    assert_eq!(span.position.line, 2);
+    assert_eq!(span.position.column, 11);
+    assert_eq!(&source[span.bytes.clone()], "x");
+    assert_eq!(
+        diagnostics[0].render(source),
+        "error: Invalid char found at line 2 column 11: expected >\n \
+         --> line 2, column 11\n  |\n2 | tr t0 p0 -x p1\n  |           ^\n"
+    );
+
+    assert_eq!(diagnostics[1].span.as_ref().unwrap().position.line, 3);
+
+    let span = diagnostics[2].span.as_ref().unwrap();
+    assert_eq!(span.position.line, 4);
+    assert_eq!(span.position.column, 1);
+    assert_eq!(&source[span.bytes.clone()], "foo");
+
+    // Cyclic priorities are about the whole net
+    assert!(diagnostics[3].span.is_none());
+    assert_eq!(
+        diagnostics[3].render(source),
+        "error: Cyclic priorities in Petri net: t2 > t2\n"
+    );
+
+    // The first error is returned by `parse`
+    let error = pnets_tina::Parser::new(source.as_bytes())
+        .parse()
+        .unwrap_err();
+    assert_eq!(error.to_string(), diagnostics[0].to_string());
+}
+
+#[test]
+fn large_input_test() {
+    let mut source = "net large\n".to_string();
+    for i in 0..10_000 {
+        source.push_str(&format!("tr t{} p{} -> {{p {}}} *(2K)\n", i, i, i + 1));
+    }
+    let net = pnets_tina::Parser::new(source.as_bytes()).parse().unwrap();
+    assert_eq!(net.transitions.len(), 10_000);
+    assert_eq!(net.places.len(), 20_000);
+    let tr = match net.get_index_by_name("t9999") {
+        Some(NodeId::Transition(tr)) => tr,
+        _ => panic!("t9999 is not a transition"),
+    };
+    assert_eq!(net[tr].produce.iter().next().unwrap().1, 2_000);
+}
+
+#[test]
+fn invalid_input_test() {
+    // Invalid utf-8 stops the parsing at its position
+    let source = b"net n\ntr t\xff0 p0 -> p1\n";
+    let diagnostics = pnets_tina::Parser::new(&source[..])
+        .parse_with_diagnostics()
+        .unwrap_err();
+    assert_eq!(diagnostics.len(), 1);
+    let span = diagnostics[0].span.as_ref().unwrap();
+    assert_eq!(span.position.line, 2);
+    assert_eq!(span.position.column, 5);
+    assert_eq!(span.bytes, 10..11);
+
+    let source = "pl p0 (99999999999999999999999)\ntr t {t1\n";
+    let diagnostics = pnets_tina::Parser::new(source.as_bytes())
+        .parse_with_diagnostics()
+        .unwrap_err();
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(
+        diagnostics[0].to_string(),
+        "Invalid char found at line 1 column 8: integer too large: 99999999999999999999999"
+    );
+    // An escaped identifier must be closed
+    assert_eq!(diagnostics[1].span.as_ref().unwrap().position.line, 2);
+
+    // Time ranges can not start at infinity
+    assert!(pnets_tina::Parser::new("tr t ]w,w[ p0 -> p1\n".as_bytes())
+        .parse()
+        .is_err());
+}
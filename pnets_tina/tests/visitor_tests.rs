@@ -0,0 +1,158 @@
+use std::error::Error;
+
+use pnets::timed::TimeRange;
+use pnets_tina::{ArcKind, Parser, Visitor};
+
+/// Record all events as strings
+#[derive(Default)]
+struct Recorder {
+    events: Vec<String>,
+}
+
+impl Visitor for Recorder {
+    fn net(&mut self, name: &str) -> Result<(), Box<dyn Error>> {
+        self.events.push(format!("net {}", name));
+        Ok(())
+    }
+
+    fn place(
+        &mut self,
+        name: &str,
+        label: Option<&str>,
+        marking: usize,
+    ) -> Result<(), Box<dyn Error>> {
+        self.events
+            .push(format!("pl {} {:?} {}", name, label, marking));
+        Ok(())
+    }
+
+    fn transition(
+        &mut self,
+        name: &str,
+        label: Option<&str>,
+        time: Option<TimeRange>,
+    ) -> Result<(), Box<dyn Error>> {
+        self.events.push(format!(
+            "tr {} {:?} {}",
+            name,
+            label,
+            time.map_or("-".to_string(), |time| time.to_string())
+        ));
+        Ok(())
+    }
+
+    fn arc(
+        &mut self,
+        place: &str,
+        transition: &str,
+        kind: ArcKind,
+        weight: usize,
+    ) -> Result<(), Box<dyn Error>> {
+        self.events.push(format!(
+            "arc {} {} {:?} {}",
+            place, transition, kind, weight
+        ));
+        Ok(())
+    }
+
+    fn priority(&mut self, higher: &[&str], lower: &[&str]) -> Result<(), Box<dyn Error>> {
+        self.events.push(format!("pr {:?} {:?}", higher, lower));
+        Ok(())
+    }
+
+    fn label(&mut self, node: &str, label: &str) -> Result<(), Box<dyn Error>> {
+        self.events.push(format!("lb {} {}", node, label));
+        Ok(())
+    }
+
+    fn note(&mut self, name: &str, justification: usize, text: &str) -> Result<(), Box<dyn Error>> {
+        self.events
+            .push(format!("nt {} {} {}", name, justification, text));
+        Ok(())
+    }
+
+    fn comment(&mut self, comment: &str, inline: bool) -> Result<(), Box<dyn Error>> {
+        self.events.push(format!("# {} {}", comment, inline));
+        Ok(())
+    }
+}
+
+#[test]
+fn events_test() {
+    let source = "# header\nnet n\ntr t0 : a [1,2] p0 ?-3 -> p1 # inline\npl p1 (2K) t1 -> t0 !1\n\
+                  pr t0 < t1 t2\nlb p0 {b c}\nnt n1 0 {text}\n";
+    let mut recorder = Recorder::default();
+    Parser::new(source.as_bytes()).visit(&mut recorder).unwrap();
+    assert_eq!(
+        recorder.events,
+        vec![
+            "#  header false",
+            "net n",
+            "tr t0 Some(\"a\") [1,2]",
+            "arc p0 t0 Inhibitor 3",
+            "arc p1 t0 Produce 1",
+            "#  inline true",
+            "pl p1 None 2000",
+            "arc p1 t1 Produce 1",
+            "arc p1 t0 StopWatch 1",
+            "pr [\"t1\", \"t2\"] [\"t0\"]",
+            "lb p0 b c",
+            "nt n1 0 text",
+        ]
+    );
+}
+
+/// Count the arcs of a net without building it
+#[derive(Default)]
+struct ArcCounter {
+    arcs: usize,
+}
+
+impl Visitor for ArcCounter {
+    fn arc(&mut self, _: &str, _: &str, _: ArcKind, _: usize) -> Result<(), Box<dyn Error>> {
+        self.arcs += 1;
+        Ok(())
+    }
+}
+
+#[test]
+fn count_test() {
+    let mut counter = ArcCounter::default();
+    Parser::new(include_str!("sokoban_3.net").as_bytes())
+        .visit(&mut counter)
+        .unwrap();
+    let net = Parser::new(include_str!("sokoban_3.net").as_bytes())
+        .parse()
+        .unwrap();
+    let arcs: usize = net
+        .transitions
+        .iter()
+        .map(|tr| tr.consume.len() + tr.produce.len() + tr.conditions.len() + tr.inhibitors.len())
+        .sum();
+    assert_eq!(counter.arcs, arcs);
+}
+
+/// Reject places with a marking
+struct Unmarked;
+
+impl Visitor for Unmarked {
+    fn place(&mut self, name: &str, _: Option<&str>, marking: usize) -> Result<(), Box<dyn Error>> {
+        if marking > 0 {
+            Err(format!("{} is marked", name).into())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[test]
+fn visitor_error_test() {
+    let source = "pl p0\npl p1 (1)\npl p2 (2)\n";
+    let diagnostics = Parser::new(source.as_bytes())
+        .visit_with_diagnostics(&mut Unmarked)
+        .unwrap_err();
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].to_string(), "p1 is marked");
+    assert_eq!(diagnostics[0].span.as_ref().unwrap().position.line, 2);
+    assert_eq!(diagnostics[1].span.as_ref().unwrap().position.line, 3);
+}
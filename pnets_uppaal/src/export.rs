@@ -0,0 +1,402 @@
+use std::error::Error;
+use std::io::Write;
+
+use pnets::timed::priority::PriorityGraph;
+use pnets::timed::{Bound, Net, Time, Transition};
+use pnets::{arc, NetError, NodeId, TransitionId};
+
+/// Exporter to the XML format of [UPPAAL](https://uppaal.org)
+///
+/// A [`Net`] is translated into a network of timed automata with one automaton and one clock
+/// per transition. The marking is a global array `m` of integers, so the net must be bounded
+/// and its places must not contain more tokens than UPPAAL integers.
+///
+/// The automaton of a transition is `enabled` or `disabled`, with the upper bound of its time
+/// range as invariant of `enabled`. It fires by consuming tokens, then broadcasts `update` so
+/// transitions disabled by the consumption become `disabled`, produces tokens and broadcasts
+/// `check` so newly enabled transitions reset their clock and transitions disabled by the
+/// production become `disabled`. A transition with priority over
+/// another one prevents its firing when its own clock is in its time range.
+///
+/// UPPAAL constants are integers, clocks count the net time multiplied by `TIME_SCALE`: the
+/// least common multiple of the denominators of the bounds of time ranges.
+pub struct Exporter<'w> {
+    writer: &'w mut dyn Write,
+}
+
+/// Greatest common divisor
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Name of a transition which is a valid UPPAAL identifier
+fn identifier(net: &Net, tr: TransitionId) -> String {
+    let name: String = net
+        .get_name_by_index(&NodeId::Transition(tr))
+        .unwrap_or_default()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("t{}_{}", tr, name)
+}
+
+/// Escape XML special characters
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\n', " ")
+}
+
+/// Condition on the marking under which a transition is enabled
+fn enabled(transition: &Transition) -> String {
+    let mut conditions = vec![];
+    for &(pl, w) in transition
+        .consume
+        .iter()
+        .chain(transition.conditions.iter())
+    {
+        conditions.push(format!("m[{}] >= {}", pl, w));
+    }
+    for &(pl, w) in transition.inhibitors.iter() {
+        conditions.push(format!("m[{}] < {}", pl, w));
+    }
+    if conditions.is_empty() {
+        "true".to_string()
+    } else {
+        conditions.join(" && ")
+    }
+}
+
+impl<'w> Exporter<'w> {
+    /// Create a new exporter which writes in `writer`
+    pub fn new(writer: &'w mut dyn Write) -> Self {
+        Self { writer }
+    }
+
+    /// Least common multiple of the denominators of the bounds of time ranges
+    fn time_scale(net: &Net) -> u64 {
+        let mut scale = 1;
+        for transition in net.transitions.iter() {
+            for bound in &[transition.time.start, transition.time.end] {
+                if let Bound::Closed(v) | Bound::Open(v) = bound {
+                    scale = scale / gcd(scale, *v.denom()) * v.denom();
+                }
+            }
+        }
+        scale
+    }
+
+    /// Constant of a bound in clock units
+    fn scaled(v: Time, scale: u64) -> u64 {
+        (v * Time::from_integer(scale)).to_integer()
+    }
+
+    /// Export a timed net
+    ///
+    /// # Errors
+    /// Return [`NetError::UnsupportedArc`] if the net has stopwatch arcs,
+    /// [`NetError::InvalidTimeRange`] if a time range has an infinite start and
+    /// [`NetError::CyclicPriorities`] if priorities are cyclic
+    pub fn export(&mut self, net: &Net) -> Result<(), Box<dyn Error>> {
+        for transition in net.transitions.iter() {
+            if let Some(&(pl, w)) = transition.stopwatches.iter().next() {
+                return Err(Box::new(NetError::UnsupportedArc(arc::Kind::StopWatch(
+                    pl,
+                    transition.id(),
+                    w,
+                ))));
+            }
+            if let Some(&(pl, w)) = transition.stopwatch_inhibitors.iter().next() {
+                return Err(Box::new(NetError::UnsupportedArc(
+                    arc::Kind::StopWatchInhibitor(pl, transition.id(), w),
+                )));
+            }
+            if transition.time.start == Bound::Infinity {
+                return Err(Box::new(NetError::InvalidTimeRange));
+            }
+        }
+        let scale = Self::time_scale(net);
+        let marking = net.initial_marking();
+
+        self.writer.write_all(
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n\
+             <!DOCTYPE nta PUBLIC '-//Uppaal Team//DTD Flat System 1.1//EN' \
+             'http://www.it.uu.se/research/group/darts/uppaal/flat-1_2.dtd'>\n\
+             <nta>\n<declaration>\n"
+                .as_ref(),
+        )?;
+        if !net.name.is_empty() {
+            self.writer
+                .write_all(format!("// Net {}\n", escape(&net.name)).as_ref())?;
+        }
+        self.writer.write_all(
+            format!(
+                "// A time unit of the net is TIME_SCALE clock units\n\
+                 const int TIME_SCALE = {};\n",
+                scale
+            )
+            .as_ref(),
+        )?;
+        let names: Vec<String> = net
+            .places
+            .iter()
+            .map(|place| {
+                escape(
+                    &net.get_name_by_index(&NodeId::Place(place.id()))
+                        .unwrap_or_default(),
+                )
+            })
+            .collect();
+        let initial: Vec<String> = net
+            .places
+            .iter()
+            .map(|place| marking[place.id()].to_string())
+            .collect();
+        self.writer.write_all(
+            format!(
+                "// Marking of places {}\nint m[{}] = {{{}}};\n",
+                names.join(", "),
+                net.places.len().max(1),
+                if initial.is_empty() {
+                    "0".to_string()
+                } else {
+                    initial.join(", ")
+                }
+            )
+            .as_ref(),
+        )?;
+        // Disconnected transitions are never enabled and have no automaton
+        let enabled_initially: Vec<bool> = net
+            .transitions
+            .iter()
+            .map(|tr| !tr.is_disconnected() && tr.is_enabled(&marking))
+            .collect();
+        self.writer.write_all(
+            format!(
+                "// Clock and enabling of each transition\nclock x[{}];\nbool en[{}] = {{{}}};\n\
+                 broadcast chan update, check;\n</declaration>\n",
+                net.transitions.len().max(1),
+                net.transitions.len().max(1),
+                if enabled_initially.is_empty() {
+                    "false".to_string()
+                } else {
+                    enabled_initially
+                        .iter()
+                        .map(bool::to_string)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                }
+            )
+            .as_ref(),
+        )?;
+
+        let priorities = net.priority_graph().transitive_closure()?;
+        let mut processes = vec![];
+        for ((tr, transition), &initial) in net
+            .transitions
+            .iter_enumerated()
+            .zip(enabled_initially.iter())
+        {
+            if transition.is_disconnected() {
+                continue;
+            }
+            self.export_transition(net, &priorities, tr, transition, scale, initial)?;
+            processes.push(identifier(net, tr));
+        }
+
+        self.writer.write_all(
+            format!(
+                "<system>system {};</system>\n<queries/>\n</nta>\n",
+                processes.join(", ")
+            )
+            .as_ref(),
+        )?;
+        Ok(())
+    }
+
+    /// Export the automaton of a transition
+    fn export_transition(
+        &mut self,
+        net: &Net,
+        priorities: &PriorityGraph,
+        tr: TransitionId,
+        transition: &Transition,
+        scale: u64,
+        enabled_initially: bool,
+    ) -> Result<(), Box<dyn Error>> {
+        let clock = format!("x[{}]", tr);
+        let id = |location: &str| format!("{}_{}", identifier(net, tr), location);
+        self.writer.write_all(
+            format!(
+                "<template>\n<name>{}</name>\n\
+                 <location id=\"{}\"><name>disabled</name></location>\n",
+                identifier(net, tr),
+                id("disabled")
+            )
+            .as_ref(),
+        )?;
+        let invariant = match transition.time.end {
+            Bound::Closed(b) => format!("{} &lt;= {}", clock, Self::scaled(b, scale)),
+            Bound::Open(b) => format!("{} &lt; {}", clock, Self::scaled(b, scale)),
+            Bound::Infinity => String::new(),
+        };
+        self.writer.write_all(
+            format!(
+                "<location id=\"{}\"><name>enabled</name>{}</location>\n\
+                 <location id=\"{}\"><name>fired</name><committed/></location>\n\
+                 <location id=\"{}\"><name>check</name><committed/></location>\n\
+                 <init ref=\"{}\"/>\n",
+                id("enabled"),
+                if invariant.is_empty() {
+                    String::new()
+                } else {
+                    format!("<label kind=\"invariant\">{}</label>", invariant)
+                },
+                id("fired"),
+                id("check"),
+                id(if enabled_initially {
+                    "enabled"
+                } else {
+                    "disabled"
+                })
+            )
+            .as_ref(),
+        )?;
+
+        let lower = match transition.time.start {
+            Bound::Closed(a) => format!("{} &gt;= {}", clock, Self::scaled(a, scale)),
+            Bound::Open(a) => format!("{} &gt; {}", clock, Self::scaled(a, scale)),
+            Bound::Infinity => return Err(Box::new(NetError::InvalidTimeRange)),
+        };
+        // The marking is tested again when firing, the transition may have been disabled by a
+        // production which `update?` does not see
+        let condition = escape(&enabled(transition));
+        // A transition with priority is either disabled or before its earliest firing time,
+        // each combination of these alternatives is an edge
+        let mut guards = vec![format!("{} &amp;&amp; ({})", lower, condition)];
+        for other in net.transitions.iter() {
+            if other.is_disconnected() || !priorities.has_priority(other.id(), tr) {
+                continue;
+            }
+            let before = match other.time.start {
+                Bound::Closed(a) if a > Time::from_integer(0) => {
+                    Some(format!("x[{}] &lt; {}", other.id(), Self::scaled(a, scale)))
+                }
+                Bound::Open(a) => Some(format!(
+                    "x[{}] &lt;= {}",
+                    other.id(),
+                    Self::scaled(a, scale)
+                )),
+                _ => None,
+            };
+            let mut next = vec![];
+            for guard in &guards {
+                next.push(format!("{} &amp;&amp; !en[{}]", guard, other.id()));
+                if let Some(before) = &before {
+                    next.push(format!(
+                        "{} &amp;&amp; en[{}] &amp;&amp; {}",
+                        guard,
+                        other.id(),
+                        before
+                    ));
+                }
+            }
+            guards = next;
+        }
+
+        let mut consume: Vec<String> = transition
+            .consume
+            .iter()
+            .map(|&(pl, w)| format!("m[{}] -= {}", pl, w))
+            .collect();
+        // Reset places are emptied after the consumption
+        consume.extend(
+            transition
+                .resets
+                .iter()
+                .map(|&(pl, _)| format!("m[{}] = 0", pl)),
+        );
+        consume.push(format!("en[{}] = false", tr));
+        let produce: Vec<String> = transition
+            .produce
+            .iter()
+            .map(|&(pl, w)| format!("m[{}] += {}", pl, w))
+            .collect();
+        let reset = format!("{} = 0, en[{}] = true", clock, tr);
+
+        for guard in &guards {
+            self.edge(&id("enabled"), &id("fired"), guard, "", &consume.join(", "))?;
+        }
+        self.edge(
+            &id("fired"),
+            &id("check"),
+            "",
+            "update!",
+            &produce.join(", "),
+        )?;
+        self.edge(&id("check"), &id("enabled"), &condition, "check!", &reset)?;
+        self.edge(
+            &id("check"),
+            &id("disabled"),
+            &format!("!({})", condition),
+            "check!",
+            "",
+        )?;
+        // Consumptions are seen on `update?`, productions which disable the transition through
+        // an inhibitor arc are only seen on `check?`
+        for synchronisation in &["update?", "check?"] {
+            self.edge(
+                &id("enabled"),
+                &id("disabled"),
+                &format!("!({})", condition),
+                synchronisation,
+                &format!("en[{}] = false", tr),
+            )?;
+        }
+        self.edge(
+            &id("disabled"),
+            &id("enabled"),
+            &condition,
+            "check?",
+            &reset,
+        )?;
+        self.writer.write_all("</template>\n".as_ref())?;
+        Ok(())
+    }
+
+    /// Write an edge, empty labels are omitted
+    fn edge(
+        &mut self,
+        source: &str,
+        target: &str,
+        guard: &str,
+        synchronisation: &str,
+        assignment: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        self.writer.write_all(
+            format!(
+                "<transition><source ref=\"{}\"/><target ref=\"{}\"/>",
+                source, target
+            )
+            .as_ref(),
+        )?;
+        for (kind, label) in &[
+            ("guard", guard),
+            ("synchronisation", synchronisation),
+            ("assignment", assignment),
+        ] {
+            if !label.is_empty() {
+                self.writer
+                    .write_all(format!("<label kind=\"{}\">{}</label>", kind, label).as_ref())?;
+            }
+        }
+        self.writer.write_all("</transition>\n".as_ref())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,7 @@
+#![forbid(missing_docs)]
+//! This crate provides an exporter of time Petri nets to the XML format of
+//! [UPPAAL](https://uppaal.org) for [pnets](https://crates.io/crates/pnets) framework.
+
+pub use export::Exporter;
+
+mod export;
@@ -0,0 +1,138 @@
+use pnets::arc::Kind;
+use pnets::timed::Net;
+use pnets::{NetError, NodeId};
+use pnets_uppaal::Exporter;
+
+/// Net with `a` [1/2,2] moving a token from `p` to `q`, then `b` ]1,w[ moving it back, `c` has
+/// priority over `b`
+fn net() -> Net {
+    let mut net = Net::default();
+    let p = net.create_place();
+    let q = net.create_place();
+    let a = net.create_transition();
+    let b = net.create_transition();
+    let c = net.create_transition();
+    net.rename_node(NodeId::Place(p), "p").unwrap();
+    net.rename_node(NodeId::Place(q), "q").unwrap();
+    net.rename_node(NodeId::Transition(a), "a").unwrap();
+    net.rename_node(NodeId::Transition(b), "b").unwrap();
+    net.rename_node(NodeId::Transition(c), "c.1").unwrap();
+    net[p].initial = 1;
+    net[a].time = "[1/2,2]".parse().unwrap();
+    net[b].time = "]1,w[".parse().unwrap();
+    net[c].time = "[3,3]".parse().unwrap();
+    net.add_arc(Kind::Consume(p, a, 1)).unwrap();
+    net.add_arc(Kind::Produce(q, a, 1)).unwrap();
+    net.add_arc(Kind::Consume(q, b, 1)).unwrap();
+    net.add_arc(Kind::Produce(p, b, 1)).unwrap();
+    net.add_arc(Kind::Consume(q, c, 2)).unwrap();
+    net.add_priority(c, b);
+    net
+}
+
+fn export(net: &Net) -> Result<String, Box<dyn std::error::Error>> {
+    let mut buffer = Vec::new();
+    Exporter::new(&mut buffer).export(net)?;
+    Ok(String::from_utf8(buffer).unwrap())
+}
+
+#[test]
+fn declaration_test() {
+    let xml = export(&net()).unwrap();
+    assert!(xml.starts_with("<?xml"));
+    assert!(xml.contains("const int TIME_SCALE = 2;"));
+    assert!(xml.contains("int m[2] = {1, 0};"));
+    assert!(xml.contains("clock x[3];"));
+    assert!(xml.contains("bool en[3] = {true, false, false};"));
+    assert!(xml.contains("<system>system t0_a, t1_b, t2_c_1;</system>"));
+    assert!(xml.trim_end().ends_with("</nta>"));
+}
+
+#[test]
+fn template_test() {
+    let xml = export(&net()).unwrap();
+    assert!(xml.contains("<init ref=\"t0_a_enabled\"/>"));
+    assert!(xml.contains("<init ref=\"t1_b_disabled\"/>"));
+    // Bounds are scaled by TIME_SCALE
+    assert!(xml.contains("<label kind=\"invariant\">x[0] &lt;= 4</label>"));
+    // The marking is tested again when firing
+    assert!(xml.contains("<label kind=\"guard\">x[0] &gt;= 1 &amp;&amp; (m[0] &gt;= 1)</label>"));
+    assert!(xml.contains("<label kind=\"assignment\">m[0] -= 1, en[0] = false</label>"));
+    assert!(xml.contains("<label kind=\"assignment\">m[1] += 1</label>"));
+    assert!(xml.contains("<label kind=\"guard\">m[1] &gt;= 2</label>"));
+    // No invariant without upper bound
+    assert!(xml.contains("<location id=\"t1_b_enabled\"><name>enabled</name></location>"));
+}
+
+#[test]
+fn priority_test() {
+    let xml = export(&net()).unwrap();
+    assert!(xml.contains(
+        "<label kind=\"guard\">x[1] &gt; 2 &amp;&amp; (m[1] &gt;= 1) &amp;&amp; !en[2]</label>"
+    ));
+    assert!(xml.contains(
+        "<label kind=\"guard\">x[1] &gt; 2 &amp;&amp; (m[1] &gt;= 1) &amp;&amp; en[2] \
+         &amp;&amp; x[2] &lt; 6</label>"
+    ));
+}
+
+#[test]
+fn inhibitor_test() {
+    // `a` produces in `q`, which inhibits `d`
+    let mut net = net();
+    let q = match net.get_index_by_name("q") {
+        Some(NodeId::Place(q)) => q,
+        _ => panic!("q must be a place"),
+    };
+    let d = net.create_transition();
+    net.rename_node(NodeId::Transition(d), "d").unwrap();
+    net.add_arc(Kind::Inhibitor(q, d, 1)).unwrap();
+    let xml = export(&net).unwrap();
+    assert!(xml.contains("<init ref=\"t3_d_enabled\"/>"));
+    // `d` is disabled by the `check!` broadcast which follows the production of `a`
+    assert!(xml.contains(
+        "<transition><source ref=\"t3_d_enabled\"/><target ref=\"t3_d_disabled\"/>\
+         <label kind=\"guard\">!(m[1] &lt; 1)</label>\
+         <label kind=\"synchronisation\">check?</label>\
+         <label kind=\"assignment\">en[3] = false</label></transition>"
+    ));
+    // and can not fire after this production
+    assert!(xml.contains("<label kind=\"guard\">x[3] &gt;= 0 &amp;&amp; (m[1] &lt; 1)</label>"));
+}
+
+#[test]
+fn reset_test() {
+    // `c` also empties `p`
+    let mut net = net();
+    let (p, c) = match (net.get_index_by_name("p"), net.get_index_by_name("c.1")) {
+        (Some(NodeId::Place(p)), Some(NodeId::Transition(c))) => (p, c),
+        _ => panic!("p and c.1 must be nodes of the net"),
+    };
+    net.add_arc(Kind::Reset(p, c, 1)).unwrap();
+    let xml = export(&net).unwrap();
+    assert!(xml.contains("<label kind=\"assignment\">m[1] -= 2, m[0] = 0, en[2] = false</label>"));
+}
+
+#[test]
+fn unsupported_test() {
+    let mut net = net();
+    let p = net.create_place();
+    let tr = net.create_transition();
+    net.add_arc(Kind::StopWatch(p, tr, 1)).unwrap();
+    let err = export(&net).unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<NetError>(),
+        Some(&NetError::UnsupportedArc(Kind::StopWatch(p, tr, 1)))
+    );
+
+    let mut net = self::net();
+    let p = net.create_place();
+    let tr = net.create_transition();
+    net[tr].time = "[2,3]".parse().unwrap();
+    net[tr].time.start = pnets::timed::Bound::Infinity;
+    net.add_arc(Kind::Consume(p, tr, 1)).unwrap();
+    assert_eq!(
+        export(&net).unwrap_err().downcast_ref::<NetError>(),
+        Some(&NetError::InvalidTimeRange)
+    );
+}